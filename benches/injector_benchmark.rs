@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use television::matcher::{config::Config, Matcher};
+
+const ITEM_COUNT: usize = 100_000;
+
+fn items() -> Vec<String> {
+    (0..ITEM_COUNT)
+        .map(|i| format!("some/path/to/a/file_{i}.rs"))
+        .collect()
+}
+
+pub fn injector_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("injector");
+
+    group.bench_function("push", |b| {
+        b.iter_batched(
+            || (Matcher::<String>::new(Config::default()), items()),
+            |(matcher, items)| {
+                let injector = matcher.injector();
+                for item in items {
+                    injector.push(item, |e, cols| {
+                        cols[0] = e.clone().into();
+                    });
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("push_batch", |b| {
+        b.iter_batched(
+            || (Matcher::<String>::new(Config::default()), items()),
+            |(matcher, items)| {
+                let injector = matcher.injector();
+                injector.push_batch(items, |e, cols| {
+                    cols[0] = e.clone().into();
+                });
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, injector_benchmark);
+criterion_main!(benches);
@@ -18,6 +18,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
     let entries = [
         Entry {
             name: "typeshed/LICENSE".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -35,6 +36,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/README.md".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -52,6 +54,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/re.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -69,6 +72,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/io.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -86,6 +90,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/gc.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -103,6 +108,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/uu.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -120,6 +126,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/nt.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -137,6 +144,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/dis.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -154,6 +162,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/imp.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -171,6 +180,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/bdb.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -188,6 +198,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/abc.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -205,6 +216,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/cgi.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -222,6 +234,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/bz2.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -239,6 +252,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/grp.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -256,6 +270,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/ast.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -273,6 +288,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/csv.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -290,6 +306,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/pdb.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -307,6 +324,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/pwd.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -324,6 +342,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/ssl.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -341,6 +360,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/tty.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -358,6 +378,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/nis.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -375,6 +396,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/pty.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -392,6 +414,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/cmd.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -409,6 +432,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/tests/utils.py".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -426,6 +450,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/pyproject.toml".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -443,6 +468,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/MAINTAINERS.md".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -460,6 +486,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/enum.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -477,6 +504,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/hmac.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -494,6 +522,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/uuid.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -511,6 +540,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/glob.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -528,6 +558,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/_ast.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -545,6 +576,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/_csv.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -562,6 +594,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/code.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -579,6 +612,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/spwd.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -596,6 +630,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/_msi.pyi".to_string(),
+            display: None,
             value: None,
             name_match_ranges: Some(merge_ranges(&[
                 (0, 1),
@@ -613,6 +648,7 @@ pub fn results_list_benchmark(c: &mut Criterion) {
         },
         Entry {
             name: "typeshed/stdlib/time.pyi".to_string(),
+            display: None,
             value: None,
 
             icon: Some(FileIcon {
@@ -658,6 +694,10 @@ pub fn results_list_benchmark(c: &mut Criterion) {
                 false,
                 &mut icon_color_cache,
                 &colorscheme,
+                None,
+                None,
+                None,
+                &[],
             );
         });
     });
@@ -263,6 +263,66 @@ fn impl_tv_channel(ast: &syn::DeriveInput) -> TokenStream {
                 }
             }
 
+            fn loading(&self) -> bool {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref channel) => {
+                            channel.loading()
+                        }
+                    )*
+                }
+            }
+
+            fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref mut channel) => {
+                            channel.set_matching_scheme(scheme)
+                        }
+                    )*
+                }
+            }
+
+            fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref mut channel) => {
+                            channel.set_prefix_bonus(prefix_bonus)
+                        }
+                    )*
+                }
+            }
+
+            fn set_filter(&mut self, filter: Option<Regex>) {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref mut channel) => {
+                            channel.set_filter(filter)
+                        }
+                    )*
+                }
+            }
+
+            fn set_paused(&mut self, paused: bool) {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref mut channel) => {
+                            channel.set_paused(paused)
+                        }
+                    )*
+                }
+            }
+
+            fn wait_for_matches(&mut self, timeout: std::time::Duration) {
+                match self {
+                    #(
+                        #enum_name::#variant_names(ref mut channel) => {
+                            channel.wait_for_matches(timeout)
+                        }
+                    )*
+                }
+            }
+
             fn shutdown(&self) {
                 match self {
                     #(
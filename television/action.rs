@@ -7,6 +7,11 @@ pub enum Action {
     /// Add a character to the input buffer.
     #[serde(skip)]
     AddInputChar(char),
+    /// Insert a literal string at the cursor position in the input buffer.
+    ///
+    /// Bound in the configuration as `"insert:<string>" = "<key>"`.
+    #[serde(skip)]
+    InsertString(String),
     /// Delete the character before the cursor from the input buffer.
     #[serde(skip)]
     DeletePrevChar,
@@ -16,12 +21,24 @@ pub enum Action {
     /// Delete the character after the cursor from the input buffer.
     #[serde(skip)]
     DeleteNextChar,
+    /// Delete the next word from the input buffer.
+    #[serde(skip)]
+    DeleteNextWord,
     /// Move the cursor to the character before the current cursor position.
     #[serde(skip)]
     GoToPrevChar,
     /// Move the cursor to the character after the current cursor position.
     #[serde(skip)]
     GoToNextChar,
+    /// Move the cursor to the start of the previous word.
+    #[serde(skip)]
+    GoToPrevWord,
+    /// Move the cursor to the start of the next word.
+    #[serde(skip)]
+    GoToNextWord,
+    /// Clear the whole input buffer.
+    #[serde(alias = "clear_query")]
+    ClearQuery,
     /// Move the cursor to the start of the input buffer.
     #[serde(alias = "go_to_input_start")]
     GoToInputStart,
@@ -45,6 +62,9 @@ pub enum Action {
     /// Add entry under cursor to the list of selected entries and move the cursor up.
     #[serde(alias = "toggle_selection_up")]
     ToggleSelectionUp,
+    /// Replace the selection with all matched entries that aren't selected.
+    #[serde(alias = "invert_selection")]
+    InvertSelection,
     /// Confirm current selection (multi select or entry under cursor).
     #[serde(alias = "select_entry")]
     #[serde(alias = "confirm_selection")]
@@ -56,12 +76,47 @@ pub enum Action {
     /// Select the entry currently under the cursor and exit the application.
     #[serde(alias = "select_and_exit")]
     SelectAndExit,
+    /// Exit the application and only print the current query, regardless of
+    /// the selection.
+    #[serde(alias = "abort_with_query")]
+    AbortWithQuery,
+    /// Start typing a pattern to search for in the preview panel.
+    #[serde(alias = "preview_search")]
+    PreviewSearch,
+    /// Scroll the preview to the next line matching the search pattern.
+    #[serde(alias = "preview_search_next")]
+    PreviewSearchNext,
+    /// Scroll the preview to the previous line matching the search pattern.
+    #[serde(alias = "preview_search_prev")]
+    PreviewSearchPrev,
+    /// Confirm the preview search pattern being typed.
+    #[serde(skip)]
+    ConfirmPreviewSearch,
+    /// Stop typing the preview search pattern.
+    #[serde(skip)]
+    CancelPreviewSearch,
+    /// Start editing the regex that entries must match to be listed,
+    /// whatever the query.
+    #[serde(alias = "edit_filter_pattern")]
+    EditFilterPattern,
+    /// Apply the filter pattern being edited.
+    #[serde(skip)]
+    ConfirmFilterPattern,
+    /// Stop editing the filter pattern and keep the previous one.
+    #[serde(skip)]
+    CancelFilterPattern,
     /// Select the next entry in the currently focused list.
     #[serde(alias = "select_next_entry")]
     SelectNextEntry,
     /// Select the previous entry in the currently focused list.
     #[serde(alias = "select_prev_entry")]
     SelectPrevEntry,
+    /// Confirm the Nth entry of the currently focused list, counting from 1
+    /// for the first listed entry.
+    ///
+    /// Bound in the configuration as `"confirm_entry:<n>" = "<key>"`.
+    #[serde(skip)]
+    ConfirmEntry(u16),
     /// Select the next page of entries in the currently focused list.
     #[serde(alias = "select_next_page")]
     SelectNextPage,
@@ -71,6 +126,21 @@ pub enum Action {
     /// Copy the currently selected entry to the clipboard.
     #[serde(alias = "copy_entry_to_clipboard")]
     CopyEntryToClipboard,
+    /// Copy the selected entries to the clipboard and exit the application
+    /// as `SelectAndExit` does.
+    #[serde(alias = "yank_and_exit")]
+    YankAndExit,
+    /// Write all currently matched entries to the export file.
+    #[serde(alias = "export_results")]
+    ExportResults,
+    /// Dump the next rendered frame and the current state to the log file
+    /// (only available in debug mode).
+    #[serde(alias = "dump_frame")]
+    DumpFrame,
+    /// Reload the configuration file, keeping the current channel, query and
+    /// results.
+    #[serde(alias = "reload_config")]
+    ReloadConfig,
     // preview actions
     /// Scroll the preview up by one line.
     #[serde(alias = "scroll_preview_up")]
@@ -106,6 +176,32 @@ pub enum Action {
     /// Toggle the preview panel.
     #[serde(alias = "toggle_preview")]
     TogglePreview,
+    /// Toggle wrapping of long lines in the preview panel.
+    #[serde(alias = "toggle_preview_wrap")]
+    TogglePreviewWrap,
+    /// Toggle anchoring the preview scroll to the last line.
+    #[serde(alias = "toggle_preview_tail")]
+    TogglePreviewTail,
+    /// Replace the channel with one listing the entries of the selected
+    /// entry (using the channel's `drill_command`).
+    #[serde(alias = "drill_into")]
+    DrillInto,
+    /// Go back to the channel left by the last `DrillInto`.
+    #[serde(alias = "drill_back")]
+    DrillBack,
+    /// Freeze the results and stop computing previews, or resume.
+    #[serde(alias = "toggle_pause")]
+    TogglePause,
+    /// Run the channel's source command again (e.g. to restart a streaming
+    /// source), keeping the query.
+    #[serde(alias = "reload_source")]
+    ReloadSource,
+    /// Exchange the space given to the results and preview panels.
+    #[serde(alias = "swap_panels")]
+    SwapPanels,
+    /// Toggle the multi-line query mode of the input bar.
+    #[serde(alias = "toggle_multiline_input")]
+    ToggleMultilineInput,
     /// Signal an error with the given message.
     #[serde(skip)]
     Error(String),
@@ -1,29 +1,50 @@
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::screen::mode::Mode;
 use color_eyre::Result;
+use regex::Regex;
 use tokio::sync::{mpsc, Mutex};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::channels::entry::Entry;
 use crate::channels::TelevisionChannel;
-use crate::config::{parse_key, Config};
+use crate::config::{parse_key, Config, EventBindings};
 use crate::keymap::Keymap;
-use crate::television::Television;
+use crate::matcher::config::MatchingScheme;
+use crate::television::{GotoTarget, Television};
 use crate::{
     action::Action,
     event::{Event, EventLoop, Key},
     render::{render, RenderingTask},
 };
 
+/// Loads the configuration again when reloading it at runtime.
+pub type ConfigLoader = Box<dyn Fn() -> Result<Config> + Send + Sync>;
+
 /// The main application struct that holds the state of the application.
+#[allow(clippy::struct_excessive_bools)]
 pub struct App {
     keymap: Keymap,
+    /// The `--passthrough-keybindings` keys, kept to rebuild the keymap.
+    passthrough_keybindings: Vec<String>,
+    /// Used by the `ReloadConfig` action (if set).
+    config_loader: Option<ConfigLoader>,
+    /// Whether to compute the rank of the selected entries when exiting.
+    entry_ranks: bool,
+    /// Actions bound to terminal events such as focus changes or resizes.
+    event_bindings: EventBindings,
     // maybe move these two into config instead of passing them
     // via the cli?
     tick_rate: f64,
     frame_rate: f64,
+    /// How long to wait without user input before exiting (if set).
+    idle_timeout: Option<Duration>,
+    /// When the last user input was received.
+    last_input: Instant,
+    /// Whether debugging actions are enabled.
+    debug: bool,
     /// The television instance that handles channels and entries.
     television: Arc<Mutex<Television>>,
     /// A flag that indicates whether the application should quit during the next frame.
@@ -56,6 +77,9 @@ pub enum ActionOutcome {
 pub struct AppOutput {
     pub selected_entries: Option<FxHashSet<Entry>>,
     pub passthrough: Option<String>,
+    /// The rank of the selected entries among the matched entries (only
+    /// computed if requested with `App::with_entry_ranks`).
+    pub entry_ranks: FxHashMap<Entry, u32>,
 }
 
 impl From<ActionOutcome> for AppOutput {
@@ -64,29 +88,39 @@ impl From<ActionOutcome> for AppOutput {
             ActionOutcome::Entries(entries) => Self {
                 selected_entries: Some(entries),
                 passthrough: None,
+                entry_ranks: FxHashMap::default(),
             },
             ActionOutcome::Input(input) => Self {
                 selected_entries: None,
                 passthrough: Some(input),
+                entry_ranks: FxHashMap::default(),
             },
             ActionOutcome::Passthrough(entries, key) => Self {
                 selected_entries: Some(entries),
                 passthrough: Some(key),
+                entry_ranks: FxHashMap::default(),
             },
             ActionOutcome::None => Self {
                 selected_entries: None,
                 passthrough: None,
+                entry_ranks: FxHashMap::default(),
             },
         }
     }
 }
 
 impl App {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         channel: TelevisionChannel,
         config: Config,
         passthrough_keybindings: &[String],
         input: Option<String>,
+        goto: Option<GotoTarget>,
+        sync: bool,
+        no_preview: bool,
+        filter: Option<Regex>,
+        matching_scheme: Option<MatchingScheme>,
     ) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
         let (render_tx, _) = mpsc::unbounded_channel();
@@ -94,24 +128,34 @@ impl App {
         let (event_abort_tx, _) = mpsc::unbounded_channel();
         let frame_rate = config.config.frame_rate;
         let tick_rate = config.config.tick_rate;
-        let keymap = Keymap::from(&config.keybindings).with_mode_mappings(
-            Mode::Channel,
-            passthrough_keybindings
-                .iter()
-                .flat_map(|s| match parse_key(s) {
-                    Ok(key) => Ok((key, Action::SelectPassthrough(s.clone()))),
-                    Err(e) => Err(e),
-                })
-                .collect(),
-        )?;
+        let debug = config.config.debug;
+        let idle_timeout = match config.config.idle_timeout {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        };
+        let keymap = build_keymap(&config, passthrough_keybindings)?;
         debug!("{:?}", keymap);
-        let television =
-            Arc::new(Mutex::new(Television::new(channel, config, input)));
+        let event_bindings = config.events.clone();
+        let television = Arc::new(Mutex::new(
+            Television::new(channel, config, input)
+                .with_goto(goto)
+                .with_sync(sync)
+                .with_preview_disabled(no_preview)
+                .with_filter(filter)
+                .with_matching_scheme(matching_scheme),
+        ));
 
         Ok(Self {
             keymap,
+            passthrough_keybindings: passthrough_keybindings.to_vec(),
+            config_loader: None,
+            entry_ranks: false,
+            event_bindings,
             tick_rate,
             frame_rate,
+            idle_timeout,
+            last_input: Instant::now(),
+            debug,
             television,
             should_quit: false,
             should_suspend: false,
@@ -123,6 +167,21 @@ impl App {
         })
     }
 
+    #[must_use]
+    pub fn with_config_loader(
+        mut self,
+        config_loader: impl Fn() -> Result<Config> + Send + Sync + 'static,
+    ) -> Self {
+        self.config_loader = Some(Box::new(config_loader));
+        self
+    }
+
+    #[must_use]
+    pub fn with_entry_ranks(mut self, entry_ranks: bool) -> Self {
+        self.entry_ranks = entry_ranks;
+        self
+    }
+
     /// Run the application main loop.
     ///
     /// This function will start the event loop and the rendering loop and handle
@@ -167,8 +226,20 @@ impl App {
         loop {
             // handle event and convert to action
             if let Some(event) = self.event_rx.recv().await {
+                match event {
+                    Event::Input(_) => self.last_input = Instant::now(),
+                    Event::Tick if self.idle_timed_out() => {
+                        info!("Idle timeout reached, exiting");
+                        action_tx.send(Action::Quit)?;
+                    }
+                    _ => {}
+                }
                 let action = self.convert_event_to_action(event).await;
                 action_tx.send(action)?;
+                // dispatch any action bound to this event in the configuration
+                if let Some(action) = self.event_bindings.get_action(&event) {
+                    action_tx.send(action)?;
+                }
             }
 
             let action_outcome = self.handle_actions().await?;
@@ -180,11 +251,33 @@ impl App {
                 // wait for the rendering task to finish
                 rendering_task.await??;
 
-                return Ok(AppOutput::from(action_outcome));
+                let mut output = AppOutput::from(action_outcome);
+                let mut television = self.television.lock().await;
+                if self.entry_ranks {
+                    if let Some(entries) = &output.selected_entries {
+                        output.entry_ranks = television.entry_ranks(entries);
+                    }
+                }
+
+                // stop any process still producing entries
+                television.shutdown();
+
+                return Ok(output);
             }
         }
     }
 
+    /// The name of the channel currently on air.
+    pub async fn current_channel_name(&self) -> String {
+        self.television.lock().await.channel.name()
+    }
+
+    /// Whether the idle timeout (if any) elapsed since the last user input.
+    fn idle_timed_out(&self) -> bool {
+        self.idle_timeout
+            .is_some_and(|timeout| self.last_input.elapsed() >= timeout)
+    }
+
     /// Convert an event to an action.
     ///
     /// This function will convert an event to an action based on the current
@@ -199,36 +292,18 @@ impl App {
         match event {
             Event::Input(keycode) => {
                 info!("{:?}", keycode);
-                // text input events
-                match keycode {
-                    Key::Backspace => return Action::DeletePrevChar,
-                    Key::Ctrl('w') => return Action::DeletePrevWord,
-                    Key::Delete => return Action::DeleteNextChar,
-                    Key::Left => return Action::GoToPrevChar,
-                    Key::Right => return Action::GoToNextChar,
-                    Key::Home | Key::Ctrl('a') => {
-                        return Action::GoToInputStart
-                    }
-                    Key::End | Key::Ctrl('e') => return Action::GoToInputEnd,
-                    Key::Char(c) => return Action::AddInputChar(c),
-                    _ => {}
-                }
-                // get action based on keybindings
-                self.keymap
-                    .get(&self.television.lock().await.mode)
-                    .and_then(|keymap| keymap.get(&keycode).cloned())
-                    .unwrap_or(if let Key::Char(c) = keycode {
-                        Action::AddInputChar(c)
-                    } else {
-                        Action::NoOp
-                    })
+                self.television
+                    .lock()
+                    .await
+                    .action_for_key_with(&self.keymap, keycode)
             }
             // terminal events
             Event::Tick => Action::Tick,
+            Event::Terminate => Action::Quit,
             Event::Resize(x, y) => Action::Resize(x, y),
-            Event::FocusGained => Action::Resume,
-            Event::FocusLost => Action::Suspend,
-            Event::Closed => Action::NoOp,
+            Event::FocusGained | Event::FocusLost | Event::Closed => {
+                Action::NoOp
+            }
         }
     }
 
@@ -263,15 +338,21 @@ impl App {
                 Action::SelectAndExit => {
                     self.should_quit = true;
                     self.render_tx.send(RenderingTask::Quit)?;
-                    if let Some(entries) = self
-                        .television
-                        .lock()
-                        .await
-                        .get_selected_entries(Some(Mode::Channel))
+                    let mut television = self.television.lock().await;
+                    television.flush_pending_find();
+                    if let Some(entries) =
+                        television.get_selected_entries(Some(Mode::Channel))
                     {
                         return Ok(ActionOutcome::Entries(entries));
                     }
 
+                    return Ok(ActionOutcome::Input(
+                        television.current_pattern.clone(),
+                    ));
+                }
+                Action::AbortWithQuery => {
+                    self.should_quit = true;
+                    self.render_tx.send(RenderingTask::Quit)?;
                     return Ok(ActionOutcome::Input(
                         self.television.lock().await.current_pattern.clone(),
                     ));
@@ -279,11 +360,10 @@ impl App {
                 Action::SelectPassthrough(passthrough) => {
                     self.should_quit = true;
                     self.render_tx.send(RenderingTask::Quit)?;
-                    if let Some(entries) = self
-                        .television
-                        .lock()
-                        .await
-                        .get_selected_entries(Some(Mode::Channel))
+                    let mut television = self.television.lock().await;
+                    television.flush_pending_find();
+                    if let Some(entries) =
+                        television.get_selected_entries(Some(Mode::Channel))
                     {
                         return Ok(ActionOutcome::Passthrough(
                             entries,
@@ -301,6 +381,16 @@ impl App {
                 Action::Render => {
                     self.render_tx.send(RenderingTask::Render)?;
                 }
+                Action::DumpFrame => {
+                    if self.debug {
+                        self.render_tx.send(RenderingTask::DumpFrame)?;
+                    } else {
+                        debug!("Ignoring `DumpFrame` outside of debug mode");
+                    }
+                }
+                Action::ReloadConfig => {
+                    self.reload_config().await;
+                }
                 _ => {}
             }
             // forward action to the television handler
@@ -312,4 +402,60 @@ impl App {
         }
         Ok(ActionOutcome::None)
     }
+
+    /// Load the configuration again and apply it to the running application.
+    ///
+    /// The current channel, query and results are kept. Settings only read
+    /// on startup (such as `tick_rate`, `frame_rate` or `mouse`) still
+    /// require a restart.
+    async fn reload_config(&mut self) {
+        let Some(config_loader) = &self.config_loader else {
+            return;
+        };
+        let reloaded = config_loader().and_then(|config| {
+            let keymap = build_keymap(&config, &self.passthrough_keybindings)?;
+            Ok((config, keymap))
+        });
+        let mut television = self.television.lock().await;
+        match reloaded {
+            Ok((config, keymap)) => {
+                self.keymap = keymap;
+                self.event_bindings = config.events.clone();
+                self.idle_timeout = match config.config.idle_timeout {
+                    0 => None,
+                    secs => Some(Duration::from_secs(secs)),
+                };
+                television.reload_config(config);
+                television
+                    .set_status_message("Configuration reloaded".to_string());
+            }
+            Err(e) => {
+                warn!("Failed to reload the configuration: {e:#}");
+                // parse errors span several lines, keep the first one
+                let reason = e.to_string();
+                television.set_status_message(format!(
+                    "Failed to reload the configuration: {}",
+                    reason.lines().next().unwrap_or_default()
+                ));
+            }
+        }
+    }
+}
+
+/// Build the keymap from the configured keybindings and the passthrough
+/// keybindings.
+fn build_keymap(
+    config: &Config,
+    passthrough_keybindings: &[String],
+) -> Result<Keymap> {
+    Keymap::from(&config.keybindings).with_mode_mappings(
+        Mode::Channel,
+        passthrough_keybindings
+            .iter()
+            .flat_map(|s| match parse_key(s) {
+                Ok(key) => Ok((key, Action::SelectPassthrough(s.clone()))),
+                Err(e) => Err(e),
+            })
+            .collect(),
+    )
 }
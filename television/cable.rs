@@ -1,9 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::channels::cable::{CableChannelPrototype, CableChannels};
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use tracing::{debug, error};
 
 use crate::config::get_config_dir;
@@ -11,8 +13,85 @@ use crate::config::get_config_dir;
 /// Just a proxy struct to deserialize prototypes
 #[derive(Debug, serde::Deserialize, Default)]
 struct ChannelPrototypes {
-    #[serde(rename = "cable_channel")]
+    #[serde(rename = "cable_channel", default)]
     prototypes: Vec<CableChannelPrototype>,
+    #[serde(rename = "cable_channel_alias", default)]
+    aliases: Vec<CableChannelAlias>,
+}
+
+/// A channel defined as another channel (or alias) with a few overrides.
+#[derive(Debug, Clone, serde::Deserialize, PartialEq)]
+pub struct CableChannelAlias {
+    pub name: String,
+    /// The name of the channel this alias is based on.
+    pub channel: String,
+    /// Arguments appended to the source command of the aliased channel.
+    pub source_args: Option<String>,
+    pub source_command: Option<String>,
+    pub preview_command: Option<String>,
+    pub default_query: Option<String>,
+}
+
+impl CableChannelAlias {
+    /// Apply the alias' overrides to the prototype of the aliased channel.
+    fn apply(
+        &self,
+        mut prototype: CableChannelPrototype,
+    ) -> CableChannelPrototype {
+        prototype.name.clone_from(&self.name);
+        if let Some(source_command) = &self.source_command {
+            prototype.source_command.clone_from(source_command);
+        }
+        if let Some(source_args) = &self.source_args {
+            prototype.source_command =
+                format!("{} {}", prototype.source_command, source_args);
+        }
+        if let Some(preview_command) = &self.preview_command {
+            prototype.preview_command = Some(preview_command.clone());
+        }
+        if let Some(default_query) = &self.default_query {
+            prototype.default_query = Some(default_query.clone());
+        }
+        prototype
+    }
+}
+
+/// Resolve the prototype of the alias named `name`, following chains of
+/// aliases.
+///
+/// Returns an error if the chain refers to an unknown channel or loops.
+fn resolve_alias(
+    name: &str,
+    aliases: &FxHashMap<String, CableChannelAlias>,
+    prototypes: &FxHashMap<String, CableChannelPrototype>,
+) -> Result<CableChannelPrototype> {
+    let mut chain = vec![];
+    let mut current = name;
+    while let Some(alias) = aliases.get(current) {
+        if chain
+            .iter()
+            .any(|a: &&CableChannelAlias| a.name == alias.name)
+        {
+            return Err(eyre!(
+                "Channel alias cycle: {} -> {}",
+                chain
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" -> "),
+                alias.name
+            ));
+        }
+        chain.push(alias);
+        current = &alias.channel;
+    }
+    let prototype = prototypes.get(current).ok_or_else(|| {
+        eyre!("Unknown channel {current:?} in alias {name:?}")
+    })?;
+    Ok(chain
+        .iter()
+        .rev()
+        .fold(prototype.clone(), |prototype, alias| alias.apply(prototype)))
 }
 
 const CABLE_FILE_NAME_SUFFIX: &str = "channels";
@@ -65,34 +144,267 @@ pub fn load_cable_channels() -> Result<CableChannels> {
         file_paths.push(default_channels_path);
     }
 
-    let user_defined_prototypes = file_paths.iter().fold(
-        Vec::<CableChannelPrototype>::new(),
-        |mut acc, p| {
-            match toml::from_str::<ChannelPrototypes>(
-                &std::fs::read_to_string(p)
-                    .expect("Unable to read configuration file"),
-            ) {
-                Ok(prototypes) => acc.extend(prototypes.prototypes),
-                Err(e) => {
-                    error!(
-                        "Failed to parse cable channel file {:?}: {}",
-                        p, e
-                    );
+    let user_defined_prototypes =
+        file_paths
+            .iter()
+            .fold(ChannelPrototypes::default(), |mut acc, p| {
+                match toml::from_str::<ChannelPrototypes>(
+                    &std::fs::read_to_string(p)
+                        .expect("Unable to read configuration file"),
+                ) {
+                    Ok(prototypes) => {
+                        acc.prototypes.extend(prototypes.prototypes);
+                        acc.aliases.extend(prototypes.aliases);
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to parse cable channel file {:?}: {}",
+                            p, e
+                        );
+                    }
                 }
-            }
-            acc
-        },
-    );
+                acc
+            });
 
     debug!("Loaded cable channels: {:?}", user_defined_prototypes);
 
     let mut cable_channels = FxHashMap::default();
-    for prototype in user_defined_prototypes {
+    for prototype in user_defined_prototypes.prototypes {
+        cable_channels.insert(prototype.name.clone(), prototype);
+    }
+    let mut aliases = user_defined_prototypes.aliases;
+    let repos = load_channel_repos(
+        &config_dir.join(CHANNEL_REPOS_DIR),
+        CHANNEL_REPOS.get().map_or(&[], Vec::as_slice),
+    );
+    for prototype in repos.prototypes {
         cable_channels.insert(prototype.name.clone(), prototype);
     }
+    aliases.extend(repos.aliases);
+    resolve_aliases(&mut cable_channels, aliases);
     Ok(CableChannels(cable_channels))
 }
 
+/// The directory (in the config directory) where channel repositories are
+/// cloned.
+const CHANNEL_REPOS_DIR: &str = "cable";
+
+/// The channel repositories listed in the configuration.
+static CHANNEL_REPOS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Set the channel repositories whose channels are loaded along with the
+/// user's, the clones of other repositories being ignored.
+///
+/// This can only be set once, further calls are ignored.
+pub fn set_channel_repos(repos: &[String]) {
+    let _ = CHANNEL_REPOS.set(repos.to_vec());
+}
+
+/// The namespace of the channels of a repository, i.e. the repository's
+/// name (e.g. `team-channels` for `https://github.com/org/team-channels.git`).
+pub fn repo_namespace(url: &str) -> Option<String> {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()?
+        .trim_end_matches(".git");
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+/// Clone the given channel repositories into the cable directory, or pull
+/// them if they were already cloned.
+///
+/// Each repository is updated independently: the outcome of each update is
+/// returned along with the repository's URL.
+pub fn update_channel_repos(repos: &[String]) -> Vec<(String, Result<()>)> {
+    let repos_dir = get_config_dir().join(CHANNEL_REPOS_DIR);
+    repos
+        .iter()
+        .map(|url| (url.clone(), update_channel_repo(&repos_dir, url)))
+        .collect()
+}
+
+fn update_channel_repo(repos_dir: &Path, url: &str) -> Result<()> {
+    let namespace = repo_namespace(url)
+        .ok_or_else(|| eyre!("Unable to name a directory after {url:?}"))?;
+    let dir = repos_dir.join(namespace);
+    let mut git = Command::new("git");
+    if dir.join(".git").exists() {
+        // another repository with the same name might have been cloned there
+        let origin = git_output(
+            Command::new("git")
+                .arg("-C")
+                .arg(&dir)
+                .args(["remote", "get-url", "origin"]),
+        )?;
+        if origin.trim() != url {
+            return Err(eyre!(
+                "{} already holds a clone of {}",
+                dir.display(),
+                origin.trim()
+            ));
+        }
+        git.arg("-C")
+            .arg(&dir)
+            .args(["pull", "--ff-only", "--quiet"]);
+    } else {
+        std::fs::create_dir_all(repos_dir)?;
+        git.args(["clone", "--depth", "1", "--quiet", "--", url])
+            .arg(&dir);
+    }
+    git_output(&mut git).map(|_| ())
+}
+
+/// Run the given git command, returning its output or its error message.
+fn git_output(git: &mut Command) -> Result<String> {
+    let output = git
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| eyre!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Load the channels of the given channel repositories that were cloned.
+///
+/// Channels (and aliases) are namespaced with the name of their repository,
+/// e.g. `team-channels/deploy`.
+fn load_channel_repos(
+    repos_dir: &Path,
+    repos: &[String],
+) -> ChannelPrototypes {
+    let mut loaded = ChannelPrototypes::default();
+    let mut namespaces = FxHashSet::default();
+    for namespace in repos.iter().filter_map(|url| repo_namespace(url)) {
+        let repo_dir = repos_dir.join(&namespace);
+        // several repositories with the same name share the same clone
+        if !repo_dir.is_dir() || !namespaces.insert(namespace.clone()) {
+            continue;
+        }
+        let mut repo = ChannelPrototypes::default();
+        for path in find_cable_files(&repo_dir) {
+            match std::fs::read_to_string(&path)
+                .map_err(|e| eyre!("{e}"))
+                .and_then(|content| {
+                    toml::from_str::<ChannelPrototypes>(&content)
+                        .map_err(|e| eyre!("{e}"))
+                }) {
+                Ok(prototypes) => {
+                    repo.prototypes.extend(prototypes.prototypes);
+                    repo.aliases.extend(prototypes.aliases);
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to load cable channel file {:?}: {}",
+                        path, e
+                    );
+                }
+            }
+        }
+        namespace_prototypes(&namespace, &mut repo);
+        loaded.prototypes.extend(repo.prototypes);
+        loaded.aliases.extend(repo.aliases);
+    }
+    loaded
+}
+
+/// Prefix the names of the channels and aliases of a repository with its
+/// namespace.
+///
+/// Aliases referring to a channel of the same repository are updated
+/// accordingly, other aliases keep referring to the user's channels.
+fn namespace_prototypes(namespace: &str, repo: &mut ChannelPrototypes) {
+    let local_names = repo
+        .prototypes
+        .iter()
+        .map(|p| p.name.clone())
+        .chain(repo.aliases.iter().map(|a| a.name.clone()))
+        .collect::<Vec<_>>();
+    let qualify = |name: &str| format!("{namespace}/{name}");
+    for prototype in &mut repo.prototypes {
+        prototype.name = qualify(&prototype.name);
+    }
+    for alias in &mut repo.aliases {
+        alias.name = qualify(&alias.name);
+        if local_names.contains(&alias.channel) {
+            alias.channel = qualify(&alias.channel);
+        }
+    }
+}
+
+/// Find cable channel files in `dir` and its subdirectories (skipping hidden
+/// ones such as `.git`).
+fn find_cable_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if path.is_dir() && !hidden {
+            files.extend(find_cable_files(&path));
+        } else if path.is_file() && is_cable_file_format(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Add the channels defined by `aliases` to the given prototypes.
+///
+/// Aliases that can't be resolved are reported and skipped.
+fn resolve_aliases(
+    prototypes: &mut FxHashMap<String, CableChannelPrototype>,
+    aliases: Vec<CableChannelAlias>,
+) {
+    let aliases = aliases
+        .into_iter()
+        .map(|alias| (alias.name.clone(), alias))
+        .collect::<FxHashMap<_, _>>();
+    let resolved = aliases
+        .keys()
+        .filter_map(|name| match resolve_alias(name, &aliases, prototypes) {
+            Ok(prototype) => Some(prototype),
+            Err(e) => {
+                error!("Failed to resolve channel alias {:?}: {}", name, e);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    for prototype in resolved {
+        prototypes.insert(prototype.name.clone(), prototype);
+    }
+}
+
+/// Load a single channel prototype from a TOML file, or from stdin if `path`
+/// is `-`.
+///
+/// This allows using ephemeral channels without adding them to the cable
+/// channel files.
+pub fn load_channel_prototype(path: &str) -> Result<CableChannelPrototype> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Unable to read channel file {path:?}: {e}"))?
+    };
+    parse_channel_prototype(&content)
+}
+
+fn parse_channel_prototype(content: &str) -> Result<CableChannelPrototype> {
+    toml::from_str::<CableChannelPrototype>(content)
+        .map_err(|e| eyre!("Invalid channel prototype: {e}"))
+}
+
 fn is_cable_file_format<P>(p: P) -> bool
 where
     P: AsRef<std::path::Path>,
@@ -110,6 +422,109 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_repo_namespace() {
+        assert_eq!(
+            repo_namespace("https://github.com/org/team-channels.git")
+                .as_deref(),
+            Some("team-channels")
+        );
+        assert_eq!(
+            repo_namespace("git@github.com:org/deploy.git").as_deref(),
+            Some("deploy")
+        );
+        assert_eq!(
+            repo_namespace("/srv/git/channels/").as_deref(),
+            Some("channels")
+        );
+        assert_eq!(repo_namespace(".."), None);
+    }
+
+    #[test]
+    fn test_load_configured_channel_repos() {
+        let repos_dir = std::env::temp_dir()
+            .join(format!("tv-channel-repos-{}", std::process::id()));
+        for repo in ["team-channels", "removed-channels"] {
+            std::fs::create_dir_all(repos_dir.join(repo)).unwrap();
+            std::fs::write(
+                repos_dir.join(repo).join("channels.toml"),
+                "[[cable_channel]]\nname = \"deploy\"\nsource_command = \"ls\"\n",
+            )
+            .unwrap();
+        }
+
+        let loaded = load_channel_repos(
+            &repos_dir,
+            &[
+                "https://github.com/org/team-channels.git".to_string(),
+                "https://github.com/other-org/team-channels".to_string(),
+            ],
+        );
+        let names = loaded
+            .prototypes
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["team-channels/deploy"]);
+
+        std::fs::remove_dir_all(&repos_dir).unwrap();
+    }
+
+    #[test]
+    fn test_update_channel_repo_with_another_origin() {
+        let repos_dir = std::env::temp_dir()
+            .join(format!("tv-channel-repo-origin-{}", std::process::id()));
+        let dir = repos_dir.join("team-channels");
+        std::fs::create_dir_all(&dir).unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &[
+                "remote",
+                "add",
+                "origin",
+                "https://github.com/org/team-channels",
+            ],
+        ] {
+            git_output(Command::new("git").arg("-C").arg(&dir).args(args))
+                .unwrap();
+        }
+
+        let error = update_channel_repo(
+            &repos_dir,
+            "https://github.com/other-org/team-channels",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("already holds a clone of"));
+
+        std::fs::remove_dir_all(&repos_dir).unwrap();
+    }
+
+    #[test]
+    fn test_namespace_prototypes() {
+        let mut repo = toml::from_str::<ChannelPrototypes>(
+            r#"
+            [[cable_channel]]
+            name = "deploy"
+            source_command = "ls"
+
+            [[cable_channel_alias]]
+            name = "deploy-prod"
+            channel = "deploy"
+
+            [[cable_channel_alias]]
+            name = "my-files"
+            channel = "files"
+            "#,
+        )
+        .unwrap();
+        namespace_prototypes("team", &mut repo);
+        assert_eq!(repo.prototypes[0].name, "team/deploy");
+        assert_eq!(repo.aliases[0].name, "team/deploy-prod");
+        assert_eq!(repo.aliases[0].channel, "team/deploy");
+        assert_eq!(repo.aliases[1].name, "team/my-files");
+        assert_eq!(repo.aliases[1].channel, "files");
+    }
+
     #[test]
     fn test_is_cable_file() {
         let path = std::path::Path::new("cable_channels.toml");
@@ -118,4 +533,68 @@ mod tests {
         let path = std::path::Path::new(DEFAULT_CABLE_CHANNELS_FILE_NAME);
         assert!(is_cable_file_format(path));
     }
+
+    #[test]
+    fn test_parse_channel_prototype() {
+        let prototype = parse_channel_prototype(
+            r#"
+            name = "my-channel"
+            source_command = "ls"
+            preview_command = "cat {}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(prototype.name, "my-channel");
+        assert_eq!(prototype.source_command, "ls");
+        assert_eq!(prototype.preview_command, Some("cat {}".to_string()));
+
+        assert!(parse_channel_prototype("name = \"missing-source\"").is_err());
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        let channels = toml::from_str::<ChannelPrototypes>(
+            r#"
+            [[cable_channel]]
+            name = "files"
+            source_command = "fd -t f"
+            preview_command = "cat {}"
+
+            [[cable_channel_alias]]
+            name = "docs"
+            channel = "files"
+            source_args = "docs"
+
+            [[cable_channel_alias]]
+            name = "markdown-docs"
+            channel = "docs"
+            default_query = ".md"
+
+            [[cable_channel_alias]]
+            name = "a"
+            channel = "b"
+
+            [[cable_channel_alias]]
+            name = "b"
+            channel = "a"
+            "#,
+        )
+        .unwrap();
+        let mut prototypes = channels
+            .prototypes
+            .into_iter()
+            .map(|p| (p.name.clone(), p))
+            .collect::<FxHashMap<_, _>>();
+        resolve_aliases(&mut prototypes, channels.aliases);
+
+        let docs = &prototypes["docs"];
+        assert_eq!(docs.source_command, "fd -t f docs");
+        assert_eq!(docs.preview_command, Some("cat {}".to_string()));
+        let markdown_docs = &prototypes["markdown-docs"];
+        assert_eq!(markdown_docs.source_command, "fd -t f docs");
+        assert_eq!(markdown_docs.default_query, Some(".md".to_string()));
+        // cycles are skipped
+        assert!(!prototypes.contains_key("a"));
+        assert!(!prototypes.contains_key("b"));
+    }
 }
@@ -1,11 +1,17 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
 use crate::channels::entry::Entry;
 use crate::channels::entry::PreviewType;
 use crate::channels::OnAir;
-use crate::matcher::{config::Config, injector::Injector, Matcher};
+use crate::matcher::{
+    config::{Config, MatchingScheme},
+    injector::Injector,
+    Matcher,
+};
 use crate::utils::indices::sep_name_and_value_indices;
 use devicons::FileIcon;
+use regex::Regex;
 use rustc_hash::FxBuildHasher;
 use rustc_hash::FxHashSet;
 use tracing::debug;
@@ -145,6 +151,30 @@ impl OnAir for Channel {
         self.matcher.status.running
     }
 
+    fn loading(&self) -> bool {
+        self.matcher.loading()
+    }
+
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+        self.matcher.set_scheme(scheme);
+    }
+
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.matcher.set_prefix_bonus(prefix_bonus);
+    }
+
+    fn set_filter(&mut self, filter: Option<Regex>) {
+        self.matcher.set_filter(filter);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.matcher.set_paused(paused);
+    }
+
+    fn wait_for_matches(&mut self, timeout: Duration) {
+        self.matcher.wait_for_matches(timeout);
+    }
+
     fn shutdown(&self) {}
 }
 
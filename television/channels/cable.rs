@@ -4,21 +4,35 @@ use std::{
     ops::Deref,
 };
 
+use std::cell::RefCell;
 use std::collections::HashSet;
-use std::io::{BufRead, BufReader};
-use std::process::Stdio;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 use color_eyre::Result;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use lazy_static::lazy_static;
 use regex::Regex;
 use rustc_hash::{FxBuildHasher, FxHashSet};
-use tracing::debug;
+use tracing::{debug, error};
 
 use crate::channels::entry::{Entry, PreviewCommand, PreviewType};
 use crate::channels::OnAir;
 use crate::matcher::Matcher;
-use crate::matcher::{config::Config, injector::Injector};
-use crate::utils::command::shell_command;
+use crate::matcher::{
+    config::{Config, MatchingScheme},
+    injector::Injector,
+};
+use crate::preview::previewers::command::format_command;
+#[cfg(unix)]
+use crate::utils::command::signal_process_group;
+use crate::utils::command::{
+    kill_process_group, shell_command, with_process_group,
+};
+use crate::utils::strings::shell_quote;
 
 #[derive(Debug, Clone)]
 enum PreviewKind {
@@ -32,8 +46,28 @@ pub struct Channel {
     name: String,
     matcher: Matcher<String>,
     entries_command: String,
+    /// How entries are produced from `entries_command`, kept to run it again
+    /// when reloading the source.
+    source_options: SourceOptions,
+    display_template: Option<String>,
+    /// Whether entries are matched against a dedicated match key rather than
+    /// their display string.
+    has_match_key: bool,
+    delimiter: String,
+    path_display: Option<PathDisplay>,
+    /// Whether the source keeps running and producing entries.
+    streaming: bool,
+    /// The directory relative paths are resolved against for `path_display`.
+    working_directory: PathBuf,
+    default_query: Option<String>,
     preview_kind: PreviewKind,
     selected_entries: FxHashSet<Entry>,
+    /// The process producing the entries, killed when the channel is shut
+    /// down.
+    source_process: Arc<Mutex<Option<Child>>>,
+    /// The prototype the channel was built from, kept to build the channel
+    /// listing the entries of a selected entry if it has a `drill_command`.
+    prototype: Option<CableChannelPrototype>,
 }
 
 impl Default for Channel {
@@ -42,28 +76,125 @@ impl Default for Channel {
             "Files",
             "find . -type f",
             Some(PreviewCommand::new("bat -n --color=always {}", ":")),
+            SourceOptions::default(),
         )
     }
 }
 
 impl From<CableChannelPrototype> for Channel {
     fn from(prototype: CableChannelPrototype) -> Self {
-        Self::new(
+        let prototype = resolve_field_names(prototype);
+        let drill_prototype =
+            prototype.drill_command.is_some().then(|| prototype.clone());
+        let mut channel = Self::new(
             &prototype.name,
             &prototype.source_command,
             match prototype.preview_command {
-                Some(command) => Some(PreviewCommand::new(
-                    &command,
-                    &prototype
-                        .preview_delimiter
-                        .unwrap_or(DEFAULT_DELIMITER.to_string()),
-                )),
+                Some(command) => {
+                    let preview_command = PreviewCommand::new(
+                        &command,
+                        &prototype
+                            .preview_delimiter
+                            .unwrap_or(DEFAULT_DELIMITER.to_string()),
+                    );
+                    let preview_command = match prototype.preview_dir_command {
+                        Some(dir_command) => {
+                            preview_command.with_dir_command(&dir_command)
+                        }
+                        None => preview_command,
+                    };
+                    let preview_command = match prototype.preview_offset {
+                        Some(offset) => preview_command.with_offset(&offset),
+                        None => preview_command,
+                    };
+                    let preview_command =
+                        match prototype.preview_fallback_command {
+                            Some(fallback_command) => preview_command
+                                .with_fallback_command(&fallback_command),
+                            None => preview_command,
+                        };
+                    Some(match prototype.preview_multi_command {
+                        Some(multi_command) => {
+                            preview_command.with_multi_command(&multi_command)
+                        }
+                        None => preview_command,
+                    })
+                }
                 None => None,
             },
-        )
+            SourceOptions {
+                trim: prototype.source_trim,
+                display: prototype.source_display,
+                delimiter: prototype
+                    .source_delimiter
+                    .unwrap_or(DEFAULT_DELIMITER.to_string()),
+                streaming: prototype.source_streaming,
+                match_key: prototype.source_match_key,
+                dedup: prototype.source_dedup,
+                path_display: prototype.path_display,
+                ignore: prototype.source_ignore,
+                max_depth: prototype.source_max_depth,
+            },
+        );
+        if let Some(scheme) = prototype.scheme {
+            channel.matcher.set_scheme(scheme);
+        }
+        channel.default_query = prototype.default_query;
+        channel.prototype = drill_prototype;
+        channel
     }
 }
 
+lazy_static! {
+    static ref FIELD_NAME_RE: Regex =
+        Regex::new(r"(\$?)\{([A-Za-z_][\w-]*)\}").unwrap();
+}
+
+/// Replace the `{name}` placeholders of the given template with the `{N}`
+/// placeholder of the corresponding field.
+///
+/// Unknown names and environment variables (`${VAR}`) are left untouched.
+fn name_fields(template: &str, fields: &[String]) -> String {
+    FIELD_NAME_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            match fields.iter().position(|field| *field == caps[2]) {
+                Some(index) if caps[1].is_empty() => format!("{{{index}}}"),
+                _ => caps[0].to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// Resolve the field names declared with `source_fields` in the templates
+/// of the given prototype.
+///
+/// Since named fields are split by `source_delimiter`, preview templates
+/// then use it as well instead of `preview_delimiter`.
+fn resolve_field_names(
+    mut prototype: CableChannelPrototype,
+) -> CableChannelPrototype {
+    let Some(fields) = prototype.source_fields.take() else {
+        return prototype;
+    };
+    for template in [
+        &mut prototype.source_display,
+        &mut prototype.source_match_key,
+        &mut prototype.preview_command,
+        &mut prototype.preview_dir_command,
+        &mut prototype.preview_offset,
+        &mut prototype.preview_fallback_command,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *template = name_fields(template, &fields);
+    }
+    prototype
+        .preview_delimiter
+        .clone_from(&prototype.source_delimiter);
+    prototype
+}
+
 lazy_static! {
     static ref BUILTIN_PREVIEW_RE: Regex = Regex::new(r"^:(\w+):$").unwrap();
 }
@@ -78,15 +209,73 @@ fn parse_preview_kind(command: &PreviewCommand) -> Result<PreviewKind> {
     }
 }
 
+/// Options controlling how entries are produced from the source command.
+#[derive(Debug, Clone)]
+pub struct SourceOptions {
+    /// Strip trailing whitespace from each entry.
+    pub trim: bool,
+    /// A template (using `{}` and `{N}` placeholders) for the string that
+    /// gets displayed and matched against, in place of the raw entry.
+    pub display: Option<String>,
+    /// The delimiter used to split entries into `{N}` fields.
+    pub delimiter: String,
+    /// Whether the source command is expected to keep running and produce
+    /// entries over time (e.g. `journalctl -f`).
+    pub streaming: bool,
+    /// A template (using `{}` and `{N}` placeholders) for the string that
+    /// gets matched against, independently of what is displayed.
+    pub match_key: Option<String>,
+    /// Skip entries that were already loaded (compared on their match key if
+    /// any, else on the raw entry).
+    pub dedup: bool,
+    /// Display entries (or their display string) as relative or absolute
+    /// paths.
+    pub path_display: Option<PathDisplay>,
+    /// Gitignore-style globs: entries that are (or are inside) a matching
+    /// path are skipped.
+    pub ignore: Vec<String>,
+    /// Skip entries more than this many directories below the working
+    /// directory (1 keeps its direct children only).
+    pub max_depth: Option<usize>,
+}
+
+impl Default for SourceOptions {
+    fn default() -> Self {
+        Self {
+            trim: false,
+            display: None,
+            delimiter: DEFAULT_DELIMITER.to_string(),
+            streaming: false,
+            match_key: None,
+            dedup: false,
+            path_display: None,
+            ignore: Vec::new(),
+            max_depth: None,
+        }
+    }
+}
+
 impl Channel {
     pub fn new(
         name: &str,
         entries_command: &str,
         preview_command: Option<PreviewCommand>,
+        source_options: SourceOptions,
     ) -> Self {
         let matcher = Matcher::new(Config::default());
         let injector = matcher.injector();
-        tokio::spawn(load_candidates(entries_command.to_string(), injector));
+        let display_template = source_options.display.clone();
+        let has_match_key = source_options.match_key.is_some();
+        let delimiter = source_options.delimiter.clone();
+        let path_display = source_options.path_display;
+        let streaming = source_options.streaming;
+        let source_process = Arc::new(Mutex::new(None));
+        spawn_source(
+            entries_command,
+            &source_options,
+            injector,
+            &source_process,
+        );
         let preview_kind = match preview_command {
             Some(command) => {
                 parse_preview_kind(&command).unwrap_or_else(|_| {
@@ -99,51 +288,465 @@ impl Channel {
         Self {
             matcher,
             entries_command: entries_command.to_string(),
+            source_options,
+            display_template,
+            has_match_key,
+            delimiter,
+            path_display,
+            streaming,
+            working_directory: std::env::current_dir().unwrap_or_default(),
+            default_query: None,
             preview_kind,
             name: name.to_string(),
             selected_entries: HashSet::with_hasher(FxBuildHasher),
+            source_process,
+            prototype: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the channel's entries have a preview.
+    pub fn has_preview(&self) -> bool {
+        !matches!(self.preview_kind, PreviewKind::None)
+    }
+
+    /// Whether the channel's source keeps running and producing entries.
+    pub fn streaming(&self) -> bool {
+        self.streaming
+    }
+
+    /// Run the channel's source again, replacing the entries loaded so far
+    /// with its new output (e.g. to restart a streaming source).
+    pub fn reload(&mut self) {
+        if let Some(mut child) = self.source_process.lock().unwrap().take() {
+            kill_process_group(&mut child);
+        }
+        self.matcher.restart();
+        spawn_source(
+            &self.entries_command,
+            &self.source_options,
+            self.matcher.injector(),
+            &self.source_process,
+        );
+    }
+
+    /// The query to apply when the channel is loaded.
+    pub fn default_query(&self) -> Option<&str> {
+        self.default_query.as_deref()
+    }
+
+    /// Build the channel listing the entries of `entry`, by running the
+    /// channel's `drill_command` formatted with it.
+    ///
+    /// Returns `None` if the channel has no `drill_command`.
+    pub fn drill_into(&self, entry: &Entry) -> Option<Channel> {
+        let prototype = self.prototype.as_ref()?;
+        let command = prototype.drill_command.as_ref()?;
+        let source_command = format_command(
+            &PreviewCommand::new(
+                command,
+                prototype
+                    .preview_delimiter
+                    .as_deref()
+                    .unwrap_or(DEFAULT_DELIMITER),
+            ),
+            entry,
+        );
+        debug!("Drilling into {:?} with {:?}", entry.name, source_command);
+        Some(
+            CableChannelPrototype {
+                source_command,
+                default_query: None,
+                ..prototype.clone()
+            }
+            .into(),
+        )
+    }
+
+    /// The string displayed for a raw entry, given the string it was matched
+    /// against (`None` to display the raw entry itself).
+    fn display(&self, raw: &str, matched_string: String) -> Option<String> {
+        if self.has_match_key {
+            display_string(
+                self.display_template.as_deref(),
+                &self.delimiter,
+                self.path_display,
+                &self.working_directory,
+                raw,
+            )
+        } else {
+            (self.display_template.is_some() || self.path_display.is_some())
+                .then_some(matched_string)
+        }
+    }
+
+    fn preview_type(&self) -> PreviewType {
+        match &self.preview_kind {
+            PreviewKind::Command(preview_command) => {
+                PreviewType::Command(preview_command.clone())
+            }
+            PreviewKind::Builtin(preview_type) => preview_type.clone(),
+            PreviewKind::None => PreviewType::None,
         }
     }
 }
 
-#[allow(clippy::unused_async)]
-async fn load_candidates(command: String, injector: Injector<String>) {
-    debug!("Loading candidates from command: {:?}", command);
-    let mut child = shell_command()
-        .arg(command)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .expect("failed to execute process");
-
-    if let Some(out) = child.stdout.take() {
-        let reader = BufReader::new(out);
-        let mut produced_output = false;
-
-        #[allow(clippy::manual_flatten)]
-        for line in reader.lines() {
-            if let Ok(l) = line {
-                if !l.trim().is_empty() {
-                    let () = injector.push(l, |e, cols| {
-                        cols[0] = e.clone().into();
-                    });
-                    produced_output = true;
+lazy_static! {
+    static ref DISPLAY_PLACEHOLDER_RE: Regex =
+        Regex::new(r"\{(\d*)\}").unwrap();
+}
+
+/// Format the display string of a line using the given template.
+///
+/// `{}` is replaced with the whole line and `{N}` with its N-th field (empty
+/// if the line doesn't have that many fields).
+pub(crate) fn format_display(
+    template: &str,
+    delimiter: &str,
+    line: &str,
+) -> String {
+    let parts = line.split(delimiter).collect::<Vec<&str>>();
+    DISPLAY_PLACEHOLDER_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            match caps[1].parse::<usize>() {
+                Ok(index) => parts.get(index).copied().unwrap_or_default(),
+                Err(_) => line,
+            }
+            .to_string()
+        })
+        .to_string()
+}
+
+/// How paths are displayed by `path_display`.
+#[derive(Clone, Copy, Debug, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathDisplay {
+    /// Relative to the working directory (paths outside of it are kept
+    /// absolute).
+    Relative,
+    /// Absolute, relative paths being resolved against the working
+    /// directory.
+    Absolute,
+}
+
+/// Rewrite `path` as a relative or absolute path, without touching the file
+/// system.
+fn normalize_path(
+    path: &str,
+    mode: PathDisplay,
+    working_directory: &Path,
+) -> String {
+    let path = Path::new(path);
+    let normalized = match mode {
+        PathDisplay::Relative if path.is_absolute() => {
+            match path.strip_prefix(working_directory) {
+                Ok(relative) if relative.as_os_str().is_empty() => {
+                    PathBuf::from(".")
                 }
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => path.to_path_buf(),
             }
         }
+        PathDisplay::Relative => path
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .collect(),
+        PathDisplay::Absolute if path.is_absolute() => path.to_path_buf(),
+        PathDisplay::Absolute => working_directory
+            .join(path)
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .collect(),
+    };
+    if normalized.as_os_str().is_empty() {
+        return ".".to_string();
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+/// The string displayed for `line`: its display template (if any) applied to
+/// it, then rewritten as a path if `path_display` is set.
+///
+/// Returns `None` if the line is displayed as-is.
+fn display_string(
+    template: Option<&str>,
+    delimiter: &str,
+    path_display: Option<PathDisplay>,
+    working_directory: &Path,
+    line: &str,
+) -> Option<String> {
+    let display = template.map(|t| format_display(t, delimiter, line));
+    match path_display {
+        Some(mode) => Some(normalize_path(
+            display.as_deref().unwrap_or(line),
+            mode,
+            working_directory,
+        )),
+        None => display,
+    }
+}
+
+/// Filters entries as paths, according to `source_ignore` and
+/// `source_max_depth`.
+struct PathFilter {
+    ignore: Gitignore,
+    max_depth: Option<usize>,
+    working_directory: PathBuf,
+}
+
+impl PathFilter {
+    /// Build the filter, or `None` if it would keep every entry.
+    ///
+    /// Invalid globs are logged and skipped.
+    fn new(
+        ignore: &[String],
+        max_depth: Option<usize>,
+        working_directory: &Path,
+    ) -> Option<Self> {
+        if ignore.is_empty() && max_depth.is_none() {
+            return None;
+        }
+        let mut builder = GitignoreBuilder::new(working_directory);
+        for glob in ignore {
+            if let Err(e) = builder.add_line(None, glob) {
+                error!("Invalid ignore glob {:?}: {}", glob, e);
+            }
+        }
+        let ignore = builder.build().unwrap_or_else(|e| {
+            error!("Failed to build ignore globs: {}", e);
+            Gitignore::empty()
+        });
+        Some(Self {
+            ignore,
+            max_depth,
+            working_directory: working_directory.to_path_buf(),
+        })
+    }
+
+    fn keeps(&self, entry: &str) -> bool {
+        let path = PathBuf::from(normalize_path(
+            entry,
+            PathDisplay::Relative,
+            &self.working_directory,
+        ));
+        // paths outside of the working directory stay absolute
+        let ignored = if path.is_absolute() {
+            self.ignore.matched(&path, false).is_ignore()
+        } else {
+            self.ignore
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+        };
+        !ignored
+            && self.max_depth.map_or(true, |max_depth| {
+                path.is_absolute() || path.components().count() <= max_depth
+            })
+    }
+}
+
+/// The placeholder replaced with the extra command line arguments in source
+/// commands.
+const ARGV_PLACEHOLDER: &str = "{argv}";
 
-        if !produced_output {
-            let reader = BufReader::new(child.stderr.take().unwrap());
-            for line in reader.lines() {
-                let line = line.unwrap();
+/// The extra command line arguments, already shell-quoted and joined.
+static SOURCE_ARGS: OnceLock<String> = OnceLock::new();
+
+/// Set the extra command line arguments substituted for `{argv}` in source
+/// commands.
+///
+/// Each argument is shell-quoted so that it is passed as a single word. This
+/// can only be set once, further calls are ignored.
+pub fn set_source_args(args: &[String]) {
+    let _ = SOURCE_ARGS.set(
+        args.iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+}
+
+/// Replace `{argv}` in the source command with the given arguments.
+fn expand_argv(command: &str, argv: &str) -> String {
+    command.replace(ARGV_PLACEHOLDER, argv)
+}
+
+/// Strip trailing whitespace (including stray carriage returns) from a line.
+fn trim_line(mut line: String) -> String {
+    line.truncate(line.trim_end().len());
+    line
+}
+
+/// Load the entries produced by `command` on a dedicated thread, as reading
+/// the source blocks.
+fn spawn_source(
+    command: &str,
+    options: &SourceOptions,
+    injector: Injector<String>,
+    source_process: &Arc<Mutex<Option<Child>>>,
+) {
+    let command = command.to_string();
+    let options = options.clone();
+    let process = source_process.clone();
+    std::thread::spawn(move || {
+        load_candidates(&command, &options, &injector, &process);
+    });
+}
+
+fn load_candidates(
+    command: &str,
+    options: &SourceOptions,
+    injector: &Injector<String>,
+    source_process: &Mutex<Option<Child>>,
+) {
+    // the match key (or else the display string) is what gets matched
+    // against while the entry itself keeps the raw line
+    let working_directory = std::env::current_dir().unwrap_or_default();
+    let path_filter = PathFilter::new(
+        &options.ignore,
+        options.max_depth,
+        &working_directory,
+    );
+    // every loaded entry (or match key) is kept around to skip duplicates
+    let seen = RefCell::new(FxHashSet::default());
+    let push_batch = |mut batch: Vec<String>| {
+        if let Some(filter) = &path_filter {
+            batch.retain(|entry| filter.keeps(entry));
+        }
+        if options.dedup {
+            retain_unseen(
+                &mut batch,
+                &mut seen.borrow_mut(),
+                options.match_key.as_deref(),
+                &options.delimiter,
+            );
+        }
+        injector.push_batch(batch, |e, cols| {
+            cols[0] = match &options.match_key {
+                Some(template) => {
+                    format_display(template, &options.delimiter, e)
+                }
+                None => display_string(
+                    options.display.as_deref(),
+                    &options.delimiter,
+                    options.path_display,
+                    &working_directory,
+                    e,
+                )
+                .unwrap_or_else(|| e.clone()),
+            }
+            .into();
+        });
+    };
+    let command = expand_argv(
+        command,
+        SOURCE_ARGS.get().map(String::as_str).unwrap_or_default(),
+    );
+    debug!("Loading candidates from command: {:?}", command);
+    // the source gets its own process group so that the whole pipeline can
+    // be killed on shutdown, not just the shell running it
+    let spawned = with_process_group(&mut shell_command())
+        .arg(&command)
+        .stdout(Stdio::piped())
+        // a streaming source's stderr is never read, so it mustn't be piped
+        // or the process would eventually block on it
+        .stderr(if options.streaming {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            error!("Failed to run source command {:?}: {}", command, e);
+            return;
+        }
+    };
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    *source_process.lock().unwrap() = Some(child);
+
+    if let Some(out) = stdout {
+        let produced_output =
+            read_batches(BufReader::new(out), options.trim, push_batch);
+
+        if let (false, Some(err)) = (produced_output, stderr) {
+            read_batches(BufReader::new(err), options.trim, push_batch);
+        }
+    }
+}
+
+/// Remove the entries of `batch` that were already seen, recording the new
+/// ones in `seen`.
+///
+/// Entries are compared on their match key if a template is given, else on
+/// their raw value.
+fn retain_unseen(
+    batch: &mut Vec<String>,
+    seen: &mut FxHashSet<String>,
+    match_key: Option<&str>,
+    delimiter: &str,
+) {
+    batch.retain(|entry| {
+        let key = match match_key {
+            Some(template) => format_display(template, delimiter, entry),
+            None => entry.clone(),
+        };
+        seen.insert(key)
+    });
+}
+
+/// The maximum number of lines injected into the matcher at once.
+const LOAD_BATCH_SIZE: usize = 1024;
+
+/// Read non-empty lines from `reader` and hand them over in batches.
+///
+/// A batch is flushed when it is full or when no more data is immediately
+/// available, so that slow sources still get their entries displayed as soon
+/// as they are produced.
+///
+/// Returns whether any line was read.
+fn read_batches<R: Read>(
+    mut reader: BufReader<R>,
+    trim: bool,
+    on_batch: impl Fn(Vec<String>),
+) -> bool {
+    let mut produced_output = false;
+    let mut batch = Vec::with_capacity(LOAD_BATCH_SIZE);
+    let mut buffer = String::new();
+    loop {
+        buffer.clear();
+        match reader.read_line(&mut buffer) {
+            Ok(0) => break,
+            Ok(_) => {
+                let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
                 if !line.trim().is_empty() {
-                    let () = injector.push(line, |e, cols| {
-                        cols[0] = e.clone().into();
-                    });
+                    let line = line.to_string();
+                    batch.push(if trim { trim_line(line) } else { line });
+                    produced_output = true;
                 }
             }
+            // skip lines that aren't valid UTF-8
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {}
+            Err(_) => break,
         }
+        if batch.len() >= LOAD_BATCH_SIZE
+            || (!batch.is_empty() && reader.buffer().is_empty())
+        {
+            on_batch(std::mem::replace(
+                &mut batch,
+                Vec::with_capacity(LOAD_BATCH_SIZE),
+            ));
+        }
+    }
+    if !batch.is_empty() {
+        on_batch(batch);
     }
+    produced_output
 }
 
 impl OnAir for Channel {
@@ -157,37 +760,28 @@ impl OnAir for Channel {
             .results(num_entries, offset)
             .into_iter()
             .map(|item| {
-                let path = item.matched_string;
-                Entry::new(
-                    path,
-                    match &self.preview_kind {
-                        PreviewKind::Command(ref preview_command) => {
-                            PreviewType::Command(preview_command.clone())
-                        }
-                        PreviewKind::Builtin(preview_type) => {
-                            preview_type.clone()
-                        }
-                        PreviewKind::None => PreviewType::None,
-                    },
-                )
-                .with_name_match_ranges(&item.match_indices)
+                let display = self.display(&item.inner, item.matched_string);
+                let mut entry = Entry::new(item.inner, self.preview_type());
+                // match indices refer to the match key which isn't displayed
+                if !self.has_match_key {
+                    entry = entry.with_name_match_ranges(&item.match_indices);
+                }
+                match display {
+                    Some(display) => entry.with_display(display),
+                    None => entry,
+                }
             })
             .collect()
     }
 
     fn get_result(&self, index: u32) -> Option<Entry> {
         self.matcher.get_result(index).map(|item| {
-            let path = item.matched_string;
-            Entry::new(
-                path,
-                match &self.preview_kind {
-                    PreviewKind::Command(ref preview_command) => {
-                        PreviewType::Command(preview_command.clone())
-                    }
-                    PreviewKind::Builtin(preview_type) => preview_type.clone(),
-                    PreviewKind::None => PreviewType::None,
-                },
-            )
+            let display = self.display(&item.inner, item.matched_string);
+            let entry = Entry::new(item.inner, self.preview_type());
+            match display {
+                Some(display) => entry.with_display(display),
+                None => entry,
+            }
         })
     }
 
@@ -215,7 +809,44 @@ impl OnAir for Channel {
         self.matcher.status.running
     }
 
-    fn shutdown(&self) {}
+    fn loading(&self) -> bool {
+        self.matcher.loading()
+    }
+
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+        self.matcher.set_scheme(scheme);
+    }
+
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.matcher.set_prefix_bonus(prefix_bonus);
+    }
+
+    fn set_filter(&mut self, filter: Option<Regex>) {
+        self.matcher.set_filter(filter);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.matcher.set_paused(paused);
+        // the source (along with the processes it spawned) is stopped too,
+        // rather than piling up entries that won't be matched until resuming
+        #[cfg(unix)]
+        if let Some(child) = self.source_process.lock().unwrap().as_ref() {
+            signal_process_group(
+                child,
+                if paused { libc::SIGSTOP } else { libc::SIGCONT },
+            );
+        }
+    }
+
+    fn wait_for_matches(&mut self, timeout: Duration) {
+        self.matcher.wait_for_matches(timeout);
+    }
+
+    fn shutdown(&self) {
+        if let Some(mut child) = self.source_process.lock().unwrap().take() {
+            kill_process_group(&mut child);
+        }
+    }
 }
 
 #[derive(Clone, Debug, serde::Deserialize, PartialEq)]
@@ -225,6 +856,59 @@ pub struct CableChannelPrototype {
     pub preview_command: Option<String>,
     #[serde(default = "default_delimiter")]
     pub preview_delimiter: Option<String>,
+    /// The command used to preview entries that are directories.
+    pub preview_dir_command: Option<String>,
+    /// A template evaluating to the line the preview should scroll to.
+    pub preview_offset: Option<String>,
+    /// The command used to preview entries when `preview_command` fails.
+    pub preview_fallback_command: Option<String>,
+    /// The command used to preview the selected entries at once when several
+    /// of them are selected.
+    pub preview_multi_command: Option<String>,
+    /// The scoring scheme used to match entries (defaults to `text`).
+    pub scheme: Option<MatchingScheme>,
+    /// Whether to strip trailing whitespace (and carriage returns) from
+    /// entries when loading them.
+    #[serde(default)]
+    pub source_trim: bool,
+    /// A template for the string displayed and matched against for each
+    /// entry, while the raw entry is still used for previews and output.
+    pub source_display: Option<String>,
+    /// The delimiter used to split entries into fields for
+    /// `source_display` (defaults to " ").
+    pub source_delimiter: Option<String>,
+    /// A template for the string matched against for each entry, when it
+    /// should differ from what is displayed (e.g. a path's basename).
+    pub source_match_key: Option<String>,
+    /// Whether the source command keeps running and its output should be
+    /// streamed into the channel as it is produced.
+    #[serde(default)]
+    pub source_streaming: bool,
+    /// Whether to skip entries that were already loaded (compared on
+    /// `source_match_key` if set).
+    #[serde(default)]
+    pub source_dedup: bool,
+    /// A query applied when the channel is loaded (`--input` takes
+    /// precedence over it).
+    pub default_query: Option<String>,
+    /// A command listing the entries "inside" the selected entry (e.g. the
+    /// files of a package), using the same placeholders as
+    /// `preview_command`.
+    pub drill_command: Option<String>,
+    /// Names for the fields of entries (split by `source_delimiter`), which
+    /// templates can then reference as `{name}` rather than `{N}`.
+    pub source_fields: Option<Vec<String>>,
+    /// Display entries (or `source_display`) as paths relative to the
+    /// working directory or as absolute paths, while the raw entries are
+    /// still what gets previewed and output.
+    pub path_display: Option<PathDisplay>,
+    /// Gitignore-style globs (relative to the working directory) for entries
+    /// to skip, e.g. `["node_modules", "target/**"]`.
+    #[serde(default)]
+    pub source_ignore: Vec<String>,
+    /// Skip entries that are more than this many directories below the
+    /// working directory.
+    pub source_max_depth: Option<usize>,
 }
 
 pub const DEFAULT_DELIMITER: &str = " ";
@@ -250,3 +934,163 @@ impl Deref for CableChannels {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_trim_line() {
+        assert_eq!(trim_line("entry  ".to_string()), "entry");
+        assert_eq!(trim_line("entry\r".to_string()), "entry");
+        assert_eq!(trim_line("entry \t\r".to_string()), "entry");
+        assert_eq!(trim_line("  entry".to_string()), "  entry");
+    }
+
+    #[test]
+    fn test_expand_argv() {
+        assert_eq!(
+            expand_argv("git log {argv} --oneline", "'my branch' main"),
+            "git log 'my branch' main --oneline"
+        );
+        assert_eq!(expand_argv("ls {argv}", ""), "ls ");
+        assert_eq!(expand_argv("ls", "foo"), "ls");
+    }
+
+    #[test]
+    fn test_retain_unseen() {
+        let mut seen = FxHashSet::default();
+        let mut batch =
+            vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        retain_unseen(&mut batch, &mut seen, None, " ");
+        assert_eq!(batch, ["a", "b"]);
+
+        // duplicates across batches are skipped as well
+        let mut batch = vec!["b".to_string(), "c".to_string()];
+        retain_unseen(&mut batch, &mut seen, None, " ");
+        assert_eq!(batch, ["c"]);
+
+        // entries are compared on their match key
+        let mut seen = FxHashSet::default();
+        let mut batch =
+            vec!["1 ls".to_string(), "2 cd".to_string(), "3 ls".to_string()];
+        retain_unseen(&mut batch, &mut seen, Some("{1}"), " ");
+        assert_eq!(batch, ["1 ls", "2 cd"]);
+    }
+
+    #[test]
+    fn test_read_batches() {
+        let input = "a\r\n\nb  \nc";
+        let batches = std::cell::RefCell::new(Vec::new());
+        let produced =
+            read_batches(BufReader::new(input.as_bytes()), true, |batch| {
+                batches.borrow_mut().push(batch);
+            });
+        assert!(produced);
+        assert_eq!(batches.into_inner().concat(), vec!["a", "b", "c"]);
+
+        assert!(!read_batches(
+            BufReader::new("\n  \n".as_bytes()),
+            false,
+            |_| {}
+        ));
+    }
+
+    #[test]
+    fn test_name_fields() {
+        let fields = ["path", "size", "mtime"].map(String::from);
+        assert_eq!(
+            name_fields("{path} ({size}, {mtime})", &fields),
+            "{0} ({1}, {2})"
+        );
+        assert_eq!(
+            name_fields("${size} {} {1} {owner}", &fields),
+            "${size} {} {1} {owner}"
+        );
+    }
+
+    #[test]
+    fn test_format_display() {
+        let line = "a1b2c3 fix the thing";
+        assert_eq!(format_display("{1}", " ", line), "fix");
+        assert_eq!(format_display("{}", " ", line), line);
+        assert_eq!(
+            format_display("[{0}] {}", " ", line),
+            "[a1b2c3] a1b2c3 fix the thing"
+        );
+        assert_eq!(format_display("{1}|{7}", "|", "a|b"), "b|");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        let cwd = Path::new("/home/user/project");
+        let relative = |path| normalize_path(path, PathDisplay::Relative, cwd);
+        let absolute = |path| normalize_path(path, PathDisplay::Absolute, cwd);
+
+        assert_eq!(relative("/home/user/project/src/main.rs"), "src/main.rs");
+        assert_eq!(relative("./src/main.rs"), "src/main.rs");
+        assert_eq!(relative("/home/user/project"), ".");
+        // paths outside of the working directory stay absolute
+        assert_eq!(relative("/etc/hosts"), "/etc/hosts");
+        assert_eq!(
+            absolute("./src/main.rs"),
+            "/home/user/project/src/main.rs"
+        );
+        assert_eq!(absolute("/etc/hosts"), "/etc/hosts");
+        assert_eq!(absolute("."), "/home/user/project");
+    }
+
+    #[test]
+    fn test_path_filter() {
+        let cwd = Path::new("/home/user/project");
+        let filter = PathFilter::new(
+            &["node_modules".to_string(), "docs/*.md".to_string()],
+            Some(2),
+            cwd,
+        )
+        .unwrap();
+
+        assert!(filter.keeps("src/main.rs"));
+        assert!(!filter.keeps("web/node_modules/react/index.js"));
+        assert!(!filter.keeps("/home/user/project/node_modules/a.js"));
+        assert!(!filter.keeps("docs/README.md"));
+        assert!(filter.keeps("docs/README.txt"));
+        // too deep
+        assert!(!filter.keeps("src/channels/cable.rs"));
+        assert!(!filter.keeps("./docs/img/logo.png"));
+        assert!(filter.keeps("/etc/hosts"));
+        assert!(PathFilter::new(&[], None, cwd).is_none());
+    }
+
+    #[test]
+    fn test_reload() {
+        let runs = std::env::temp_dir()
+            .join(format!("tv-reload-source-{}", std::process::id()));
+        let mut channel = Channel::new(
+            "test",
+            &format!("echo run >> '{0}'; wc -l < '{0}'", runs.display()),
+            None,
+            SourceOptions::default(),
+        );
+        let entries = |channel: &mut Channel| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while (channel.loading() || channel.running())
+                && Instant::now() < deadline
+            {
+                channel.wait_for_matches(Duration::from_millis(10));
+            }
+            channel
+                .results(10, 0)
+                .into_iter()
+                .map(|entry| entry.name.trim_start().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(entries(&mut channel), ["1"]);
+        // the source runs again and its entries replace the previous ones
+        channel.reload();
+        assert_eq!(entries(&mut channel), ["2"]);
+        std::fs::remove_file(runs).unwrap();
+    }
+}
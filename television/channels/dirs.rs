@@ -1,11 +1,17 @@
 use crate::channels::entry::{Entry, PreviewCommand, PreviewType};
 use crate::channels::{OnAir, TelevisionChannel};
-use crate::matcher::{config::Config, injector::Injector, Matcher};
+use crate::matcher::{
+    config::{Config, MatchingScheme},
+    injector::Injector,
+    Matcher,
+};
 use crate::utils::files::{walk_builder, DEFAULT_NUM_THREADS};
 use devicons::FileIcon;
+use regex::Regex;
 use rustc_hash::{FxBuildHasher, FxHashSet};
 use std::collections::HashSet;
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub struct Channel {
     matcher: Matcher<String>,
@@ -139,6 +145,30 @@ impl OnAir for Channel {
         self.matcher.status.running
     }
 
+    fn loading(&self) -> bool {
+        self.matcher.loading()
+    }
+
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+        self.matcher.set_scheme(scheme);
+    }
+
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.matcher.set_prefix_bonus(prefix_bonus);
+    }
+
+    fn set_filter(&mut self, filter: Option<Regex>) {
+        self.matcher.set_filter(filter);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.matcher.set_paused(paused);
+    }
+
+    fn wait_for_matches(&mut self, timeout: Duration) {
+        self.matcher.wait_for_matches(timeout);
+    }
+
     fn shutdown(&self) {
         self.crawl_handle.abort();
     }
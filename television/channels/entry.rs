@@ -16,6 +16,10 @@ use strum::EnumString;
 pub struct Entry {
     /// The name of the entry.
     pub name: String,
+    /// An optional string displayed in place of the name.
+    ///
+    /// When set, the name match ranges refer to this string.
+    pub display: Option<String>,
     /// An optional value associated with the entry.
     pub value: Option<String>,
     /// The optional ranges for matching characters in the name.
@@ -100,6 +104,7 @@ impl Entry {
     pub fn new(name: String, preview_type: PreviewType) -> Self {
         Self {
             name,
+            display: None,
             value: None,
             name_match_ranges: None,
             value_match_ranges: None,
@@ -109,6 +114,16 @@ impl Entry {
         }
     }
 
+    pub fn with_display(mut self, display: String) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    /// The string to display for this entry (defaults to its name).
+    pub fn display_name(&self) -> &str {
+        self.display.as_deref().unwrap_or(&self.name)
+    }
+
     pub fn with_value(mut self, value: String) -> Self {
         self.value = Some(value);
         self
@@ -151,6 +166,7 @@ impl Entry {
 
 pub const ENTRY_PLACEHOLDER: Entry = Entry {
     name: String::new(),
+    display: None,
     value: None,
     name_match_ranges: None,
     value_match_ranges: None,
@@ -163,6 +179,18 @@ pub const ENTRY_PLACEHOLDER: Entry = Entry {
 pub struct PreviewCommand {
     pub command: String,
     pub delimiter: String,
+    /// An optional command to use instead of `command` when the entry is a
+    /// directory.
+    pub dir_command: Option<String>,
+    /// An optional template (e.g. `{1}`) evaluating to the line the preview
+    /// should be scrolled to.
+    pub offset: Option<String>,
+    /// An optional command to run when `command` exits with a non-zero
+    /// status.
+    pub fallback_command: Option<String>,
+    /// An optional command used to preview several selected entries at once
+    /// (e.g. to diff them).
+    pub multi_command: Option<String>,
 }
 
 impl PreviewCommand {
@@ -170,10 +198,49 @@ impl PreviewCommand {
         Self {
             command: command.to_string(),
             delimiter: delimiter.to_string(),
+            dir_command: None,
+            offset: None,
+            fallback_command: None,
+            multi_command: None,
         }
     }
+
+    pub fn with_dir_command(mut self, dir_command: &str) -> Self {
+        self.dir_command = Some(dir_command.to_string());
+        self
+    }
+
+    pub fn with_offset(mut self, offset: &str) -> Self {
+        self.offset = Some(offset.to_string());
+        self
+    }
+
+    pub fn with_fallback_command(mut self, fallback_command: &str) -> Self {
+        self.fallback_command = Some(fallback_command.to_string());
+        self
+    }
+
+    pub fn with_multi_command(mut self, multi_command: &str) -> Self {
+        self.multi_command = Some(multi_command.to_string());
+        self
+    }
+
+    /// Whether any of the commands references the current query through the
+    /// `TV_QUERY` environment variable, in which case previews depend on the
+    /// query as well as on the entry.
+    pub fn uses_query(&self) -> bool {
+        std::iter::once(&self.command)
+            .chain(&self.dir_command)
+            .chain(&self.fallback_command)
+            .chain(&self.multi_command)
+            .any(|command| command.contains(QUERY_ENV_VAR))
+    }
 }
 
+/// The environment variable holding the current query when running preview
+/// commands.
+pub const QUERY_ENV_VAR: &str = "TV_QUERY";
+
 impl Display for PreviewCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -224,6 +291,7 @@ mod tests {
     fn test_leaves_name_intact() {
         let entry = Entry {
             name: "test name with spaces".to_string(),
+            display: None,
             value: None,
             name_match_ranges: None,
             value_match_ranges: None,
@@ -238,6 +306,7 @@ mod tests {
         let a: usize = 10;
         let entry = Entry {
             name: "test_file_name.rs".to_string(),
+            display: None,
             value: None,
             name_match_ranges: None,
             value_match_ranges: None,
@@ -247,4 +316,13 @@ mod tests {
         };
         assert_eq!(entry.stdout_repr(), "test_file_name.rs:10");
     }
+
+    #[test]
+    fn test_preview_command_uses_query() {
+        let command = PreviewCommand::new("bat -n {}", " ");
+        assert!(!command.uses_query());
+        assert!(command
+            .with_fallback_command("rg --passthru \"$TV_QUERY\" {}")
+            .uses_query());
+    }
 }
@@ -1,11 +1,16 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
 use devicons::FileIcon;
+use regex::Regex;
 use rustc_hash::{FxBuildHasher, FxHashSet};
 
 use super::OnAir;
 use crate::channels::entry::{Entry, PreviewType};
-use crate::matcher::{config::Config, Matcher};
+use crate::matcher::{
+    config::{Config, MatchingScheme},
+    Matcher,
+};
 use crate::utils::indices::sep_name_and_value_indices;
 
 #[derive(Debug, Clone)]
@@ -130,5 +135,29 @@ impl OnAir for Channel {
         self.matcher.status.running
     }
 
+    fn loading(&self) -> bool {
+        self.matcher.loading()
+    }
+
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+        self.matcher.set_scheme(scheme);
+    }
+
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.matcher.set_prefix_bonus(prefix_bonus);
+    }
+
+    fn set_filter(&mut self, filter: Option<Regex>) {
+        self.matcher.set_filter(filter);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.matcher.set_paused(paused);
+    }
+
+    fn wait_for_matches(&mut self, timeout: Duration) {
+        self.matcher.wait_for_matches(timeout);
+    }
+
     fn shutdown(&self) {}
 }
@@ -1,6 +1,9 @@
 use crate::channels::entry::Entry;
+use crate::matcher::config::MatchingScheme;
 use color_eyre::Result;
+use regex::Regex;
 use rustc_hash::FxHashSet;
+use std::time::{Duration, Instant};
 use television_derive::{Broadcast, ToCliChannel, ToUnitChannel};
 
 pub mod alias;
@@ -14,6 +17,17 @@ pub mod remote_control;
 pub mod stdin;
 pub mod text;
 
+/// How often to check whether the channel has finished loading and matching
+/// in `--filter` and `--server` modes.
+const FILTER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long a streaming source must go without producing entries for the
+/// ones produced so far to be filtered.
+const STREAMING_IDLE_TIME: Duration = Duration::from_millis(200);
+
+/// The longest a streaming source that never goes idle is waited for.
+const STREAMING_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// The interface that all television channels must implement.
 ///
 /// # Note
@@ -82,6 +96,30 @@ pub trait OnAir: Send {
     /// Check if the channel is currently running.
     fn running(&self) -> bool;
 
+    /// Whether entries are still being loaded from the channel's source.
+    ///
+    /// Unlike `running`, this doesn't depend on the matcher having been
+    /// ticked, but the last entries loaded might not be matched yet.
+    fn loading(&self) -> bool;
+
+    /// Set the scoring scheme used to match entries.
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme);
+
+    /// Set the score added to entries whose basename starts with the query.
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32);
+
+    /// Only list the entries matching `filter` (or all of them with `None`),
+    /// whatever the query.
+    fn set_filter(&mut self, filter: Option<Regex>);
+
+    /// Suspend (or resume) matching, keeping the current results.
+    fn set_paused(&mut self, paused: bool);
+
+    /// Wait (at most `timeout`) for the results of the last pattern passed
+    /// to `find` to be available. A zero `timeout` moves matching forward
+    /// without waiting.
+    fn wait_for_matches(&mut self, timeout: Duration);
+
     /// Turn off
     fn shutdown(&self);
 }
@@ -174,6 +212,102 @@ impl TelevisionChannel {
             _ => unreachable!(),
         }
     }
+
+    /// The name of the channel, as used on the command line.
+    pub fn name(&self) -> String {
+        match self {
+            TelevisionChannel::Cable(channel) => channel.name().to_string(),
+            TelevisionChannel::RemoteControl(_) => {
+                "remote-control".to_string()
+            }
+            _ => UnitChannel::from(self).to_string(),
+        }
+    }
+
+    /// The query the channel starts with, if it defines one.
+    pub fn default_query(&self) -> Option<&str> {
+        match self {
+            TelevisionChannel::Cable(channel) => channel.default_query(),
+            _ => None,
+        }
+    }
+
+    /// Whether the channel's entries have a preview (channels without one
+    /// never show the preview panel, whatever its visibility setting).
+    pub fn has_preview(&self) -> bool {
+        match self {
+            TelevisionChannel::Cable(channel) => channel.has_preview(),
+            TelevisionChannel::RemoteControl(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Whether the channel's source keeps running and producing entries.
+    pub fn streaming(&self) -> bool {
+        match self {
+            TelevisionChannel::Cable(channel) => channel.streaming(),
+            _ => false,
+        }
+    }
+
+    /// Run the channel's source again, if it has one that can be reloaded.
+    pub fn reload(&mut self) {
+        if let TelevisionChannel::Cable(channel) = self {
+            channel.reload();
+        }
+    }
+
+    /// Wait for the channel to finish loading and return the entries
+    /// matching the query (at most `max_results` if given), best matches
+    /// first.
+    ///
+    /// Streaming sources never finish loading: the entries they produced
+    /// are filtered once they stop producing new ones for a moment (or after
+    /// `STREAMING_TIMEOUT` for sources that never do).
+    pub async fn filter(
+        &mut self,
+        query: &str,
+        max_results: Option<u32>,
+    ) -> Vec<Entry> {
+        self.find(query);
+        let start = Instant::now();
+        let mut last_loaded = start;
+        let mut total_count = self.total_count();
+        loop {
+            // fetching results ticks the matcher, which updates its status
+            self.results(0, 0);
+            if !self.running() {
+                break;
+            }
+            if self.streaming() {
+                let now = Instant::now();
+                if self.total_count() != total_count {
+                    total_count = self.total_count();
+                    last_loaded = now;
+                } else if now - last_loaded >= STREAMING_IDLE_TIME
+                    || now - start >= STREAMING_TIMEOUT
+                {
+                    // the last entries loaded might not be matched yet
+                    self.wait_for_matches(STREAMING_TIMEOUT);
+                    break;
+                }
+            }
+            tokio::time::sleep(FILTER_POLL_INTERVAL).await;
+        }
+        let count = self.result_count();
+        self.results(max_results.map_or(count, |max| max.min(count)), 0)
+    }
+
+    /// The channel listing the entries of `entry`, if the channel supports
+    /// drilling into its entries.
+    pub fn drill_into(&self, entry: &Entry) -> Option<TelevisionChannel> {
+        match self {
+            TelevisionChannel::Cable(channel) => {
+                channel.drill_into(entry).map(TelevisionChannel::Cable)
+            }
+            _ => None,
+        }
+    }
 }
 
 macro_rules! variant_to_module {
@@ -298,3 +432,60 @@ define_transitions! {
     Dirs => [Files, Text, Dirs],
     GitRepos => [Files, Text, Dirs],
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_filter_streaming_source() {
+        let mut channel = TelevisionChannel::Cable(cable::Channel::new(
+            "test",
+            "echo a; echo b; sleep 30",
+            None,
+            cable::SourceOptions {
+                streaming: true,
+                ..cable::SourceOptions::default()
+            },
+        ));
+
+        let entries = tokio::time::timeout(
+            Duration::from_secs(10),
+            channel.filter("b", None),
+        )
+        .await
+        .expect("filtering a streaming source shouldn't wait for it to end");
+        channel.shutdown();
+
+        let names =
+            entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["b"]);
+    }
+
+    #[tokio::test]
+    async fn test_serve_streaming_source() {
+        // `--server` mode filters the same channel once per query
+        let mut channel = TelevisionChannel::Cable(cable::Channel::new(
+            "test",
+            "echo a1; echo a2; echo b; sleep 30",
+            None,
+            cable::SourceOptions {
+                streaming: true,
+                ..cable::SourceOptions::default()
+            },
+        ));
+
+        for (query, expected) in [("a", ["a1"]), ("b", ["b"])] {
+            let entries = tokio::time::timeout(
+                Duration::from_secs(10),
+                channel.filter(query, Some(1)),
+            )
+            .await
+            .expect("queries on a streaming source should be answered");
+            let names =
+                entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+            assert_eq!(names, expected);
+        }
+        channel.shutdown();
+    }
+}
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
 use std::fmt::Display;
+use std::time::Duration;
 
 use crate::channels::cable::{CableChannelPrototype, CableChannels};
 use crate::channels::entry::{Entry, PreviewType};
 use crate::channels::{CliTvChannel, OnAir, TelevisionChannel, UnitChannel};
-use crate::matcher::{config::Config, Matcher};
+use crate::matcher::{
+    config::{Config, MatchingScheme},
+    Matcher,
+};
 use clap::ValueEnum;
 use color_eyre::Result;
 use devicons::FileIcon;
+use regex::Regex;
 use rustc_hash::{FxBuildHasher, FxHashSet};
 
 use super::cable;
@@ -21,7 +26,7 @@ pub struct RemoteControl {
 #[derive(Clone)]
 pub enum RCButton {
     Channel(UnitChannel),
-    CableChannel(CableChannelPrototype),
+    CableChannel(Box<CableChannelPrototype>),
 }
 
 impl Display for RCButton {
@@ -36,24 +41,41 @@ impl Display for RCButton {
 const NUM_THREADS: usize = 1;
 
 impl RemoteControl {
+    /// Create a new remote control from the given builtin and cable channels.
+    ///
+    /// Channels whose names appear in `pinned` are listed first (in the order
+    /// in which they appear in `pinned`), followed by the remaining channels.
+    /// Pinned names that don't match any available channel are ignored.
     pub fn new(
         builtin_channels: Vec<UnitChannel>,
         cable_channels: Option<CableChannels>,
+        pinned: &[String],
     ) -> Self {
         let matcher = Matcher::new(Config::default().n_threads(NUM_THREADS));
         let injector = matcher.injector();
-        let buttons =
-            builtin_channels.into_iter().map(RCButton::Channel).chain(
+        let mut buttons = builtin_channels
+            .into_iter()
+            .map(RCButton::Channel)
+            .chain(
                 cable_channels
                     .as_ref()
                     .map(|channels| {
                         channels.iter().map(|(_, prototype)| {
-                            RCButton::CableChannel(prototype.clone())
+                            RCButton::CableChannel(Box::new(prototype.clone()))
                         })
                     })
                     .into_iter()
                     .flatten(),
-            );
+            )
+            .collect::<Vec<_>>();
+        // stable sort: unpinned channels keep their original relative order
+        buttons.sort_by_key(|button| {
+            let name = button.to_string();
+            pinned
+                .iter()
+                .position(|p| *p == name)
+                .unwrap_or(pinned.len())
+        });
         for button in buttons {
             let () = injector.push(button.clone(), |e, cols| {
                 cols[0] = e.to_string().into();
@@ -69,7 +91,7 @@ impl RemoteControl {
     pub fn with_transitions_from(
         television_channel: &TelevisionChannel,
     ) -> Self {
-        Self::new(television_channel.available_transitions(), None)
+        Self::new(television_channel.available_transitions(), None, &[])
     }
 
     pub fn zap(&self, channel_name: &str) -> Result<TelevisionChannel> {
@@ -100,6 +122,7 @@ impl Default for RemoteControl {
                 .flat_map(|v| UnitChannel::try_from(v.to_string().as_str()))
                 .collect(),
             None,
+            &[],
         )
     }
 }
@@ -180,5 +203,29 @@ impl OnAir for RemoteControl {
         self.matcher.status.running
     }
 
+    fn loading(&self) -> bool {
+        self.matcher.loading()
+    }
+
+    fn set_matching_scheme(&mut self, scheme: MatchingScheme) {
+        self.matcher.set_scheme(scheme);
+    }
+
+    fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.matcher.set_prefix_bonus(prefix_bonus);
+    }
+
+    fn set_filter(&mut self, filter: Option<Regex>) {
+        self.matcher.set_filter(filter);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.matcher.set_paused(paused);
+    }
+
+    fn wait_for_matches(&mut self, timeout: Duration) {
+        self.matcher.wait_for_matches(timeout);
+    }
+
     fn shutdown(&self) {}
 }
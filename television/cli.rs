@@ -3,17 +3,21 @@ use std::path::Path;
 
 use clap::{Parser, Subcommand, ValueEnum};
 use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
 use tracing::debug;
 
 use crate::channels::{
     cable::CableChannelPrototype, entry::PreviewCommand, CliTvChannel,
 };
+use crate::matcher::config::MatchingScheme;
+use crate::preview::previewers::command::{validate_command, validate_offset};
 use crate::utils::shell::Shell as UtilShell;
 use crate::{
     cable,
     config::{get_config_dir, get_data_dir},
 };
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Parser, Debug)]
 #[command(author, version = version(), about)]
 pub struct Cli {
@@ -21,14 +25,71 @@ pub struct Cli {
     #[arg(value_enum, default_value = "files", index = 1)]
     pub channel: String,
 
+    /// Read the channel definition (a cable channel prototype in TOML format)
+    /// from the given file, or from stdin if set to "-"
+    #[arg(long, value_name = "PATH")]
+    pub channel_file: Option<String>,
+
+    /// Load environment variables from the given dotenv-style file
+    ///
+    /// These are available to all source and preview commands.
+    #[arg(long, value_name = "PATH")]
+    pub env_file: Option<String>,
+
+    /// Load keybindings from the given TOML file
+    ///
+    /// The file uses the same format as the `[keybindings]` section of the
+    /// configuration file and takes precedence over it.
+    #[arg(long, value_name = "PATH")]
+    pub bind_file: Option<String>,
+
     /// Use a custom preview command (currently only supported by the stdin channel)
     #[arg(short, long, value_name = "STRING")]
     pub preview: Option<String>,
 
-    /// Disable the preview pane
+    /// A template evaluating to the line the preview should scroll to, using
+    /// the same placeholders as the preview command (e.g. "{1}"), optionally
+    /// followed by a column to highlight (e.g. "{1}:{2}"), or "/" followed
+    /// by a string to scroll to the first line containing (e.g. "/## {1}")
+    #[arg(long, value_name = "STRING", requires = "preview")]
+    pub preview_offset: Option<String>,
+
+    /// A fixed title for the preview panel's border, displayed instead of the
+    /// name of the previewed entry (overrides the `ui.preview_panel.label`
+    /// setting)
+    #[arg(long, value_name = "STRING")]
+    pub preview_border_label: Option<String>,
+
+    /// The maximum number of preview commands running at the same time
+    /// (overrides the `previewers.command.max_concurrent` setting)
+    #[arg(long, value_name = "N")]
+    pub preview_concurrency: Option<u8>,
+
+    /// Disable the preview pane: previews are never computed and the pane
+    /// can't be toggled
     #[arg(long, default_value = "false")]
     pub no_preview: bool,
 
+    /// Start with the preview pane hidden, previews still being computed so
+    /// that toggling the pane shows them right away (overrides the
+    /// `ui.show_preview_panel` setting)
+    #[arg(long, default_value = "false", conflicts_with = "no_preview")]
+    pub preview_hidden: bool,
+
+    /// Wrap results that are too long for the results panel onto several
+    /// lines (overrides the `ui.results_panel.wrap` setting)
+    #[arg(long, default_value = "false")]
+    pub wrap_results: bool,
+
+    /// Capture mouse events (overrides the `ui.mouse` setting)
+    #[arg(long, default_value = "false", conflicts_with = "no_mouse")]
+    pub mouse: bool,
+
+    /// Don't capture mouse events, keeping the terminal's native text
+    /// selection and scrollback (overrides the `ui.mouse` setting)
+    #[arg(long, default_value = "false")]
+    pub no_mouse: bool,
+
     /// The delimiter used to extract fields from the entry to provide to the preview command
     /// (defaults to " ")
     #[arg(long, value_name = "STRING", default_value = " ", value_parser = delimiter_parser)]
@@ -42,6 +103,20 @@ pub struct Cli {
     #[arg(short, long, value_name = "FLOAT")]
     pub frame_rate: Option<f64>,
 
+    /// Exit automatically after this many seconds without any user input
+    /// (0 disables the timeout)
+    #[arg(long, value_name = "SECONDS")]
+    pub idle_timeout: Option<u64>,
+
+    /// The maximum number of threads used by the fuzzy matcher (0 means the
+    /// number of available cores)
+    #[arg(long, value_name = "INT")]
+    pub matcher_threads: Option<usize>,
+
+    /// Enable debugging actions such as `dump_frame`
+    #[arg(long, default_value = "false")]
+    pub debug: bool,
+
     /// Passthrough keybindings (comma separated, e.g. "q,ctrl-w,ctrl-t") These keybindings will
     /// trigger selection of the current entry and be passed through to stdout along with the entry
     /// to be handled by the parent process.
@@ -60,15 +135,133 @@ pub struct Cli {
     #[arg(long, value_name = "STRING")]
     pub autocomplete_prompt: Option<String>,
 
+    /// The scoring scheme used to rank matches: `path` favors matches on path
+    /// segments (e.g. file basenames) while `text` is suited to arbitrary text.
+    /// Overrides the channel's own setting (which defaults to `text`).
+    #[arg(long, value_enum, value_name = "SCHEME")]
+    pub scheme: Option<MatchingScheme>,
+
+    /// The file to which all currently matched entries are written when
+    /// triggering the `export_results` action
+    #[arg(long, value_name = "PATH")]
+    pub export_file: Option<String>,
+
+    /// Once the channel has finished loading, select the entry that exactly
+    /// matches this value. If no such entry exists, the value is used as the
+    /// search query instead.
+    #[arg(long, value_name = "STRING")]
+    pub goto: Option<String>,
+
+    /// Immediately confirm the entry selected with `--goto` and exit
+    #[arg(long, default_value = "false", requires = "goto")]
+    pub goto_confirm: bool,
+
+    /// Wait for the channel to finish loading before displaying the UI.
+    ///
+    /// This avoids results popping in incrementally and is mostly useful for
+    /// small, fast sources.
+    #[arg(long, default_value = "false")]
+    pub sync: bool,
+
+    /// Shell-quote every selected entry when printing it.
+    ///
+    /// This overrides the `output.shell_escaping` configuration setting.
+    #[arg(long, default_value = "false")]
+    pub shell_quote: bool,
+
+    /// Terminate every printed line with a NUL character instead of a
+    /// newline (e.g. to pipe the output into `xargs -0`)
+    #[arg(long, default_value = "false")]
+    pub print0: bool,
+
+    /// Prefix every printed entry with its rank among the matched entries
+    /// (starting at 0) followed by a tab.
+    ///
+    /// Selected entries that don't match the current query get an empty
+    /// rank. Selected entries are printed in rank order.
+    #[arg(long, default_value = "false")]
+    pub with_index: bool,
+
+    /// Write the output (selected entries and passthrough key) to the given
+    /// file instead of stdout, e.g. for editor integrations that can't
+    /// capture stdout
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Print the output to stdout as well when using `--output-file`
+    #[arg(long, default_value = "false", requires = "output_file")]
+    pub tee: bool,
+
+    /// Send confirmed selections to the given named pipe instead of exiting.
+    ///
+    /// Each confirmation writes the selected entries to the pipe, one entry
+    /// per line, and television keeps running. The pipe is opened on the
+    /// first confirmation and reopened if the reading process went away in
+    /// the meantime. On non-Unix platforms, entries are appended to the given
+    /// file instead.
+    #[arg(long, value_name = "PATH")]
+    pub selection_pipe: Option<String>,
+
+    /// Pass the output of preview commands through without replacing
+    /// non-printable characters (e.g. to keep OSC hyperlinks)
+    #[arg(long, default_value = "false")]
+    pub no_ansi_strip: bool,
+
+    /// Filter the channel's entries with the given query and print the
+    /// matches without starting the UI (exits with 1 if nothing matches)
+    #[arg(long, value_name = "QUERY")]
+    pub filter: Option<String>,
+
+    /// Only list the entries matching the given regex, whatever the query.
+    ///
+    /// The filter is applied before fuzzy matching, so the query only
+    /// searches the filtered entries. It can be changed from the UI with the
+    /// `edit_filter_pattern` action.
+    #[arg(long, value_name = "REGEX")]
+    pub filter_pattern: Option<String>,
+
+    /// Load the channel's entries once, then read queries from stdin (one
+    /// per line) and print the best matches for each without starting the
+    /// UI.
+    ///
+    /// The matches of each query are followed by the `--server-delimiter`
+    /// line, so that the reading process knows when a response is complete.
+    /// Television exits when stdin is closed.
+    #[arg(long, default_value = "false", conflicts_with_all = ["filter", "output_file"])]
+    pub server: bool,
+
+    /// The maximum number of matches printed for each query in `--server`
+    /// mode
+    #[arg(long, value_name = "N", default_value = "10", requires = "server")]
+    pub server_limit: u32,
+
+    /// The line printed after the matches of each query in `--server` mode
+    #[arg(
+        long,
+        value_name = "STRING",
+        default_value = "",
+        requires = "server"
+    )]
+    pub server_delimiter: String,
+
+    /// Extra arguments substituted for `{argv}` in the channel's source
+    /// command (each one shell-quoted)
+    #[arg(last = true, index = 3, value_name = "ARGS")]
+    pub source_args: Vec<String>,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
 
-#[derive(Subcommand, Debug, PartialEq)]
+#[derive(Subcommand, Debug, Clone, PartialEq)]
 pub enum Command {
     /// Lists available channels
     ListChannels,
-    /// Initializes shell completion ("tv init zsh")
+    /// Clones or updates the channel repositories listed in the
+    /// `channel_repos` setting
+    UpdateChannels,
+    /// Initializes shell completion ("tv init zsh") or the tmux popup
+    /// integration ("tv init tmux")
     #[clap(name = "init")]
     InitShell {
         /// The shell for which to generate the autocompletion script
@@ -84,6 +277,7 @@ pub enum Shell {
     Fish,
     PowerShell,
     Cmd,
+    Tmux,
 }
 
 impl From<Shell> for UtilShell {
@@ -94,26 +288,70 @@ impl From<Shell> for UtilShell {
             Shell::Fish => UtilShell::Fish,
             Shell::PowerShell => UtilShell::PowerShell,
             Shell::Cmd => UtilShell::Cmd,
+            Shell::Tmux => UtilShell::Tmux,
         }
     }
 }
 
-#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
 pub struct PostProcessedCli {
     pub channel: ParsedCliChannel,
+    pub channel_file: Option<String>,
+    pub env_file: Option<String>,
+    pub bind_file: Option<String>,
     pub preview_command: Option<PreviewCommand>,
+    pub preview_border_label: Option<String>,
+    pub preview_concurrency: Option<u8>,
     pub no_preview: bool,
+    pub preview_hidden: bool,
+    pub wrap_results: bool,
+    pub mouse: bool,
+    pub no_mouse: bool,
     pub tick_rate: Option<f64>,
     pub frame_rate: Option<f64>,
+    pub idle_timeout: Option<u64>,
+    pub matcher_threads: Option<usize>,
+    pub debug: bool,
     pub passthrough_keybindings: Vec<String>,
     pub input: Option<String>,
     pub command: Option<Command>,
     pub working_directory: Option<String>,
     pub autocomplete_prompt: Option<String>,
+    pub scheme: Option<MatchingScheme>,
+    pub export_file: Option<String>,
+    pub goto: Option<String>,
+    pub goto_confirm: bool,
+    pub sync: bool,
+    pub shell_quote: bool,
+    pub print0: bool,
+    pub with_index: bool,
+    pub output_file: Option<String>,
+    pub tee: bool,
+    pub selection_pipe: Option<String>,
+    pub no_ansi_strip: bool,
+    pub filter: Option<String>,
+    pub filter_pattern: Option<Regex>,
+    pub server: bool,
+    pub server_limit: u32,
+    pub server_delimiter: String,
+    pub source_args: Vec<String>,
 }
 
 impl From<Cli> for PostProcessedCli {
     fn from(cli: Cli) -> Self {
+        if let Err(e) = validate_templates(&cli) {
+            cli_parsing_error_exit(&e.to_string());
+        }
+
+        let filter_pattern =
+            match cli.filter_pattern.as_deref().map(Regex::new) {
+                Some(Err(e)) => cli_parsing_error_exit(&format!(
+                    "invalid --filter-pattern regex: {e}"
+                )),
+                filter_pattern => filter_pattern.and_then(Result::ok),
+            };
+
         let passthrough_keybindings = cli
             .passthrough_keybindings
             .unwrap_or_default()
@@ -124,6 +362,10 @@ impl From<Cli> for PostProcessedCli {
         let preview_command = cli.preview.map(|preview| PreviewCommand {
             command: preview,
             delimiter: cli.delimiter.clone(),
+            dir_command: None,
+            offset: cli.preview_offset,
+            fallback_command: None,
+            multi_command: None,
         });
 
         let channel: ParsedCliChannel;
@@ -151,45 +393,127 @@ impl From<Cli> for PostProcessedCli {
 
         Self {
             channel,
+            channel_file: cli.channel_file,
+            env_file: cli.env_file,
+            bind_file: cli.bind_file,
             preview_command,
+            preview_border_label: cli.preview_border_label,
+            preview_concurrency: cli.preview_concurrency,
             no_preview: cli.no_preview,
+            preview_hidden: cli.preview_hidden,
+            wrap_results: cli.wrap_results,
+            mouse: cli.mouse,
+            no_mouse: cli.no_mouse,
             tick_rate: cli.tick_rate,
             frame_rate: cli.frame_rate,
+            idle_timeout: cli.idle_timeout,
+            matcher_threads: cli.matcher_threads,
+            debug: cli.debug,
             passthrough_keybindings,
             input: cli.input,
             command: cli.command,
             working_directory,
             autocomplete_prompt: cli.autocomplete_prompt,
+            scheme: cli.scheme,
+            export_file: cli.export_file,
+            goto: cli.goto,
+            goto_confirm: cli.goto_confirm,
+            sync: cli.sync,
+            shell_quote: cli.shell_quote,
+            print0: cli.print0,
+            with_index: cli.with_index,
+            output_file: cli.output_file,
+            tee: cli.tee,
+            selection_pipe: cli.selection_pipe,
+            no_ansi_strip: cli.no_ansi_strip,
+            filter: cli.filter,
+            filter_pattern,
+            server: cli.server,
+            server_limit: cli.server_limit,
+            server_delimiter: cli.server_delimiter,
+            source_args: cli.source_args,
         }
     }
 }
 
+/// Check the templates passed on the command line so that typos are
+/// reported before starting rather than silently ignored when rendering.
+fn validate_templates(cli: &Cli) -> Result<()> {
+    if let Some(preview) = &cli.preview {
+        validate_command(preview).map_err(|e| {
+            eyre!("invalid --preview template {preview:?}: {e}")
+        })?;
+    }
+    if let Some(offset) = &cli.preview_offset {
+        validate_offset(offset).map_err(|e| {
+            eyre!("invalid --preview-offset template {offset:?}: {e}")
+        })?;
+    }
+    Ok(())
+}
+
 fn unknown_channel_exit(channel: &str) {
     eprintln!("Unknown channel: {channel}\n");
     std::process::exit(1);
 }
 
+/// Report an invalid command line argument and exit.
+pub fn cli_parsing_error_exit(message: &str) -> ! {
+    eprintln!("Error parsing CLI arguments: {message}\n");
+    std::process::exit(1);
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParsedCliChannel {
     Builtin(CliTvChannel),
-    Cable(CableChannelPrototype),
+    Cable(Box<CableChannelPrototype>),
 }
 
 fn parse_channel(channel: &str) -> Result<ParsedCliChannel> {
     let cable_channels = cable::load_cable_channels().unwrap_or_default();
     // try to parse the channel as a cable channel
-    cable_channels
+    if let Some(name) = resolve_channel_name(
+        channel,
+        cable_channels.keys().map(String::as_str),
+    ) {
+        return Ok(ParsedCliChannel::Cable(Box::new(
+            cable_channels[name].clone(),
+        )));
+    }
+    // try to parse the channel as a builtin channel
+    let builtin_channels = CliTvChannel::all_channels();
+    resolve_channel_name(channel, builtin_channels.iter().map(String::as_str))
+        .and_then(|name| CliTvChannel::try_from(name).ok())
+        .map(ParsedCliChannel::Builtin)
+        .ok_or_else(|| eyre!("Unknown channel: {}", channel))
+}
+
+/// Find the channel called `name` among `names`, ignoring case.
+///
+/// An exact match is preferred over a case-insensitive one, and a warning is
+/// printed whenever the case of `name` differs from the resolved channel's or
+/// several channels only differ by case.
+fn resolve_channel_name<'a>(
+    name: &str,
+    names: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut matches: Vec<&str> =
+        names.filter(|n| n.eq_ignore_ascii_case(name)).collect();
+    matches.sort_unstable();
+    let resolved = matches
         .iter()
-        .find(|(k, _)| k.to_lowercase() == channel)
-        .map_or_else(
-            || {
-                // try to parse the channel as a builtin channel
-                CliTvChannel::try_from(channel)
-                    .map(ParsedCliChannel::Builtin)
-                    .map_err(|_| eyre!("Unknown channel: {}", channel))
-            },
-            |(_, v)| Ok(ParsedCliChannel::Cable(v.clone())),
-        )
+        .find(|n| **n == name)
+        .or(matches.first())
+        .copied()?;
+    if matches.len() > 1 {
+        eprintln!(
+            "Warning: several channels match `{name}` ignoring case ({}), using `{resolved}`",
+            matches.join(", ")
+        );
+    } else if resolved != name {
+        eprintln!("Warning: using channel `{resolved}` for `{name}`");
+    }
+    Some(resolved)
 }
 
 pub fn list_cable_channels() -> Vec<String> {
@@ -213,11 +537,49 @@ pub fn list_channels() {
         println!("\t{c}");
     }
     println!("\n\x1b[4mCustom channels:\x1b[0m");
-    for c in list_cable_channels().iter().map(|c| c.to_lowercase()) {
+    for c in list_cable_channels() {
         println!("\t{c}");
     }
 }
 
+/// The last command of a prompt made of several commands separated by `&&`,
+/// `||`, `|`, `;` or `&`.
+///
+/// Separators are only looked for outside of quotes and escapes, following
+/// the shell's rules, and the `&` of redirections (e.g. `2>&1`) doesn't
+/// separate commands.
+fn last_command(prompt: &str) -> &str {
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut previous = None;
+    for (index, c) in prompt.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            } else if c == '\\' && q == '"' {
+                escaped = true;
+            }
+        } else {
+            match c {
+                '\\' => escaped = true,
+                '\'' | '"' => quote = Some(c),
+                '|' | ';' => start = index + 1,
+                '&' if !matches!(previous, Some('>' | '<'))
+                    && !prompt[index + 1..].starts_with('>') =>
+                {
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        previous = Some(c);
+    }
+    &prompt[start..]
+}
+
 /// Backtrack from the end of the prompt and try to match each word to a known command
 /// if a match is found, return the corresponding channel
 /// if no match is found, throw an error
@@ -237,15 +599,18 @@ pub fn list_channels() {
 ///
 /// assert_eq!(channel, ParsedCliChannel::Builtin(CliTvChannel::Files));
 /// ```
+///
+/// Prompts containing multiple commands (e.g. "ls -l && cat <CTRL+T>") are
+/// matched on their last command only.
+///
 /// NOTE: this is a very naive implementation and needs to be improved
-/// - it should be able to handle prompts containing multiple commands
-///   e.g. "ls -l && cat <CTRL+T>"
 /// - it should be able to handle commands within delimiters (quotes, brackets, etc.)
 pub fn guess_channel_from_prompt(
     prompt: &str,
     command_mapping: &FxHashMap<String, String>,
 ) -> Result<ParsedCliChannel> {
     debug!("Guessing channel from prompt: {}", prompt);
+    let prompt = last_command(prompt);
     // git checkout -qf
     // --- -------- --- <---------
     if prompt.trim().is_empty() {
@@ -254,7 +619,11 @@ pub fn guess_channel_from_prompt(
             None => Err(eyre!("No channel found for prompt: {}", prompt)),
         };
     }
-    let rev_prompt_words = prompt.split_whitespace().rev();
+    // an unterminated quote (while still typing) is split on whitespace
+    let prompt_words = shlex::split(prompt).unwrap_or_else(|| {
+        prompt.split_whitespace().map(String::from).collect()
+    });
+    let rev_prompt_words = prompt_words.iter().map(String::as_str).rev();
     let mut stack = Vec::new();
     // for each patern
     for (pattern, channel) in command_mapping {
@@ -342,16 +711,47 @@ mod tests {
     fn test_from_cli() {
         let cli = Cli {
             channel: "files".to_string(),
+            channel_file: None,
+            env_file: None,
+            bind_file: None,
             preview: Some("bat -n --color=always {}".to_string()),
+            preview_offset: None,
+            preview_border_label: None,
+            preview_concurrency: None,
             no_preview: false,
+            preview_hidden: false,
+            wrap_results: false,
+            mouse: false,
+            no_mouse: false,
             delimiter: ":".to_string(),
             tick_rate: Some(50.0),
             frame_rate: Some(60.0),
+            idle_timeout: None,
+            matcher_threads: None,
+            debug: false,
             passthrough_keybindings: Some("q,ctrl-w,ctrl-t".to_string()),
             input: None,
             command: None,
             working_directory: Some("/home/user".to_string()),
             autocomplete_prompt: None,
+            scheme: None,
+            export_file: None,
+            goto: None,
+            goto_confirm: false,
+            sync: false,
+            shell_quote: false,
+            print0: false,
+            with_index: false,
+            output_file: None,
+            tee: false,
+            selection_pipe: None,
+            no_ansi_strip: false,
+            filter: None,
+            filter_pattern: None,
+            server: false,
+            server_limit: 10,
+            server_delimiter: String::new(),
+            source_args: vec![],
         };
 
         let post_processed_cli: PostProcessedCli = cli.into();
@@ -364,7 +764,11 @@ mod tests {
             post_processed_cli.preview_command,
             Some(PreviewCommand {
                 command: "bat -n --color=always {}".to_string(),
-                delimiter: ":".to_string()
+                delimiter: ":".to_string(),
+                dir_command: None,
+                offset: None,
+                fallback_command: None,
+                multi_command: None,
             })
         );
         assert_eq!(post_processed_cli.tick_rate, Some(50.0));
@@ -384,16 +788,47 @@ mod tests {
     fn test_from_cli_no_args() {
         let cli = Cli {
             channel: ".".to_string(),
+            channel_file: None,
+            env_file: None,
+            bind_file: None,
             preview: None,
+            preview_offset: None,
+            preview_border_label: None,
+            preview_concurrency: None,
             no_preview: false,
+            preview_hidden: false,
+            wrap_results: false,
+            mouse: false,
+            no_mouse: false,
             delimiter: ":".to_string(),
             tick_rate: Some(50.0),
             frame_rate: Some(60.0),
+            idle_timeout: None,
+            matcher_threads: None,
+            debug: false,
             passthrough_keybindings: None,
             input: None,
             command: None,
             working_directory: None,
             autocomplete_prompt: None,
+            scheme: None,
+            export_file: None,
+            goto: None,
+            goto_confirm: false,
+            sync: false,
+            shell_quote: false,
+            print0: false,
+            with_index: false,
+            output_file: None,
+            tee: false,
+            selection_pipe: None,
+            no_ansi_strip: false,
+            filter: None,
+            filter_pattern: None,
+            server: false,
+            server_limit: 10,
+            server_delimiter: String::new(),
+            source_args: vec![],
         };
 
         let post_processed_cli: PostProcessedCli = cli.into();
@@ -408,4 +843,90 @@ mod tests {
         );
         assert_eq!(post_processed_cli.command, None);
     }
+
+    #[test]
+    fn test_guess_channel_from_compound_prompt() {
+        let command_mapping = FxHashMap::from_iter([
+            ("ls".to_string(), "dirs".to_string()),
+            ("cat".to_string(), "files".to_string()),
+            ("export".to_string(), "env".to_string()),
+        ]);
+        let guess =
+            |prompt| guess_channel_from_prompt(prompt, &command_mapping).ok();
+
+        assert_eq!(
+            guess("ls -l && cat "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Files))
+        );
+        assert_eq!(
+            guess("cat foo | grep bar; export "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Env))
+        );
+        assert_eq!(
+            guess("cat foo || ls "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Dirs))
+        );
+        // the first command doesn't match anymore
+        assert_eq!(guess("ls -l | sort "), None);
+        // separators within quotes or redirections don't count
+        assert_eq!(
+            guess("cat 'a & b' "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Files))
+        );
+        assert_eq!(
+            guess("cat \"a | b\" foo\\;bar "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Files))
+        );
+        assert_eq!(
+            guess("ls 2>&1 "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Dirs))
+        );
+        assert_eq!(
+            guess("ls &> /dev/null; cat "),
+            Some(ParsedCliChannel::Builtin(CliTvChannel::Files))
+        );
+    }
+
+    #[test]
+    fn test_resolve_channel_name() {
+        let names = ["files", "Git-Repos", "env", "Env"];
+        let resolve = |name| resolve_channel_name(name, names.iter().copied());
+
+        assert_eq!(resolve("files"), Some("files"));
+        assert_eq!(resolve("Files"), Some("files"));
+        assert_eq!(resolve("git-repos"), Some("Git-Repos"));
+        // exact matches win over case-insensitive collisions
+        assert_eq!(resolve("env"), Some("env"));
+        assert_eq!(resolve("Env"), Some("Env"));
+        assert_eq!(resolve("ENV"), Some("Env"));
+        assert_eq!(resolve("dirs"), None);
+    }
+
+    #[test]
+    fn test_validate_templates() {
+        let validate = |args: &[&str]| {
+            let cli = Cli::try_parse_from(
+                std::iter::once("tv").chain(args.iter().copied()),
+            )
+            .unwrap();
+            validate_templates(&cli)
+        };
+
+        assert!(validate(&[]).is_ok());
+        assert!(validate(&[
+            "--preview",
+            "bat {0}",
+            "--preview-offset",
+            "{1}"
+        ])
+        .is_ok());
+        assert!(validate(&["--preview", "bat {0"]).is_err());
+        assert!(validate(&[
+            "--preview",
+            "bat {0}",
+            "--preview-offset",
+            "{1}+1"
+        ])
+        .is_err());
+    }
 }
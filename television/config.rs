@@ -1,20 +1,34 @@
 #![allow(clippy::module_name_repetitions)]
 use std::{env, path::PathBuf};
 
-use color_eyre::Result;
+use color_eyre::{eyre::WrapErr, Result};
 use directories::ProjectDirs;
-pub use keybindings::{parse_key, Binding, KeyBindings};
+pub use keybindings::{
+    load_keybindings_file, parse_action, parse_key, Binding, EventBindings,
+    KeyBindings,
+};
 use lazy_static::lazy_static;
+use matcher::MatcherConfig;
+use output::OutputConfig;
 use previewers::PreviewersConfig;
+use remote_control::RemoteControlConfig;
 use serde::Deserialize;
 use shell_integration::ShellIntegrationConfig;
+pub use styles::parse_style;
 use styles::Styles;
 pub use themes::Theme;
 use tracing::{debug, warn};
 use ui::UiConfig;
+pub use ui::{
+    ColumnAlignment, ColumnConfig, HighlightRuleConfig, PreviewRender,
+    SpinnerConfig,
+};
 
 mod keybindings;
+mod matcher;
+mod output;
 mod previewers;
+mod remote_control;
 mod shell_integration;
 mod styles;
 mod themes;
@@ -33,6 +47,46 @@ pub struct AppConfig {
     pub frame_rate: f64,
     #[serde(default = "default_tick_rate")]
     pub tick_rate: f64,
+    /// The file to which matched entries are written by the `export_results`
+    /// action.
+    #[serde(default)]
+    pub export_file: Option<PathBuf>,
+    /// Exit automatically after this many seconds without user input (0
+    /// disables the timeout).
+    #[serde(default)]
+    pub idle_timeout: u64,
+    /// Whether to remember the last selected entry of each channel and move
+    /// the cursor back to it the next time the channel is opened.
+    #[serde(default)]
+    pub remember_selection: bool,
+    /// Git repositories of cable channels fetched by `tv update-channels`.
+    #[serde(default)]
+    pub channel_repos: Vec<String>,
+    /// What confirming the selection does when no entry matches the query.
+    #[serde(default)]
+    pub on_empty_confirm: OnEmptyConfirm,
+    /// Whether debugging actions are enabled (only set from the CLI).
+    #[serde(skip)]
+    pub debug: bool,
+    /// The named pipe to which confirmed selections are sent instead of
+    /// exiting (only set from the CLI).
+    #[serde(skip)]
+    pub selection_pipe: Option<PathBuf>,
+}
+
+/// What confirming the selection does when no entry matches the query.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+pub enum OnEmptyConfirm {
+    /// Exit without any output.
+    #[serde(rename = "abort")]
+    Abort,
+    /// Exit and output the current query.
+    #[default]
+    #[serde(rename = "use_query")]
+    UseQuery,
+    /// Do nothing and keep television open.
+    #[serde(rename = "noop")]
+    Noop,
 }
 
 #[allow(dead_code)]
@@ -44,6 +98,8 @@ pub struct Config {
     #[serde(default)]
     pub keybindings: KeyBindings,
     #[serde(default)]
+    pub events: EventBindings,
+    #[serde(default)]
     pub styles: Styles,
     #[serde(default)]
     pub ui: UiConfig,
@@ -51,6 +107,12 @@ pub struct Config {
     pub previewers: PreviewersConfig,
     #[serde(default)]
     pub shell_integration: ShellIntegrationConfig,
+    #[serde(default)]
+    pub remote_control: RemoteControlConfig,
+    #[serde(default)]
+    pub matcher: MatcherConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
 }
 
 lazy_static! {
@@ -99,7 +161,13 @@ impl Config {
             .set_default(
                 "shell_integration",
                 default_config.shell_integration.clone(),
-            )?;
+            )?
+            .set_default(
+                "remote_control",
+                default_config.remote_control.clone(),
+            )?
+            .set_default("matcher", default_config.matcher.clone())?
+            .set_default("output", default_config.output.clone())?;
 
         // Load the user's config file
         let source = config::File::from(config_dir.join(CONFIG_FILE_NAME))
@@ -109,13 +177,13 @@ impl Config {
 
         if config_dir.join(CONFIG_FILE_NAME).is_file() {
             debug!("Found config file at {:?}", config_dir);
-            let mut cfg: Self = builder.build()?.try_deserialize().unwrap();
-            //.with_context(|| {
-            //    format!(
-            //        "Error parsing config file at {:?}",
-            //        config_dir.join(CONFIG_FILE_NAME)
-            //    )
-            //})?;
+            let mut cfg: Self =
+                builder.build()?.try_deserialize().wrap_err_with(|| {
+                    format!(
+                        "Error parsing config file at {:?}",
+                        config_dir.join(CONFIG_FILE_NAME)
+                    )
+                })?;
 
             for (mode, default_bindings) in default_config.keybindings.iter() {
                 let user_bindings = cfg.keybindings.entry(*mode).or_default();
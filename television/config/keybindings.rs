@@ -1,16 +1,21 @@
 use crate::action::Action;
-use crate::event::{convert_raw_event_to_key, Key};
+use crate::event::{convert_raw_event_to_key, Event, EventType, Key};
 use crate::screen::mode::Mode;
+use color_eyre::eyre::Context;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rustc_hash::FxHashMap;
+use serde::de::{value::StrDeserializer, Error};
 use serde::{Deserialize, Deserializer};
 use std::fmt::Display;
 use std::ops::{Deref, DerefMut};
+use std::path::Path;
 
 #[derive(Clone, Debug, Deserialize)]
 pub enum Binding {
     SingleKey(Key),
     MultipleKeys(Vec<Key>),
+    /// Keys pressed one after the other, e.g. `"esc esc"`.
+    Sequence(Vec<Key>),
 }
 
 impl Display for Binding {
@@ -24,6 +29,52 @@ impl Display for Binding {
                     .collect();
                 write!(f, "{}", keys_str.join(", "))
             }
+            Binding::Sequence(keys) => {
+                let keys_str: Vec<String> = keys
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect();
+                write!(f, "{}", keys_str.join(" "))
+            }
+        }
+    }
+}
+
+impl Binding {
+    /// The keys triggering this binding on their own (key sequences
+    /// aren't included).
+    pub fn keys(&self) -> &[Key] {
+        match self {
+            Binding::SingleKey(key) => std::slice::from_ref(key),
+            Binding::MultipleKeys(keys) => keys,
+            Binding::Sequence(_) => &[],
+        }
+    }
+
+    /// The sequence of keys triggering this binding, if it is one.
+    pub fn sequence(&self) -> Option<&[Key]> {
+        match self {
+            Binding::Sequence(keys) => Some(keys),
+            _ => None,
+        }
+    }
+
+    /// This binding without the keys (or key sequence) of `other`, or
+    /// `None` if nothing is left of it.
+    fn without(&self, other: &Binding) -> Option<Binding> {
+        match self {
+            Binding::SingleKey(key) => (!other.keys().contains(key))
+                .then_some(Binding::SingleKey(*key)),
+            Binding::MultipleKeys(keys) => {
+                let keys = keys
+                    .iter()
+                    .filter(|k| !other.keys().contains(k))
+                    .copied()
+                    .collect::<Vec<_>>();
+                (!keys.is_empty()).then_some(Binding::MultipleKeys(keys))
+            }
+            Binding::Sequence(keys) => (other.sequence() != Some(keys))
+                .then(|| Binding::Sequence(keys.clone())),
         }
     }
 }
@@ -44,6 +95,42 @@ impl DerefMut for KeyBindings {
     }
 }
 
+impl KeyBindings {
+    /// Merge `other` over these bindings.
+    ///
+    /// Bindings of `other` replace those of the same action, and any other
+    /// action bound to one of their keys loses that key (keeping its other
+    /// keys) so that keys stay unambiguous.
+    pub fn merge(&mut self, other: KeyBindings) {
+        for (mode, bindings) in other.0 {
+            let current = self.entry(mode).or_default();
+            for (action, binding) in bindings {
+                current.retain(|_, existing| {
+                    match existing.without(&binding) {
+                        Some(rest) => {
+                            *existing = rest;
+                            true
+                        }
+                        None => false,
+                    }
+                });
+                current.insert(action, binding);
+            }
+        }
+    }
+}
+
+/// Load keybindings from a standalone TOML file, using the same format as the
+/// `[keybindings]` section of the configuration file.
+pub fn load_keybindings_file(path: &Path) -> color_eyre::Result<KeyBindings> {
+    let content = std::fs::read_to_string(path).wrap_err_with(|| {
+        format!("Failed to read keybindings file {}", path.display())
+    })?;
+    toml::from_str(&content).wrap_err_with(|| {
+        format!("Failed to parse keybindings file {}", path.display())
+    })
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged)]
 pub enum SerializedBinding {
@@ -51,49 +138,162 @@ pub enum SerializedBinding {
     MultipleKeys(Vec<String>),
 }
 
+/// The section of bindings that apply to every mode, unless a mode binds
+/// the same action or key differently.
+const GLOBAL_SECTION: &str = "Global";
+
+fn convert_bindings<E: Error>(
+    bindings: FxHashMap<String, SerializedBinding>,
+) -> Result<config::Map<Action, Binding>, E> {
+    bindings
+        .into_iter()
+        .map(|(cmd, binding)| {
+            Ok((
+                parse_action(&cmd).map_err(E::custom)?,
+                match binding {
+                    SerializedBinding::SingleKey(key_str)
+                        if key_str.split_whitespace().count() > 1 =>
+                    {
+                        Binding::Sequence(
+                            parse_sequence(&key_str).map_err(E::custom)?,
+                        )
+                    }
+                    SerializedBinding::SingleKey(key_str) => {
+                        Binding::SingleKey(
+                            parse_key(&key_str).map_err(E::custom)?,
+                        )
+                    }
+                    SerializedBinding::MultipleKeys(keys_str) => {
+                        Binding::MultipleKeys(
+                            keys_str
+                                .iter()
+                                .map(|key_str| {
+                                    if key_str.split_whitespace().count() > 1 {
+                                        return Err(format!("key sequences can't be combined with other keys: {key_str:?}"));
+                                    }
+                                    parse_key(key_str)
+                                })
+                                .collect::<Result<_, _>>()
+                                .map_err(E::custom)?,
+                        )
+                    }
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Parse space-separated keys pressed one after the other, e.g. `"esc esc"`.
+///
+/// Plain characters aren't allowed since they are typed into the input.
+fn parse_sequence(raw: &str) -> Result<Vec<Key>, String> {
+    let keys = raw
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.iter().any(|key| matches!(key, Key::Char(_))) {
+        return Err(format!(
+            "key sequences can't contain plain characters: {raw:?}"
+        ));
+    }
+    Ok(keys)
+}
+
 impl<'de> Deserialize<'de> for KeyBindings {
     fn deserialize<D>(deserializer: D) -> color_eyre::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let parsed_map = FxHashMap::<
-            Mode,
-            FxHashMap<Action, SerializedBinding>,
+            String,
+            FxHashMap<String, SerializedBinding>,
         >::deserialize(deserializer)?;
 
-        let keybindings = parsed_map
-            .into_iter()
-            .map(|(mode, inner_map)| {
-                let converted_inner_map = inner_map
-                    .into_iter()
-                    .map(|(cmd, binding)| {
-                        (
-                            cmd,
-                            match binding {
-                                SerializedBinding::SingleKey(key_str) => {
-                                    Binding::SingleKey(
-                                        parse_key(&key_str).unwrap(),
-                                    )
-                                }
-                                SerializedBinding::MultipleKeys(keys_str) => {
-                                    Binding::MultipleKeys(
-                                        keys_str
-                                            .iter()
-                                            .map(|key_str| {
-                                                parse_key(key_str).unwrap()
-                                            })
-                                            .collect(),
-                                    )
-                                }
-                            },
-                        )
-                    })
-                    .collect();
-                (mode, converted_inner_map)
-            })
-            .collect();
+        // section names are matched case-insensitively since the config
+        // crate lowercases keys
+        let mut global = None;
+        let mut keybindings = KeyBindings::default();
+        for (section, inner_map) in parsed_map {
+            if section.eq_ignore_ascii_case(GLOBAL_SECTION) {
+                global = Some(inner_map);
+                continue;
+            }
+            let mode = Mode::ALL
+                .into_iter()
+                .find(|mode| {
+                    format!("{mode:?}").eq_ignore_ascii_case(&section)
+                })
+                .ok_or_else(|| {
+                    D::Error::unknown_variant(
+                        &section,
+                        &[
+                            "Global",
+                            "Channel",
+                            "RemoteControl",
+                            "SendToChannel",
+                        ],
+                    )
+                })?;
+            keybindings.insert(mode, convert_bindings(inner_map)?);
+        }
 
-        Ok(KeyBindings(keybindings))
+        match global {
+            Some(global) => {
+                let global = convert_bindings::<D::Error>(global)?;
+                let mut scoped = KeyBindings(
+                    Mode::ALL
+                        .into_iter()
+                        .map(|mode| (mode, global.clone()))
+                        .collect(),
+                );
+                scoped.merge(keybindings);
+                Ok(scoped)
+            }
+            None => Ok(keybindings),
+        }
+    }
+}
+
+/// The prefix of actions inserting a literal string into the input, e.g.
+/// `"insert:ext:" = "ctrl-e"`.
+const INSERT_ACTION_PREFIX: &str = "insert:";
+
+/// The prefix of actions confirming the Nth listed entry, e.g.
+/// `"confirm_entry:1" = "alt-1"`.
+const CONFIRM_ENTRY_ACTION_PREFIX: &str = "confirm_entry:";
+
+/// Parse an action name as used in the keybindings configuration.
+pub fn parse_action(raw: &str) -> Result<Action, String> {
+    if let Some(text) = raw.strip_prefix(INSERT_ACTION_PREFIX) {
+        return Ok(Action::InsertString(text.to_string()));
+    }
+    if let Some(n) = raw.strip_prefix(CONFIRM_ENTRY_ACTION_PREFIX) {
+        return match n.parse::<u16>() {
+            Ok(n) if n > 0 => Ok(Action::ConfirmEntry(n)),
+            _ => Err(format!("invalid entry number in {raw:?}")),
+        };
+    }
+    Action::deserialize(StrDeserializer::<serde::de::value::Error>::new(raw))
+        .map_err(|e| e.to_string())
+}
+
+/// Bindings between terminal events (focus changes, resizes) and actions.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EventBindings(pub FxHashMap<EventType, Action>);
+
+impl Deref for EventBindings {
+    type Target = FxHashMap<EventType, Action>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl EventBindings {
+    /// Get the action bound to the given event, if any.
+    pub fn get_action<I>(&self, event: &Event<I>) -> Option<Action> {
+        event
+            .event_type()
+            .and_then(|event_type| self.get(&event_type).cloned())
     }
 }
 
@@ -346,4 +546,248 @@ mod tests {
             KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)
         );
     }
+
+    #[test]
+    fn test_insert_string_binding() {
+        let bindings: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            "insert:ext:" = "ctrl-e"
+            quit = "esc"
+            "#,
+        )
+        .unwrap();
+
+        let channel_bindings = bindings.get(&Mode::Channel).unwrap();
+        assert!(matches!(
+            channel_bindings.get(&Action::InsertString("ext:".to_string())),
+            Some(Binding::SingleKey(Key::Ctrl('e')))
+        ));
+        assert!(channel_bindings.contains_key(&Action::Quit));
+    }
+
+    #[test]
+    fn test_confirm_entry_binding() {
+        let bindings: KeyBindings = toml::from_str(
+            r#"
+            [RemoteControl]
+            "confirm_entry:2" = "alt-2"
+            "#,
+        )
+        .unwrap();
+
+        let rc_bindings = bindings.get(&Mode::RemoteControl).unwrap();
+        assert!(matches!(
+            rc_bindings.get(&Action::ConfirmEntry(2)),
+            Some(Binding::SingleKey(Key::Alt('2')))
+        ));
+        assert!(toml::from_str::<KeyBindings>(
+            r#"
+            [RemoteControl]
+            "confirm_entry:0" = "alt-0"
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sequence_bindings() {
+        let bindings: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            quit = "esc esc"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            bindings.get(&Mode::Channel).unwrap().get(&Action::Quit),
+            Some(Binding::Sequence(keys)) if keys == &[Key::Esc, Key::Esc]
+        ));
+
+        for invalid in [
+            // plain characters are typed into the input
+            r#"quit = "g g""#,
+            r#"quit = "ctrl-x q""#,
+            // sequences can't be part of an array
+            r#"quit = ["ctrl-c", "esc esc"]"#,
+            r#"quit = ["ctrl-c", "not-a-key"]"#,
+            r#"quit = "not-a-key""#,
+        ] {
+            assert!(
+                toml::from_str::<KeyBindings>(&format!(
+                    "[Channel]\n{invalid}"
+                ))
+                .is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_bindings() {
+        let bindings: EventBindings = toml::from_str(
+            r#"
+            resize = "toggle_preview"
+            focus_gained = "toggle_help"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bindings.get_action::<Key>(&Event::Resize(80, 24)),
+            Some(Action::TogglePreview)
+        );
+        assert_eq!(
+            bindings.get_action::<Key>(&Event::FocusGained),
+            Some(Action::ToggleHelp)
+        );
+        assert_eq!(bindings.get_action::<Key>(&Event::FocusLost), None);
+        assert_eq!(bindings.get_action(&Event::Input(Key::Enter)), None);
+    }
+
+    #[test]
+    fn test_merge_keybindings() {
+        let mut bindings: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            quit = "esc"
+            toggle_preview = "ctrl-o"
+            select_next_entry = ["down", "ctrl-n"]
+            "#,
+        )
+        .unwrap();
+        let overrides: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            quit = "ctrl-c"
+            toggle_help = "ctrl-o"
+            "#,
+        )
+        .unwrap();
+
+        bindings.merge(overrides);
+
+        let channel_bindings = bindings.get(&Mode::Channel).unwrap();
+        assert_eq!(
+            channel_bindings.get(&Action::Quit).unwrap().keys(),
+            [Key::Ctrl('c')]
+        );
+        assert_eq!(
+            channel_bindings.get(&Action::ToggleHelp).unwrap().keys(),
+            [Key::Ctrl('o')]
+        );
+        // the key was taken over by `toggle_help`
+        assert!(!channel_bindings.contains_key(&Action::TogglePreview));
+        assert!(channel_bindings.contains_key(&Action::SelectNextEntry));
+    }
+
+    #[test]
+    fn test_merge_keybindings_partial_overlap() {
+        let mut bindings: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            quit = ["esc", "ctrl-c"]
+            "#,
+        )
+        .unwrap();
+        let overrides: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            toggle_help = "esc"
+            "#,
+        )
+        .unwrap();
+
+        bindings.merge(overrides);
+
+        // `quit` only loses the key taken over by `toggle_help`
+        let channel_bindings = bindings.get(&Mode::Channel).unwrap();
+        assert_eq!(
+            channel_bindings.get(&Action::Quit).unwrap().keys(),
+            [Key::Ctrl('c')]
+        );
+        assert_eq!(
+            channel_bindings.get(&Action::ToggleHelp).unwrap().keys(),
+            [Key::Esc]
+        );
+    }
+
+    #[test]
+    fn test_global_keybindings() {
+        let bindings: KeyBindings = toml::from_str(
+            r#"
+            [Global]
+            quit = "esc"
+            toggle_help = "ctrl-g"
+
+            [RemoteControl]
+            quit = "ctrl-c"
+            toggle_preview = "ctrl-g"
+            "#,
+        )
+        .unwrap();
+
+        let channel_bindings = bindings.get(&Mode::Channel).unwrap();
+        assert_eq!(
+            channel_bindings.get(&Action::Quit).unwrap().keys(),
+            [Key::Esc]
+        );
+        assert_eq!(
+            channel_bindings.get(&Action::ToggleHelp).unwrap().keys(),
+            [Key::Ctrl('g')]
+        );
+        // mode bindings take precedence over global ones
+        let rc_bindings = bindings.get(&Mode::RemoteControl).unwrap();
+        assert_eq!(
+            rc_bindings.get(&Action::Quit).unwrap().keys(),
+            [Key::Ctrl('c')]
+        );
+        assert!(!rc_bindings.contains_key(&Action::ToggleHelp));
+        assert!(bindings.contains_key(&Mode::SendToChannel));
+    }
+
+    #[test]
+    fn test_keybindings_loaded_through_config_crate() {
+        // the config crate lowercases table keys, which section names must
+        // survive
+        #[derive(Deserialize)]
+        struct UserConfig {
+            keybindings: KeyBindings,
+        }
+        let user_config: UserConfig = config::Config::builder()
+            .add_source(config::File::from_str(
+                r#"
+                [keybindings.Global]
+                toggle_help = "ctrl-g"
+
+                [keybindings.RemoteControl]
+                quit = "ctrl-c"
+                "#,
+                config::FileFormat::Toml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        let bindings = user_config.keybindings;
+        assert_eq!(
+            bindings
+                .get(&Mode::Channel)
+                .unwrap()
+                .get(&Action::ToggleHelp)
+                .unwrap()
+                .keys(),
+            [Key::Ctrl('g')]
+        );
+        assert_eq!(
+            bindings
+                .get(&Mode::RemoteControl)
+                .unwrap()
+                .get(&Action::Quit)
+                .unwrap()
+                .keys(),
+            [Key::Ctrl('c')]
+        );
+    }
 }
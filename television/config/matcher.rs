@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use config::ValueKind;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct MatcherConfig {
+    /// The maximum number of threads used by the fuzzy matcher (0 means the
+    /// number of available cores).
+    #[serde(default)]
+    pub threads: usize,
+    /// How long (in milliseconds) to wait for the user to pause typing
+    /// before matching the query (0 matches on every keystroke).
+    #[serde(default)]
+    pub input_debounce_ms: u64,
+    /// The score added to entries whose basename starts with the query (0
+    /// keeps the fuzzy matcher's ranking untouched).
+    #[serde(default)]
+    pub prefix_bonus: u32,
+}
+
+impl From<MatcherConfig> for ValueKind {
+    fn from(val: MatcherConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("threads"),
+            ValueKind::U64(val.threads as u64).into(),
+        );
+        m.insert(
+            String::from("input_debounce_ms"),
+            ValueKind::U64(val.input_debounce_ms).into(),
+        );
+        m.insert(
+            String::from("prefix_bonus"),
+            ValueKind::U64(val.prefix_bonus.into()).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use config::ValueKind;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct OutputConfig {
+    /// Whether to shell-quote every selected entry when printing it.
+    #[serde(default)]
+    pub shell_escaping: bool,
+}
+
+impl From<OutputConfig> for ValueKind {
+    fn from(val: OutputConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("shell_escaping"),
+            ValueKind::Boolean(val.shell_escaping).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
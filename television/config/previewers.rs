@@ -11,12 +11,19 @@ pub struct PreviewersConfig {
     pub file: FilePreviewerConfig,
     #[serde(default)]
     pub env_var: EnvVarPreviewerConfig,
+    #[serde(default)]
+    pub command: CommandPreviewerConfig,
 }
 
 impl From<PreviewersConfig> for PreviewerConfig {
     fn from(val: PreviewersConfig) -> Self {
         PreviewerConfig::default()
             .file(previewers::files::FilePreviewerConfig::new(val.file.theme))
+            .command(
+                previewers::command::CommandPreviewerConfig::default()
+                    .with_max_concurrent_tasks(val.command.max_concurrent)
+                    .with_max_bytes(val.command.max_bytes),
+            )
     }
 }
 
@@ -26,6 +33,7 @@ impl From<PreviewersConfig> for ValueKind {
         m.insert(String::from("basic"), val.basic.into());
         m.insert(String::from("file"), val.file.into());
         m.insert(String::from("env_var"), val.env_var.into());
+        m.insert(String::from("command"), val.command.into());
         ValueKind::Table(m)
     }
 }
@@ -70,3 +78,46 @@ impl From<EnvVarPreviewerConfig> for ValueKind {
         ValueKind::Table(HashMap::new())
     }
 }
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommandPreviewerConfig {
+    /// The maximum number of preview commands running at the same time.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: u8,
+    /// The maximum number of bytes of a preview command's output that are
+    /// read, the rest being cut off (0 means no limit).
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+}
+
+fn default_max_concurrent() -> u8 {
+    previewers::command::DEFAULT_MAX_CONCURRENT_PREVIEW_TASKS
+}
+
+fn default_max_bytes() -> usize {
+    previewers::command::DEFAULT_MAX_PREVIEW_BYTES
+}
+
+impl Default for CommandPreviewerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_max_concurrent(),
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+impl From<CommandPreviewerConfig> for ValueKind {
+    fn from(val: CommandPreviewerConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("max_concurrent"),
+            ValueKind::U64(val.max_concurrent.into()).into(),
+        );
+        m.insert(
+            String::from("max_bytes"),
+            ValueKind::U64(val.max_bytes as u64).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use config::ValueKind;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct RemoteControlConfig {
+    /// Names of channels that should always be listed first in the remote
+    /// control, in the given order.
+    #[serde(default)]
+    pub pinned: Vec<String>,
+}
+
+impl From<RemoteControlConfig> for ValueKind {
+    fn from(val: RemoteControlConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("pinned"),
+            ValueKind::Array(
+                val.pinned
+                    .into_iter()
+                    .map(|name| ValueKind::String(name).into())
+                    .collect(),
+            )
+            .into(),
+        );
+        ValueKind::Table(m)
+    }
+}
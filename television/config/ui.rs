@@ -1,14 +1,430 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 
 use config::ValueKind;
 use serde::Deserialize;
 
-use crate::screen::layout::{InputPosition, PreviewTitlePosition};
+use crate::screen::layout::{
+    InputPosition, PreviewPosition, PreviewTitlePosition, UiMargin,
+};
 
 use super::themes::DEFAULT_THEME;
 
 const DEFAULT_UI_SCALE: u16 = 100;
 
+/// How a column's content is aligned within its width.
+#[derive(Clone, Copy, Debug, Deserialize, Default, PartialEq)]
+pub enum ColumnAlignment {
+    #[serde(rename = "left")]
+    #[default]
+    Left,
+    #[serde(rename = "right")]
+    Right,
+    #[serde(rename = "center")]
+    Center,
+}
+
+impl Display for ColumnAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnAlignment::Left => write!(f, "left"),
+            ColumnAlignment::Right => write!(f, "right"),
+            ColumnAlignment::Center => write!(f, "center"),
+        }
+    }
+}
+
+/// How the preview panel renders the content of previewed entries.
+#[derive(Clone, Copy, Debug, Deserialize, Default, PartialEq)]
+pub enum PreviewRender {
+    /// Display previews as produced by the previewers.
+    #[serde(rename = "raw")]
+    #[default]
+    Raw,
+    /// Render markdown files with basic formatting instead of running the
+    /// channel's preview command on them.
+    #[serde(rename = "markdown")]
+    Markdown,
+}
+
+impl Display for PreviewRender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewRender::Raw => write!(f, "raw"),
+            PreviewRender::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+/// The layout of a column of the results panel.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ColumnConfig {
+    /// The width of the column (longer fields are truncated).
+    pub width: u16,
+    #[serde(default)]
+    pub align: ColumnAlignment,
+}
+
+impl From<ColumnConfig> for ValueKind {
+    fn from(val: ColumnConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("width"),
+            ValueKind::U64(val.width.into()).into(),
+        );
+        m.insert(
+            String::from("align"),
+            ValueKind::String(val.align.to_string()).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+/// A style applied to the rows of the entries matching a pattern.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HighlightRuleConfig {
+    /// The text entries are matched against (a substring unless `regex` is
+    /// set).
+    pub pattern: String,
+    /// The style of matching rows, e.g. `"bold red"` or `"white on red"`.
+    pub style: String,
+    /// Whether `pattern` is a regular expression.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl From<HighlightRuleConfig> for ValueKind {
+    fn from(val: HighlightRuleConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("pattern"),
+            ValueKind::String(val.pattern).into(),
+        );
+        m.insert(String::from("style"), ValueKind::String(val.style).into());
+        m.insert(String::from("regex"), ValueKind::Boolean(val.regex).into());
+        ValueKind::Table(m)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct ResultsPanelConfig {
+    /// The minimum number of rows to keep between the selected entry and the
+    /// edges of the results panel while scrolling.
+    #[serde(default)]
+    pub scroll_margin: u16,
+    /// A template for a dimmed second line displayed below each entry.
+    ///
+    /// `{}` is replaced with the entry and `{N}` with its N-th field.
+    pub secondary_line_template: Option<String>,
+    /// The delimiter used to split entries into fields for the secondary
+    /// line (defaults to a space).
+    pub secondary_line_delimiter: Option<String>,
+    /// Whether to wrap entries that don't fit in the panel onto several
+    /// lines instead of truncating them.
+    #[serde(default)]
+    pub wrap: bool,
+    /// Display entries as aligned columns: each field of an entry is laid
+    /// out according to the corresponding column.
+    #[serde(default)]
+    pub columns: Vec<ColumnConfig>,
+    /// The delimiter used to split entries into columns (defaults to runs
+    /// of whitespace).
+    pub column_delimiter: Option<String>,
+    /// Styles applied to the rows of matching entries (the first matching
+    /// rule wins).
+    #[serde(default)]
+    pub highlight_rules: Vec<HighlightRuleConfig>,
+}
+
+impl From<ResultsPanelConfig> for ValueKind {
+    fn from(val: ResultsPanelConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("scroll_margin"),
+            ValueKind::U64(val.scroll_margin.into()).into(),
+        );
+        m.insert(
+            String::from("secondary_line_template"),
+            match val.secondary_line_template {
+                Some(template) => ValueKind::String(template),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(
+            String::from("secondary_line_delimiter"),
+            match val.secondary_line_delimiter {
+                Some(delimiter) => ValueKind::String(delimiter),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(String::from("wrap"), ValueKind::Boolean(val.wrap).into());
+        m.insert(
+            String::from("columns"),
+            ValueKind::Array(
+                val.columns
+                    .into_iter()
+                    .map(|c| ValueKind::from(c).into())
+                    .collect(),
+            )
+            .into(),
+        );
+        m.insert(
+            String::from("column_delimiter"),
+            match val.column_delimiter {
+                Some(delimiter) => ValueKind::String(delimiter),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(
+            String::from("highlight_rules"),
+            ValueKind::Array(
+                val.highlight_rules
+                    .into_iter()
+                    .map(|rule| ValueKind::from(rule).into())
+                    .collect(),
+            )
+            .into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InputBarConfig {
+    /// A template for the result count displayed in the input bar.
+    ///
+    /// Supported tokens are `{position}`, `{matched}`, `{total}` and
+    /// `{selected}`.
+    pub count_template: Option<String>,
+    /// The number of rows of the input bar in multi-line query mode.
+    #[serde(default = "default_multiline_rows")]
+    pub multiline_rows: u16,
+    /// Whether the lines of a multi-line query are matched as-is rather
+    /// than joined by spaces.
+    #[serde(default)]
+    pub keep_newlines: bool,
+}
+
+fn default_multiline_rows() -> u16 {
+    3
+}
+
+impl Default for InputBarConfig {
+    fn default() -> Self {
+        Self {
+            count_template: None,
+            multiline_rows: default_multiline_rows(),
+            keep_newlines: false,
+        }
+    }
+}
+
+impl From<InputBarConfig> for ValueKind {
+    fn from(val: InputBarConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("count_template"),
+            match val.count_template {
+                Some(template) => ValueKind::String(template),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(
+            String::from("multiline_rows"),
+            ValueKind::U64(val.multiline_rows.into()).into(),
+        );
+        m.insert(
+            String::from("keep_newlines"),
+            ValueKind::Boolean(val.keep_newlines).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize)]
+pub struct PreviewPanelConfig {
+    /// Where to place the preview panel relative to the results.
+    #[serde(default)]
+    pub position: PreviewPosition,
+    /// A fixed title for the preview panel's border, displayed in place of
+    /// the previewed entry's name.
+    pub label: Option<String>,
+    /// Whether to wrap long lines instead of truncating them.
+    #[serde(default)]
+    pub wrap: bool,
+    /// Whether to replace non-printable characters in the output of preview
+    /// commands (disabling this passes escape sequences such as OSC
+    /// hyperlinks through as-is).
+    #[serde(default = "default_sanitize")]
+    pub sanitize: bool,
+    /// The share (in percent) of the space given to the preview panel, the
+    /// rest going to the results panel.
+    #[serde(default = "default_preview_size")]
+    pub size: u16,
+    /// How previews are rendered.
+    #[serde(default)]
+    pub render: PreviewRender,
+    /// Whether to scroll previews to their last line (like `tail`) instead
+    /// of the entry's target line.
+    #[serde(default)]
+    pub tail: bool,
+    /// Whether to show the size, permissions and modification time of
+    /// previewed files above the output of preview commands.
+    #[serde(default)]
+    pub show_meta: bool,
+}
+
+fn default_sanitize() -> bool {
+    true
+}
+
+fn default_preview_size() -> u16 {
+    50
+}
+
+impl Default for PreviewPanelConfig {
+    fn default() -> Self {
+        Self {
+            position: PreviewPosition::default(),
+            label: None,
+            wrap: false,
+            sanitize: default_sanitize(),
+            size: default_preview_size(),
+            render: PreviewRender::default(),
+            tail: false,
+            show_meta: false,
+        }
+    }
+}
+
+impl From<PreviewPanelConfig> for ValueKind {
+    fn from(val: PreviewPanelConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("position"),
+            ValueKind::String(val.position.to_string()).into(),
+        );
+        m.insert(
+            String::from("label"),
+            match val.label {
+                Some(label) => ValueKind::String(label),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(String::from("wrap"), ValueKind::Boolean(val.wrap).into());
+        m.insert(
+            String::from("sanitize"),
+            ValueKind::Boolean(val.sanitize).into(),
+        );
+        m.insert(String::from("size"), ValueKind::U64(val.size.into()).into());
+        m.insert(
+            String::from("render"),
+            ValueKind::String(val.render.to_string()).into(),
+        );
+        m.insert(String::from("tail"), ValueKind::Boolean(val.tail).into());
+        m.insert(
+            String::from("show_meta"),
+            ValueKind::Boolean(val.show_meta).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ScrollAccelerationConfig {
+    /// The successive steps (in lines or entries) used when a scrolling
+    /// action is repeated quickly.
+    pub steps: Vec<u16>,
+    /// How many quick repeats it takes to move on to the next step.
+    pub repeats_per_step: u16,
+    /// The pause (in milliseconds) after which the step goes back to the
+    /// first one.
+    pub reset_after_ms: u64,
+}
+
+impl Default for ScrollAccelerationConfig {
+    fn default() -> Self {
+        Self {
+            steps: vec![1],
+            repeats_per_step: 10,
+            reset_after_ms: 150,
+        }
+    }
+}
+
+impl From<ScrollAccelerationConfig> for ValueKind {
+    fn from(val: ScrollAccelerationConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("steps"),
+            ValueKind::Array(
+                val.steps
+                    .into_iter()
+                    .map(|step| ValueKind::U64(step.into()).into())
+                    .collect(),
+            )
+            .into(),
+        );
+        m.insert(
+            String::from("repeats_per_step"),
+            ValueKind::U64(val.repeats_per_step.into()).into(),
+        );
+        m.insert(
+            String::from("reset_after_ms"),
+            ValueKind::U64(val.reset_after_ms).into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SpinnerConfig {
+    /// Whether to display a spinner while entries are being loaded.
+    pub enabled: bool,
+    /// The frames the spinner cycles through (the default spinner is used
+    /// if empty).
+    pub frames: Vec<String>,
+}
+
+impl Default for SpinnerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl From<SpinnerConfig> for ValueKind {
+    fn from(val: SpinnerConfig) -> Self {
+        let mut m = HashMap::new();
+        m.insert(
+            String::from("enabled"),
+            ValueKind::Boolean(val.enabled).into(),
+        );
+        m.insert(
+            String::from("frames"),
+            ValueKind::Array(
+                val.frames
+                    .into_iter()
+                    .map(|frame| ValueKind::String(frame).into())
+                    .collect(),
+            )
+            .into(),
+        );
+        ValueKind::Table(m)
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct UiConfig {
     pub use_nerd_font_icons: bool,
@@ -19,6 +435,31 @@ pub struct UiConfig {
     pub input_bar_position: InputPosition,
     pub preview_title_position: Option<PreviewTitlePosition>,
     pub theme: String,
+    /// The theme to use when the terminal has a light background.
+    pub theme_light: Option<String>,
+    /// The theme to use when the terminal has a dark background.
+    pub theme_dark: Option<String>,
+    #[serde(default)]
+    pub results_panel: ResultsPanelConfig,
+    #[serde(default)]
+    pub preview_panel: PreviewPanelConfig,
+    #[serde(default)]
+    pub input_bar: InputBarConfig,
+    #[serde(default)]
+    pub scroll_acceleration: ScrollAccelerationConfig,
+    #[serde(default)]
+    pub spinner: SpinnerConfig,
+    /// Whether to capture mouse events (which disables the terminal's native
+    /// text selection).
+    #[serde(default)]
+    pub mouse: bool,
+    /// A template displayed at the bottom of the preview panel.
+    pub preview_footer: Option<String>,
+    /// A template displayed on a single line at the bottom of the whole UI.
+    pub footer: Option<String>,
+    /// Empty space left around the whole UI.
+    #[serde(default)]
+    pub margin: UiMargin,
 }
 
 impl Default for UiConfig {
@@ -31,6 +472,17 @@ impl Default for UiConfig {
             input_bar_position: InputPosition::Top,
             preview_title_position: None,
             theme: String::from(DEFAULT_THEME),
+            theme_light: None,
+            theme_dark: None,
+            results_panel: ResultsPanelConfig::default(),
+            preview_panel: PreviewPanelConfig::default(),
+            input_bar: InputBarConfig::default(),
+            scroll_acceleration: ScrollAccelerationConfig::default(),
+            spinner: SpinnerConfig::default(),
+            mouse: false,
+            preview_footer: None,
+            footer: None,
+            margin: UiMargin::default(),
         }
     }
 }
@@ -67,6 +519,65 @@ impl From<UiConfig> for ValueKind {
             .into(),
         );
         m.insert(String::from("theme"), ValueKind::String(val.theme).into());
+        m.insert(
+            String::from("theme_light"),
+            match val.theme_light {
+                Some(theme) => ValueKind::String(theme),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(
+            String::from("theme_dark"),
+            match val.theme_dark {
+                Some(theme) => ValueKind::String(theme),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(String::from("results_panel"), val.results_panel.into());
+        m.insert(String::from("preview_panel"), val.preview_panel.into());
+        m.insert(String::from("input_bar"), val.input_bar.into());
+        m.insert(
+            String::from("scroll_acceleration"),
+            val.scroll_acceleration.into(),
+        );
+        m.insert(String::from("spinner"), val.spinner.into());
+        m.insert(String::from("mouse"), ValueKind::Boolean(val.mouse).into());
+        m.insert(
+            String::from("preview_footer"),
+            match val.preview_footer {
+                Some(footer) => ValueKind::String(footer),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(
+            String::from("footer"),
+            match val.footer {
+                Some(footer) => ValueKind::String(footer),
+                None => ValueKind::Nil,
+            }
+            .into(),
+        );
+        m.insert(String::from("margin"), val.margin.into());
+        ValueKind::Table(m)
+    }
+}
+
+impl From<UiMargin> for ValueKind {
+    fn from(val: UiMargin) -> Self {
+        let mut m = HashMap::new();
+        m.insert(String::from("top"), ValueKind::U64(val.top.into()).into());
+        m.insert(
+            String::from("right"),
+            ValueKind::U64(val.right.into()).into(),
+        );
+        m.insert(
+            String::from("bottom"),
+            ValueKind::U64(val.bottom.into()).into(),
+        );
+        m.insert(String::from("left"), ValueKind::U64(val.left.into()).into());
         ValueKind::Table(m)
     }
 }
@@ -20,6 +20,9 @@ use tracing::{debug, warn};
 #[derive(Debug, Clone, Copy)]
 pub enum Event<I> {
     Closed,
+    /// The process was asked to terminate (e.g. the tmux popup or terminal
+    /// window it runs in was closed).
+    Terminate,
     Input(I),
     FocusLost,
     FocusGained,
@@ -27,6 +30,31 @@ pub enum Event<I> {
     Tick,
 }
 
+/// The types of terminal events (other than key presses) that can be bound to
+/// actions in the configuration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    FocusGained,
+    FocusLost,
+    Resize,
+}
+
+impl<I> Event<I> {
+    /// The bindable type of this event, if any.
+    pub fn event_type(&self) -> Option<EventType> {
+        match self {
+            Event::FocusGained => Some(EventType::FocusGained),
+            Event::FocusLost => Some(EventType::FocusLost),
+            Event::Resize(_, _) => Some(EventType::Resize),
+            Event::Closed
+            | Event::Terminate
+            | Event::Input(_)
+            | Event::Tick => None,
+        }
+    }
+}
+
 #[derive(
     Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Hash,
 )]
@@ -47,6 +75,7 @@ pub enum Key {
     CtrlDelete,
     AltSpace,
     AltEnter,
+    ShiftEnter,
     AltBackspace,
     AltDelete,
     AltUp,
@@ -88,6 +117,7 @@ impl Display for Key {
             Key::CtrlDelete => write!(f, "Ctrl-Del"),
             Key::AltSpace => write!(f, "Alt-Space"),
             Key::AltEnter => write!(f, "Alt-Enter"),
+            Key::ShiftEnter => write!(f, "Shift-Enter"),
             Key::AltBackspace => write!(f, "Alt-Backspace"),
             Key::AltDelete => write!(f, "Alt-Delete"),
             Key::AltUp => write!(f, "Alt-Up"),
@@ -112,6 +142,18 @@ impl Display for Key {
     }
 }
 
+impl Key {
+    /// The key this key is handled as when it isn't bound to anything, for
+    /// keys that some terminals don't report distinctly (e.g. Shift-Enter
+    /// as Enter).
+    pub fn fallback(self) -> Option<Key> {
+        match self {
+            Key::ShiftEnter => Some(Key::Enter),
+            _ => None,
+        }
+    }
+}
+
 #[allow(clippy::module_name_repetitions)]
 pub struct EventLoop {
     pub rx: mpsc::UnboundedReceiver<Event<Key>>,
@@ -149,6 +191,27 @@ async fn poll_event(timeout: Duration) -> bool {
     PollFuture { timeout }.await
 }
 
+/// Send a `Terminate` event when the process receives SIGTERM or SIGHUP, so
+/// that it quits through the usual path, restoring the terminal and stopping
+/// running commands, rather than being killed on the spot.
+#[cfg(unix)]
+async fn forward_termination_signals(tx: mpsc::UnboundedSender<Event<Key>>) {
+    use signal::unix::{signal, SignalKind};
+    let (Ok(mut terminate), Ok(mut hangup)) = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::hangup()),
+    ) else {
+        warn!("Unable to listen for termination signals");
+        return;
+    };
+    tokio::select! {
+        _ = terminate.recv() => debug!("Received SIGTERM"),
+        _ = hangup.recv() => debug!("Received SIGHUP"),
+    }
+    tx.send(Event::Terminate)
+        .unwrap_or_else(|_| warn!("Unable to send Terminate event"));
+}
+
 impl EventLoop {
     pub fn new(tick_rate: f64, init: bool) -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
@@ -157,6 +220,8 @@ impl EventLoop {
         let (abort, mut abort_recv) = mpsc::unbounded_channel();
 
         if init {
+            #[cfg(unix)]
+            tokio::spawn(forward_termination_signals(tx.clone()));
             //let mut reader = crossterm::event::EventStream::new();
             tokio::spawn(async move {
                 loop {
@@ -233,6 +298,7 @@ pub fn convert_raw_event_to_key(event: KeyEvent) -> Key {
         Enter => match event.modifiers {
             KeyModifiers::CONTROL => Key::CtrlEnter,
             KeyModifiers::ALT => Key::AltEnter,
+            KeyModifiers::SHIFT => Key::ShiftEnter,
             _ => Key::Enter,
         },
         Up => match event.modifiers {
@@ -458,7 +524,11 @@ mod tests {
             state: KeyEventState::NONE,
         };
 
-        assert_eq!(convert_raw_event_to_key(event), Key::Enter);
+        assert_eq!(convert_raw_event_to_key(event), Key::ShiftEnter);
+        assert_eq!(
+            convert_raw_event_to_key(event).fallback(),
+            Some(Key::Enter)
+        );
 
         let event = KeyEvent {
             code: KeyCode::Up,
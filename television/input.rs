@@ -9,8 +9,12 @@ pub fn convert_action_to_input_request(
         Action::DeletePrevChar => Some(InputRequest::DeletePrevChar),
         Action::DeletePrevWord => Some(InputRequest::DeletePrevWord),
         Action::DeleteNextChar => Some(InputRequest::DeleteNextChar),
+        Action::DeleteNextWord => Some(InputRequest::DeleteNextWord),
+        Action::ClearQuery => Some(InputRequest::DeleteLine),
         Action::GoToPrevChar => Some(InputRequest::GoToPrevChar),
         Action::GoToNextChar => Some(InputRequest::GoToNextChar),
+        Action::GoToPrevWord => Some(InputRequest::GoToPrevWord),
+        Action::GoToNextWord => Some(InputRequest::GoToNextWord),
         Action::GoToInputStart => Some(InputRequest::GoToStart),
         Action::GoToInputEnd => Some(InputRequest::GoToEnd),
         _ => None,
@@ -1,5 +1,7 @@
 use rustc_hash::FxHashMap;
+use std::cell::RefCell;
 use std::ops::Deref;
+use std::time::{Duration, Instant};
 
 use crate::screen::mode::Mode;
 use color_eyre::Result;
@@ -8,21 +10,32 @@ use crate::action::Action;
 use crate::config::{Binding, KeyBindings};
 use crate::event::Key;
 
+/// The maximum delay between two keys of a key sequence.
+const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(500);
+
 #[derive(Default, Debug)]
-pub struct Keymap(pub FxHashMap<Mode, FxHashMap<Key, Action>>);
+pub struct Keymap {
+    pub keys: FxHashMap<Mode, FxHashMap<Key, Action>>,
+    /// Actions triggered by sequences of keys (e.g. pressing `esc` twice).
+    pub sequences: FxHashMap<Mode, Vec<(Vec<Key>, Action)>>,
+    /// The last keys pressed, each with the time it was pressed at.
+    recent_keys: RefCell<Vec<(Key, Instant)>>,
+}
 
 impl Deref for Keymap {
     type Target = FxHashMap<Mode, FxHashMap<Key, Action>>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.keys
     }
 }
 
 impl From<&KeyBindings> for Keymap {
     fn from(keybindings: &KeyBindings) -> Self {
         let mut keymap = FxHashMap::default();
+        let mut sequences = FxHashMap::default();
         for (mode, bindings) in keybindings.iter() {
             let mut mode_keymap = FxHashMap::default();
+            let mut mode_sequences = Vec::new();
             for (action, binding) in bindings {
                 match binding {
                     Binding::SingleKey(key) => {
@@ -33,11 +46,22 @@ impl From<&KeyBindings> for Keymap {
                             mode_keymap.insert(*key, action.clone());
                         }
                     }
+                    Binding::Sequence(keys) => {
+                        mode_sequences.push((keys.clone(), action.clone()));
+                    }
                 }
             }
+            // longer sequences take precedence over the ones they end with
+            mode_sequences
+                .sort_by_key(|(keys, _)| std::cmp::Reverse(keys.len()));
             keymap.insert(*mode, mode_keymap);
+            sequences.insert(*mode, mode_sequences);
+        }
+        Self {
+            keys: keymap,
+            sequences,
+            recent_keys: RefCell::default(),
         }
-        Self(keymap)
     }
 }
 
@@ -47,7 +71,7 @@ impl Keymap {
         mode: Mode,
         mappings: Vec<(Key, Action)>,
     ) -> Result<Self> {
-        let mode_keymap = self.0.get_mut(&mode).ok_or_else(|| {
+        let mode_keymap = self.keys.get_mut(&mode).ok_or_else(|| {
             color_eyre::eyre::eyre!("Mode {:?} not found", mode)
         })?;
         for (key, action) in mappings {
@@ -55,4 +79,100 @@ impl Keymap {
         }
         Ok(self)
     }
+
+    /// Record a key press and return the action of the key sequence it
+    /// completes, if any.
+    ///
+    /// The keys of a sequence must each be pressed within
+    /// `KEY_SEQUENCE_TIMEOUT` of the previous one. Keys completing a
+    /// sequence don't count towards the next one, so that e.g. pressing `esc`
+    /// three times only triggers an `"esc esc"` sequence once.
+    pub fn sequence_action(&self, mode: Mode, key: Key) -> Option<Action> {
+        self.sequence_action_at(mode, key, Instant::now())
+    }
+
+    fn sequence_action_at(
+        &self,
+        mode: Mode,
+        key: Key,
+        now: Instant,
+    ) -> Option<Action> {
+        let sequences = self.sequences.get(&mode)?;
+        let max_len = sequences.iter().map(|(keys, _)| keys.len()).max()?;
+        let mut recent_keys = self.recent_keys.borrow_mut();
+        if recent_keys.last().is_some_and(|(_, at)| {
+            now.duration_since(*at) > KEY_SEQUENCE_TIMEOUT
+        }) {
+            recent_keys.clear();
+        }
+        recent_keys.push((key, now));
+        if recent_keys.len() > max_len {
+            recent_keys.remove(0);
+        }
+        let action = sequences.iter().find_map(|(keys, action)| {
+            recent_keys
+                .iter()
+                .map(|(key, _)| key)
+                .rev()
+                .take(keys.len())
+                .eq(keys.iter().rev())
+                .then(|| action.clone())
+        });
+        if action.is_some() {
+            recent_keys.clear();
+        }
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_action() {
+        let bindings: KeyBindings = toml::from_str(
+            r#"
+            [Channel]
+            quit = "esc esc"
+            toggle_help = "ctrl-g"
+            "#,
+        )
+        .unwrap();
+        let keymap = Keymap::from(&bindings);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(
+            keymap.get(&Mode::Channel).unwrap().get(&Key::Ctrl('g')),
+            Some(&Action::ToggleHelp)
+        );
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Esc, at(0)),
+            None
+        );
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Esc, at(100)),
+            Some(Action::Quit)
+        );
+        // the sequence starts over once completed
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Esc, at(200)),
+            None
+        );
+        // too slow
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Esc, at(1000)),
+            None
+        );
+        // interrupted by another key
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Down, at(1100)),
+            None
+        );
+        assert_eq!(
+            keymap.sequence_action_at(Mode::Channel, Key::Esc, at(1200)),
+            None
+        );
+    }
 }
@@ -2,56 +2,127 @@ use std::env;
 use std::io::{stdout, BufWriter, IsTerminal, Write};
 use std::path::Path;
 use std::process::exit;
+use std::time::Duration;
 
 use clap::Parser;
 use color_eyre::Result;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tracing::{debug, error, info};
 
-use television::app::App;
+use television::app::{App, AppOutput};
+use television::cable::{
+    load_channel_prototype, set_channel_repos, update_channel_repos,
+};
+use television::channels::cable::set_source_args;
 use television::channels::{
-    entry::PreviewType, stdin::Channel as StdinChannel, TelevisionChannel,
+    entry::{Entry, PreviewType},
+    stdin::Channel as StdinChannel,
+    OnAir, TelevisionChannel,
 };
 use television::cli::{
-    guess_channel_from_prompt, list_channels, Cli, ParsedCliChannel,
-    PostProcessedCli,
+    cli_parsing_error_exit, guess_channel_from_prompt, list_channels, Cli,
+    ParsedCliChannel, PostProcessedCli,
 };
-use television::config::Config;
+use television::config::{load_keybindings_file, Config};
+use television::matcher::config::set_max_threads;
+use television::television::GotoTarget;
 use television::utils::{
+    env_file::load_env_file,
+    last_selection::{load_last_selection, save_last_selection},
     shell::{completion_script, Shell},
     stdin::is_readable_stdin,
+    strings::shell_quote,
+    terminal::{is_light_color, query_background_color},
 };
 
+/// How long to wait for the terminal to report its background color.
+const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<()> {
     television::errors::init()?;
     television::logging::init()?;
 
-    let args: PostProcessedCli = Cli::parse().into();
-    debug!("{:?}", args);
-
+    let cli = Cli::parse();
     let mut config = Config::new()?;
+    // channels are looked up while processing the arguments
+    set_channel_repos(&config.config.channel_repos);
+
+    let args: PostProcessedCli = cli.into();
+    debug!("{:?}", args);
 
-    if let Some(command) = args.command {
+    if let Some(command) = &args.command {
         match command {
             television::cli::Command::ListChannels => {
                 list_channels();
                 exit(0);
             }
+            television::cli::Command::UpdateChannels => {
+                if config.config.channel_repos.is_empty() {
+                    println!("No channel repositories configured (see the `channel_repos` setting)");
+                }
+                let mut failed = false;
+                for (url, result) in
+                    update_channel_repos(&config.config.channel_repos)
+                {
+                    match result {
+                        Ok(()) => println!("Updated {url}"),
+                        Err(e) => {
+                            failed = true;
+                            eprintln!("Failed to update {url}: {e}");
+                        }
+                    }
+                }
+                exit(i32::from(failed));
+            }
             television::cli::Command::InitShell { shell } => {
-                let script = completion_script(Shell::from(shell))?;
+                let script = completion_script(Shell::from(*shell))?;
                 println!("{script}");
                 exit(0);
             }
         }
     }
 
-    config.config.tick_rate =
-        args.tick_rate.unwrap_or(config.config.tick_rate);
-    config.config.frame_rate =
-        args.frame_rate.unwrap_or(config.config.frame_rate);
-    if args.no_preview {
-        config.ui.show_preview_panel = false;
+    if let Err(e) = apply_cli_overrides(&mut config, &args) {
+        cli_parsing_error_exit(&format!("{e:#}"));
+    }
+    set_max_threads(args.matcher_threads.unwrap_or(config.matcher.threads));
+    set_source_args(&args.source_args);
+    let light_background = (config.ui.theme_light.is_some()
+        || config.ui.theme_dark.is_some())
+    .then(|| query_background_color(BACKGROUND_QUERY_TIMEOUT))
+    .flatten()
+    .map(|background| {
+        debug!("Terminal background: {:?}", background);
+        is_light_color(background)
+    });
+    if let Some(light_background) = light_background {
+        apply_background_theme(&mut config, light_background);
     }
+    // the configuration is reloaded with the same command line settings
+    let config_args = args.clone();
+    let load_config = move || {
+        let mut config = Config::new()?;
+        apply_cli_overrides(&mut config, &config_args)?;
+        if let Some(light_background) = light_background {
+            apply_background_theme(&mut config, light_background);
+        }
+        Ok(config)
+    };
+
+    // this needs to happen before any command is spawned
+    if let Some(env_file) = args.env_file {
+        if let Err(e) = load_env_file(Path::new(&env_file)) {
+            cli_parsing_error_exit(&e.to_string());
+        }
+    }
+
+    // the output file is relative to the directory tv was started from
+    let output_file = args
+        .output_file
+        .as_deref()
+        .map(std::path::absolute)
+        .transpose()?;
 
     if let Some(working_directory) = args.working_directory {
         let path = Path::new(&working_directory);
@@ -60,64 +131,152 @@ async fn main() -> Result<()> {
                 "Working directory \"{}\" does not exist",
                 &working_directory
             );
-            println!(
-                "Error: Working directory \"{}\" does not exist",
-                &working_directory
-            );
-            exit(1);
+            cli_parsing_error_exit(&format!(
+                "working directory \"{working_directory}\" does not exist"
+            ));
         }
         env::set_current_dir(path)?;
     }
 
-    match App::new(
-        {
-            if is_readable_stdin() {
-                debug!("Using stdin channel");
-                TelevisionChannel::Stdin(StdinChannel::new(
-                    args.preview_command.map(PreviewType::Command),
-                ))
-            } else if let Some(prompt) = args.autocomplete_prompt {
-                let channel = guess_channel_from_prompt(
-                    &prompt,
-                    &config.shell_integration.commands,
-                )?;
-                debug!("Using guessed channel: {:?}", channel);
-                match channel {
-                    ParsedCliChannel::Builtin(c) => c.to_channel(),
-                    ParsedCliChannel::Cable(c) => {
-                        TelevisionChannel::Cable(c.into())
-                    }
-                }
-            } else {
-                debug!("Using {:?} channel", args.channel);
-                match args.channel {
-                    ParsedCliChannel::Builtin(c) => c.to_channel(),
-                    ParsedCliChannel::Cable(c) => {
-                        TelevisionChannel::Cable(c.into())
-                    }
-                }
+    let mut channel = if let Some(channel_file) = args.channel_file {
+        // this takes precedence over stdin which may hold the prototype
+        match load_channel_prototype(&channel_file) {
+            Ok(prototype) => {
+                debug!("Using channel from file: {:?}", prototype);
+                TelevisionChannel::Cable(prototype.into())
+            }
+            Err(e) => cli_parsing_error_exit(&e.to_string()),
+        }
+    } else if !args.server && is_readable_stdin() {
+        // in `--server` mode, stdin holds the queries
+        debug!("Using stdin channel");
+        TelevisionChannel::Stdin(StdinChannel::new(
+            args.preview_command.map(PreviewType::Command),
+        ))
+    } else if let Some(prompt) = args.autocomplete_prompt {
+        let channel = guess_channel_from_prompt(
+            &prompt,
+            &config.shell_integration.commands,
+        )?;
+        debug!("Using guessed channel: {:?}", channel);
+        match channel {
+            ParsedCliChannel::Builtin(c) => c.to_channel(),
+            ParsedCliChannel::Cable(c) => {
+                TelevisionChannel::Cable((*c).into())
             }
-        },
+        }
+    } else {
+        debug!("Using {:?} channel", args.channel);
+        match args.channel {
+            ParsedCliChannel::Builtin(c) => c.to_channel(),
+            ParsedCliChannel::Cable(c) => {
+                TelevisionChannel::Cable((*c).into())
+            }
+        }
+    };
+    if let Some(scheme) = args.scheme {
+        channel.set_matching_scheme(scheme);
+    }
+    channel.set_filter(args.filter_pattern.clone());
+
+    let shell_escaping = args.shell_quote || config.output.shell_escaping;
+    let terminator = if args.print0 { '\0' } else { '\n' };
+
+    if let Some(query) = args.filter {
+        let entries = channel.filter(&query, None).await;
+        channel.shutdown();
+        let mut output = Vec::new();
+        write_entries(
+            &mut output,
+            (0..).zip(&entries).map(|(rank, entry)| (Some(rank), entry)),
+            shell_escaping,
+            terminator,
+            args.with_index,
+        )?;
+        emit_output(&output, output_file.as_deref(), args.tee)?;
+        exit(i32::from(entries.is_empty()));
+    }
+
+    if args.server {
+        serve_channel(
+            &mut channel,
+            &args.server_delimiter,
+            args.server_limit,
+            shell_escaping,
+            terminator,
+            args.with_index,
+        )
+        .await?;
+        channel.shutdown();
+        exit(0);
+    }
+
+    let remember_selection = config.config.remember_selection;
+    let data_dir = config.config.data_dir.clone();
+    let goto = args
+        .goto
+        .map(|value| GotoTarget {
+            value,
+            confirm: args.goto_confirm,
+            fallback_to_query: true,
+        })
+        .or_else(|| {
+            remember_selection
+                .then(|| load_last_selection(&data_dir, &channel.name()))
+                .flatten()
+                .map(|value| GotoTarget {
+                    value,
+                    confirm: false,
+                    fallback_to_query: false,
+                })
+        });
+
+    match App::new(
+        channel,
         config,
         &args.passthrough_keybindings,
         args.input,
-    ) {
+        goto,
+        args.sync,
+        args.no_preview,
+        args.filter_pattern,
+        args.scheme,
+    )
+    .map(|app| {
+        app.with_config_loader(load_config)
+            .with_entry_ranks(args.with_index)
+    }) {
         Ok(mut app) => {
             stdout().flush()?;
             let output = app.run(stdout().is_terminal()).await?;
             info!("{:?}", output);
-            // lock stdout
-            let stdout_handle = stdout().lock();
-            let mut bufwriter = BufWriter::new(stdout_handle);
+            if remember_selection {
+                remember_last_selection(&app, &output, &data_dir).await;
+            }
+            let mut buffer = Vec::new();
             if let Some(passthrough) = output.passthrough {
-                writeln!(bufwriter, "{passthrough}")?;
+                write!(buffer, "{passthrough}{terminator}")?;
             }
             if let Some(entries) = output.selected_entries {
-                for entry in &entries {
-                    writeln!(bufwriter, "{}", entry.stdout_repr())?;
+                let mut entries = entries
+                    .iter()
+                    .map(|entry| {
+                        (output.entry_ranks.get(entry).copied(), entry)
+                    })
+                    .collect::<Vec<_>>();
+                if args.with_index {
+                    // entries that don't match the query come last
+                    entries.sort_by_key(|(rank, _)| (rank.is_none(), *rank));
                 }
+                write_entries(
+                    &mut buffer,
+                    entries,
+                    shell_escaping,
+                    terminator,
+                    args.with_index,
+                )?;
             }
-            bufwriter.flush()?;
+            emit_output(&buffer, output_file.as_deref(), args.tee)?;
             exit(0);
         }
         Err(err) => {
@@ -126,3 +285,166 @@ async fn main() -> Result<()> {
         }
     }
 }
+
+/// Apply the settings given on the command line to the configuration.
+fn apply_cli_overrides(
+    config: &mut Config,
+    args: &PostProcessedCli,
+) -> Result<()> {
+    config.config.tick_rate =
+        args.tick_rate.unwrap_or(config.config.tick_rate);
+    config.config.frame_rate =
+        args.frame_rate.unwrap_or(config.config.frame_rate);
+    config.config.idle_timeout =
+        args.idle_timeout.unwrap_or(config.config.idle_timeout);
+    config.config.debug = args.debug;
+    if args.preview_hidden {
+        config.ui.show_preview_panel = false;
+    }
+    if let Some(max) = args.preview_concurrency {
+        config.previewers.command.max_concurrent = max;
+    }
+    if args.no_ansi_strip {
+        config.ui.preview_panel.sanitize = false;
+    }
+    if args.wrap_results {
+        config.ui.results_panel.wrap = true;
+    }
+    if let Some(label) = &args.preview_border_label {
+        config.ui.preview_panel.label = Some(label.clone());
+    }
+    if args.mouse {
+        config.ui.mouse = true;
+    } else if args.no_mouse {
+        config.ui.mouse = false;
+    }
+    if let Some(bind_file) = &args.bind_file {
+        config
+            .keybindings
+            .merge(load_keybindings_file(Path::new(bind_file))?);
+    }
+    if let Some(export_file) = &args.export_file {
+        config.config.export_file = Some(export_file.into());
+    }
+    config.config.selection_pipe =
+        args.selection_pipe.as_ref().map(Into::into);
+    Ok(())
+}
+
+/// Use the `theme_light` or `theme_dark` setting (if set) as the theme,
+/// depending on the terminal's background.
+fn apply_background_theme(config: &mut Config, light_background: bool) {
+    let theme = if light_background {
+        &config.ui.theme_light
+    } else {
+        &config.ui.theme_dark
+    };
+    if let Some(theme) = theme.clone() {
+        debug!("Using theme {:?}", theme);
+        config.ui.theme = theme;
+    }
+}
+
+/// Answer the queries read from stdin (one per line) with the best matches
+/// of the channel, each response being followed by `delimiter`, until stdin
+/// is closed.
+async fn serve_channel(
+    channel: &mut TelevisionChannel,
+    delimiter: &str,
+    limit: u32,
+    shell_escaping: bool,
+    terminator: char,
+    with_index: bool,
+) -> Result<()> {
+    let mut queries = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(query) = queries.next_line().await? {
+        let query = query.strip_suffix('\r').unwrap_or(&query);
+        let entries = channel.filter(query, Some(limit)).await;
+        let mut output = Vec::new();
+        write_entries(
+            &mut output,
+            (0..).zip(&entries).map(|(rank, entry)| (Some(rank), entry)),
+            shell_escaping,
+            terminator,
+            with_index,
+        )?;
+        write!(output, "{delimiter}{terminator}")?;
+        let mut stdout = stdout().lock();
+        stdout.write_all(&output)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Remember the selected entry (if a single one was selected) for the
+/// channel it was selected in.
+async fn remember_last_selection(
+    app: &App,
+    output: &AppOutput,
+    data_dir: &Path,
+) {
+    let Some(entries) = &output.selected_entries else {
+        return;
+    };
+    if let [entry] = entries.iter().collect::<Vec<_>>().as_slice() {
+        let channel = app.current_channel_name().await;
+        if let Err(e) = save_last_selection(data_dir, &channel, &entry.name) {
+            error!("{:?}", e);
+        }
+    }
+}
+
+/// Print the output to stdout, or write it to `output_file` if given (and
+/// to stdout as well with `tee`).
+///
+/// The file is written even if the output is empty, so that it never holds
+/// the results of a previous run.
+fn emit_output(
+    output: &[u8],
+    output_file: Option<&Path>,
+    tee: bool,
+) -> Result<()> {
+    if let Some(path) = output_file {
+        if let Err(e) = std::fs::write(path, output) {
+            eprintln!(
+                "Error: failed to write the output to {}: {e}",
+                path.display()
+            );
+            exit(1);
+        }
+    }
+    if output_file.is_none() || tee {
+        let mut bufwriter = BufWriter::new(stdout().lock());
+        bufwriter.write_all(output)?;
+        bufwriter.flush()?;
+    }
+    Ok(())
+}
+
+/// Write the given entries to `writer`, each followed by `terminator`.
+///
+/// With `with_index`, each entry is preceded by its rank (if known) and a
+/// tab.
+fn write_entries<'a, W: Write>(
+    writer: &mut W,
+    entries: impl IntoIterator<Item = (Option<u32>, &'a Entry)>,
+    shell_escaping: bool,
+    terminator: char,
+    with_index: bool,
+) -> Result<()> {
+    for (rank, entry) in entries {
+        if with_index {
+            if let Some(rank) = rank {
+                write!(writer, "{rank}")?;
+            }
+            write!(writer, "\t")?;
+        }
+        let repr = entry.stdout_repr();
+        if shell_escaping {
+            write!(writer, "{}{terminator}", shell_quote(&repr))?;
+        } else {
+            write!(writer, "{repr}{terminator}")?;
+        }
+    }
+    Ok(())
+}
@@ -1,3 +1,41 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// The process-wide cap on the number of matcher threads (0 means no cap).
+static MAX_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Limit the number of threads used by all matchers created from now on.
+///
+/// A value of 0 removes the limit, letting matchers use as many threads as
+/// there are available logical cores.
+pub fn set_max_threads(max_threads: usize) {
+    MAX_THREADS.store(max_threads, Ordering::Relaxed);
+}
+
+fn max_threads() -> Option<usize> {
+    match MAX_THREADS.load(Ordering::Relaxed) {
+        0 => None,
+        n => Some(n),
+    }
+}
+
+/// The scoring scheme used by the fuzzy matcher.
+///
+/// - `Path`: path-aware scoring which gives a bonus to matches on path
+///   segment boundaries (e.g. the basename of a file).
+/// - `Text`: generic scoring suited to arbitrary text.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchingScheme {
+    Path,
+    #[default]
+    Text,
+}
+
 /// The configuration of the fuzzy matcher.
 ///
 /// This contains the number of threads to use, whether to ignore case, whether
@@ -7,7 +45,8 @@
 /// fuzzy matcher, e.g. case-insensitive matching, no preference for prefix
 /// matches, and no optimization for matching paths as well as using the
 /// default number of threads (which corresponds to the number of available logical
-/// cores on the current machine).
+/// cores on the current machine, unless capped with `set_max_threads`) and
+/// no prefix bonus.
 #[derive(Copy, Clone, Debug)]
 pub struct Config {
     /// The number of threads to use for the fuzzy matcher.
@@ -18,23 +57,33 @@ pub struct Config {
     pub prefer_prefix: bool,
     /// Whether to optimize for matching paths.
     pub match_paths: bool,
+    /// The score added to items whose basename starts with the query
+    /// (0 disables the bonus).
+    ///
+    /// Each matched character is worth 16 points, so a bonus of 64 lets a
+    /// prefix match outrank a fuzzy match scoring up to 4 characters better.
+    /// Only the best matches are reordered, see `Matcher::rank`.
+    pub prefix_bonus: u32,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            n_threads: None,
+            n_threads: max_threads(),
             ignore_case: true,
             prefer_prefix: false,
             match_paths: false,
+            prefix_bonus: 0,
         }
     }
 }
 
 impl Config {
-    /// Set the number of threads to use.
+    /// Set the number of threads to use (within the limit set with
+    /// `set_max_threads`).
     pub fn n_threads(mut self, n_threads: usize) -> Self {
-        self.n_threads = Some(n_threads);
+        self.n_threads =
+            Some(max_threads().map_or(n_threads, |max| n_threads.min(max)));
         self
     }
 
@@ -55,6 +104,17 @@ impl Config {
         self.match_paths = match_paths;
         self
     }
+
+    /// Set the score added to items whose basename starts with the query.
+    pub fn prefix_bonus(mut self, prefix_bonus: u32) -> Self {
+        self.prefix_bonus = prefix_bonus;
+        self
+    }
+
+    /// Set the scoring scheme to use.
+    pub fn scheme(self, scheme: MatchingScheme) -> Self {
+        self.match_paths(scheme == MatchingScheme::Path)
+    }
 }
 
 impl From<&Config> for nucleo::Config {
@@ -1,3 +1,50 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use nucleo::Utf32Str;
+use regex::Regex;
+
+/// An item pushed into the fuzzy matcher, along with whether it matches the
+/// matcher's filter.
+pub(crate) struct FilteredItem<I> {
+    pub(crate) data: I,
+    /// The generation of the filter the item was last checked against
+    /// (shifted left by one), and whether it matched it (lowest bit).
+    ///
+    /// 0 means the item was never checked, filter generations start at 1.
+    filter_state: AtomicU32,
+}
+
+impl<I> FilteredItem<I> {
+    fn new(data: I) -> Self {
+        Self {
+            data,
+            filter_state: AtomicU32::new(0),
+        }
+    }
+
+    /// Whether `haystack`, the string this item is matched against, matches
+    /// `filter`, which is only checked once per filter generation.
+    pub(crate) fn matches_filter(
+        &self,
+        haystack: Utf32Str,
+        filter: &Regex,
+        generation: u32,
+        buffer: &mut String,
+    ) -> bool {
+        let state = self.filter_state.load(Ordering::Relaxed);
+        if state >> 1 == generation {
+            return state & 1 == 1;
+        }
+        buffer.clear();
+        buffer.extend(haystack.chars());
+        let matches = filter.is_match(buffer);
+        self.filter_state
+            .store(generation << 1 | u32::from(matches), Ordering::Relaxed);
+        matches
+    }
+}
+
 /// An injector that can be used to push items of type `I` into the fuzzy matcher.
 ///
 /// This is a wrapper around the `Injector` type from the `Nucleo` fuzzy matcher.
@@ -10,15 +57,24 @@ where
     I: Sync + Send + Clone + 'static,
 {
     /// The inner `Injector` from the `Nucleo` fuzzy matcher.
-    inner: nucleo::Injector<I>,
+    inner: nucleo::Injector<FilteredItem<I>>,
+    /// A token shared with the matcher, used to know whether items may still
+    /// be pushed.
+    _loading_token: Arc<()>,
 }
 
 impl<I> Injector<I>
 where
     I: Sync + Send + Clone + 'static,
 {
-    pub fn new(inner: nucleo::Injector<I>) -> Self {
-        Self { inner }
+    pub(crate) fn new(
+        inner: nucleo::Injector<FilteredItem<I>>,
+        loading_token: Arc<()>,
+    ) -> Self {
+        Self {
+            inner,
+            _loading_token: loading_token,
+        }
     }
 
     /// Push an item into the fuzzy matcher.
@@ -44,6 +100,36 @@ where
     where
         F: FnOnce(&I, &mut [nucleo::Utf32String]),
     {
-        self.inner.push(item, f);
+        self.inner
+            .push(FilteredItem::new(item), |item, cols| f(&item.data, cols));
+    }
+
+    /// Push a batch of items into the fuzzy matcher.
+    ///
+    /// This is equivalent to calling `push` for each item but reuses the same
+    /// closure for the whole batch, which cuts the per-item overhead when
+    /// injecting large amounts of items.
+    ///
+    /// # Example
+    /// ```
+    /// use television::matcher::{config::Config, Matcher};
+    ///
+    /// let matcher = Matcher::new(Config::default());
+    /// let injector = matcher.injector();
+    /// injector.push_batch(
+    ///     vec!["a".to_string(), "b".to_string()],
+    ///     |s, cols| cols[0] = s.clone().into(),
+    /// );
+    /// ```
+    pub fn push_batch<T, F>(&self, items: T, f: F)
+    where
+        T: IntoIterator<Item = I>,
+        F: Fn(&I, &mut [nucleo::Utf32String]),
+    {
+        for item in items {
+            self.inner.push(FilteredItem::new(item), |item, cols| {
+                f(&item.data, cols);
+            });
+        }
     }
 }
@@ -1,5 +1,9 @@
-use injector::Injector;
+use injector::{FilteredItem, Injector};
+use nucleo::pattern::Atom;
+use nucleo::Utf32Str;
+use regex::Regex;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 pub mod config;
 pub mod injector;
@@ -8,15 +12,24 @@ pub mod matched_item;
 
 const MATCHER_TICK_TIMEOUT: u64 = 2;
 
+/// How long to let the matcher work between ticks when waiting for matches.
+const MATCHER_WAIT_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The number of best matches rescored to apply the prefix bonus: the
+/// matches past those keep nucleo's order, which keeps ranking cheap however
+/// many items there are.
+const RESCORED_MATCHES: usize = 10_000;
+
 /// The status of the fuzzy matcher.
 ///
 /// This currently only contains a boolean indicating whether the matcher is
-/// running in the background.
+/// running in the background or still has items being injected.
 /// This mostly serves as a way to communicate the status of the matcher to the
 /// front-end and display a loading indicator.
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Status {
-    /// Whether the matcher is currently running.
+    /// Whether the matcher is currently running or still being fed items
+    /// (i.e. some of its injectors are still alive).
     pub running: bool,
 }
 
@@ -41,7 +54,9 @@ where
     I: Sync + Send + Clone + 'static,
 {
     /// The inner `Nucleo` fuzzy matcher.
-    inner: nucleo::Nucleo<I>,
+    inner: nucleo::Nucleo<FilteredItem<I>>,
+    /// The configuration the matcher was built with.
+    config: config::Config,
     /// The current total number of items in the matcher.
     pub total_item_count: u32,
     /// The current number of matched items in the matcher.
@@ -50,6 +65,33 @@ where
     pub status: Status,
     /// The last pattern that was matched against.
     pub last_pattern: String,
+    /// A token cloned into every injector, used to know whether the source
+    /// is fully loaded.
+    loading_token: Arc<()>,
+    /// Only items matching this regex are returned, whatever the pattern.
+    filter: Option<Regex>,
+    /// Incremented whenever the filter changes, which invalidates whether
+    /// items were found to match it.
+    filter_generation: u32,
+    /// Whether ticking is suspended, the current matches being kept as is.
+    paused: bool,
+    /// The matches of the current snapshot returned once the filter and the
+    /// prefix bonus were applied, in order (as indices into nucleo's order),
+    /// or `None` when nucleo's matches are used as is.
+    ranking: Option<Vec<u32>>,
+    /// The snapshot `ranking` was computed for, or `None` if it is outdated.
+    ranked_snapshot: Option<RankedSnapshot>,
+    /// The matcher used to rescore matches, created on first use.
+    scorer: Option<nucleo::Matcher>,
+}
+
+/// What identifies the matches of a snapshot, used to know whether they need
+/// to be ranked again.
+#[derive(PartialEq)]
+struct RankedSnapshot {
+    atoms: Vec<Atom>,
+    item_count: u32,
+    matched_item_count: u32,
 }
 
 impl<I> Matcher<I>
@@ -65,18 +107,222 @@ where
                 config.n_threads,
                 1,
             ),
+            config,
             total_item_count: 0,
             matched_item_count: 0,
             status: Status::default(),
             last_pattern: String::new(),
+            loading_token: Arc::new(()),
+            filter: None,
+            filter_generation: 0,
+            paused: false,
+            ranking: None,
+            ranked_snapshot: None,
+            scorer: None,
         }
     }
 
+    /// Change the scoring scheme used by the matcher.
+    ///
+    /// Items that were already matched are rescored on the next tick.
+    pub fn set_scheme(&mut self, scheme: config::MatchingScheme) {
+        self.config = self.config.scheme(scheme);
+        self.inner.update_config((&self.config).into());
+        if let Some(scorer) = &mut self.scorer {
+            scorer.config = (&self.config).into();
+        }
+        self.ranked_snapshot = None;
+    }
+
+    /// Change the score added to items whose basename starts with the query
+    /// (0 disables the bonus).
+    pub fn set_prefix_bonus(&mut self, prefix_bonus: u32) {
+        self.config = self.config.prefix_bonus(prefix_bonus);
+        self.ranked_snapshot = None;
+        self.rank();
+    }
+
+    /// Only return the items matching `filter` (or all of them with `None`),
+    /// independently of the pattern.
+    pub fn set_filter(&mut self, filter: Option<Regex>) {
+        self.filter = filter;
+        // the generation is stored on 31 bits, and 0 means never checked
+        self.filter_generation = self.filter_generation % (u32::MAX >> 1) + 1;
+        self.ranked_snapshot = None;
+        self.rank();
+    }
+
+    /// Suspend (or resume) matching: while paused, ticking does nothing so
+    /// that the current matches are kept and no matching work is done, even
+    /// if the pattern changes or new items are pushed.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
     /// Tick the fuzzy matcher.
     ///
     /// This should be called periodically to update the state of the matcher.
     pub fn tick(&mut self) {
-        self.status = self.inner.tick(MATCHER_TICK_TIMEOUT).into();
+        self.tick_matching();
+    }
+
+    /// Tick the fuzzy matcher, returning whether matching is still in
+    /// progress (whether the source is still being loaded or not).
+    fn tick_matching(&mut self) -> bool {
+        if self.paused {
+            return false;
+        }
+        // checked first: items pushed right before the last injector was
+        // dropped must be part of the tick for it to be the last one
+        let loading = self.loading();
+        let status = self.inner.tick(MATCHER_TICK_TIMEOUT);
+        if status.changed {
+            self.rank();
+        }
+        self.status = status.into();
+        // nucleo only reports whether matching is in progress, which might
+        // briefly not be the case while the source is still being loaded
+        self.status.running |= loading;
+        status.running
+    }
+
+    /// Tick the fuzzy matcher until the matches of the current pattern are
+    /// computed, or for at most `timeout`.
+    ///
+    /// This is used when the results of a pattern that was just set are
+    /// needed right away, rather than on the next periodic tick. With a zero
+    /// `timeout`, the matcher is ticked once without waiting.
+    pub fn wait_for_matches(&mut self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        while self.tick_matching() && Instant::now() < deadline {
+            // ticking returns right away when the worker is idle, so this
+            // would otherwise be a busy loop
+            std::thread::sleep(MATCHER_WAIT_INTERVAL);
+        }
+    }
+
+    /// Drop the matches of the current snapshot that don't match the filter
+    /// and reorder the rest after adding the prefix bonus to the score of
+    /// items whose basename starts with one of the words of the query.
+    ///
+    /// nucleo doesn't expose its scores, so matches are rescored here: only
+    /// the first `RESCORED_MATCHES` are, and only when the snapshot changed.
+    fn rank(&mut self) {
+        let snapshot = self.inner.snapshot();
+        let pattern = snapshot.pattern().column_pattern(0);
+        let ranked_snapshot = RankedSnapshot {
+            atoms: pattern.atoms.clone(),
+            item_count: snapshot.item_count(),
+            matched_item_count: snapshot.matched_item_count(),
+        };
+        if self.ranked_snapshot.as_ref() == Some(&ranked_snapshot) {
+            return;
+        }
+        self.ranked_snapshot = Some(ranked_snapshot);
+        self.ranking = None;
+
+        let prefixes: Vec<Utf32Str> = pattern
+            .atoms
+            .iter()
+            .filter(|atom| {
+                self.config.prefix_bonus > 0
+                    && !atom.negative
+                    && !atom.needle_text().is_empty()
+            })
+            .map(Atom::needle_text)
+            .collect();
+        if prefixes.is_empty() && self.filter.is_none() {
+            self.matched_item_count = self.ranked_count();
+            return;
+        }
+
+        let mut buffer = String::new();
+        let mut ranking: Vec<u32> = snapshot
+            .matched_items(..)
+            .zip(0..)
+            .filter(|(item, _)| {
+                self.filter.as_ref().map_or(true, |filter| {
+                    item.data.matches_filter(
+                        item.matcher_columns[0].slice(..),
+                        filter,
+                        self.filter_generation,
+                        &mut buffer,
+                    )
+                })
+            })
+            .map(|(_, n)| n)
+            .collect();
+
+        if !prefixes.is_empty() {
+            let scorer = self.scorer.get_or_insert_with(|| {
+                nucleo::Matcher::new((&self.config).into())
+            });
+            let rescored = ranking.len().min(RESCORED_MATCHES);
+            let mut scores: Vec<(u32, u32)> = ranking[..rescored]
+                .iter()
+                .map(|&n| {
+                    let score =
+                        snapshot.get_matched_item(n).map_or(0, |item| {
+                            let haystack = item.matcher_columns[0].slice(..);
+                            let score =
+                                pattern.score(haystack, scorer).unwrap_or(0);
+                            if prefixes.iter().any(|prefix| {
+                                basename_starts_with(haystack, *prefix)
+                            }) {
+                                score.saturating_add(self.config.prefix_bonus)
+                            } else {
+                                score
+                            }
+                        });
+                    (n, score)
+                })
+                .collect();
+            // stable, so that ties keep nucleo's order
+            scores.sort_by(|a, b| b.1.cmp(&a.1));
+            for (rank, (n, _)) in ranking.iter_mut().zip(scores) {
+                *rank = n;
+            }
+        }
+        self.ranking = Some(ranking);
+        self.matched_item_count = self.ranked_count();
+    }
+
+    /// The number of matches once the filter was applied.
+    #[allow(clippy::cast_possible_truncation)]
+    fn ranked_count(&self) -> u32 {
+        match &self.ranking {
+            Some(ranking) => ranking.len() as u32,
+            None => self.inner.snapshot().matched_item_count(),
+        }
+    }
+
+    /// Map a position in the returned results to a position in nucleo's
+    /// order.
+    fn ranked(&self, n: u32) -> Option<u32> {
+        match &self.ranking {
+            Some(ranking) => ranking.get(n as usize).copied(),
+            None => Some(n),
+        }
+    }
+
+    /// Whether items may still be pushed into the matcher, i.e. whether any
+    /// of its injectors is still alive.
+    pub fn loading(&self) -> bool {
+        Arc::strong_count(&self.loading_token) > 1
+    }
+
+    /// Drop all the items, e.g. to load them again from scratch.
+    ///
+    /// Items pushed through the injectors obtained before restarting are
+    /// ignored, and the matcher is no longer considered to be loading until
+    /// a new injector is obtained.
+    pub fn restart(&mut self) {
+        self.inner.restart(true);
+        self.loading_token = Arc::new(());
+        self.total_item_count = 0;
+        self.matched_item_count = 0;
+        self.ranking = None;
+        self.ranked_snapshot = None;
     }
 
     /// Get an injector that can be used to push items into the fuzzy matcher.
@@ -98,7 +344,7 @@ where
     /// );
     /// ```
     pub fn injector(&self) -> Injector<I> {
-        Injector::new(self.inner.injector())
+        Injector::new(self.inner.injector(), self.loading_token.clone())
     }
 
     /// Find items that match the given pattern.
@@ -157,15 +403,13 @@ where
     ) -> Vec<matched_item::MatchedItem<I>> {
         let snapshot = self.inner.snapshot();
         self.total_item_count = snapshot.item_count();
-        self.matched_item_count = snapshot.matched_item_count();
+        self.matched_item_count = self.ranked_count();
 
         let mut col_indices = Vec::new();
         let mut matcher = lazy::MATCHER.lock();
 
-        snapshot
-            .matched_items(
-                offset..(num_entries + offset).min(self.matched_item_count),
-            )
+        (offset..(num_entries + offset).min(self.matched_item_count))
+            .filter_map(|n| snapshot.get_matched_item(self.ranked(n)?))
             .map(move |item| {
                 snapshot.pattern().column_pattern(0).indices(
                     item.matcher_columns[0].slice(..),
@@ -179,7 +423,7 @@ where
 
                 let matched_string = item.matcher_columns[0].to_string();
                 matched_item::MatchedItem {
-                    inner: item.data.clone(),
+                    inner: item.data.data.clone(),
                     matched_string,
                     match_indices: indices.map(|i| (i, i + 1)).collect(),
                 }
@@ -207,13 +451,218 @@ where
         index: u32,
     ) -> Option<matched_item::MatchedItem<I>> {
         let snapshot = self.inner.snapshot();
-        snapshot.get_matched_item(index).map(|item| {
+        snapshot.get_matched_item(self.ranked(index)?).map(|item| {
             let matched_string = item.matcher_columns[0].to_string();
             matched_item::MatchedItem {
-                inner: item.data.clone(),
+                inner: item.data.data.clone(),
                 matched_string,
                 match_indices: Vec::new(),
             }
         })
     }
 }
+
+/// Whether the part of `haystack` after its last path separator starts with
+/// `prefix`, ignoring case.
+fn basename_starts_with(haystack: Utf32Str, prefix: Utf32Str) -> bool {
+    let start = haystack
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| matches!(c, '/' | '\\'))
+        .last()
+        .map_or(0, |(i, _)| i + 1);
+    let basename = haystack.slice(start..);
+    prefix.len() <= basename.len()
+        && prefix
+            .chars()
+            .zip(basename.chars())
+            .all(|(p, c)| p == c || p.to_lowercase().eq(c.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_while_injectors_are_alive() {
+        let mut matcher = Matcher::<String>::new(config::Config::default());
+        let injector = matcher.injector();
+        injector.push(String::from("entry"), |e, cols| {
+            cols[0] = e.clone().into();
+        });
+
+        matcher.tick();
+        assert!(matcher.loading());
+        assert!(matcher.status.running);
+
+        drop(injector);
+        while matcher.status.running {
+            matcher.tick();
+        }
+        assert!(!matcher.loading());
+    }
+
+    #[test]
+    fn test_paused_results_stay_frozen() {
+        let results = |matcher: &mut Matcher<String>| {
+            let mut results = matcher
+                .results(10, 0)
+                .into_iter()
+                .map(|item| item.inner)
+                .collect::<Vec<_>>();
+            results.sort();
+            results
+        };
+        let mut matcher = Matcher::<String>::new(config::Config::default());
+        let injector = matcher.injector();
+        let push = |entry: &str| {
+            injector.push(String::from(entry), |e, cols| {
+                cols[0] = e.clone().into();
+            });
+        };
+        push("a1");
+        push("a2");
+        matcher.wait_for_matches(Duration::from_millis(200));
+        assert_eq!(results(&mut matcher), ["a1", "a2"]);
+
+        matcher.set_paused(true);
+        push("a3");
+        matcher.find("2");
+        matcher.wait_for_matches(Duration::from_millis(200));
+        assert_eq!(results(&mut matcher), ["a1", "a2"]);
+        assert_eq!(matcher.total_item_count, 2);
+
+        matcher.set_paused(false);
+        push("b2");
+        matcher.wait_for_matches(Duration::from_millis(200));
+        assert_eq!(results(&mut matcher), ["a2", "b2"]);
+        assert_eq!(matcher.total_item_count, 4);
+    }
+
+    fn ranked_results(prefix_bonus: u32, pattern: &str) -> Vec<String> {
+        filtered_results(prefix_bonus, None, pattern)
+    }
+
+    fn filtered_results(
+        prefix_bonus: u32,
+        filter: Option<&str>,
+        pattern: &str,
+    ) -> Vec<String> {
+        let mut matcher = Matcher::<String>::new(
+            config::Config::default()
+                .match_paths(true)
+                .prefix_bonus(prefix_bonus),
+        );
+        matcher.set_filter(filter.map(|f| Regex::new(f).unwrap()));
+        let injector = matcher.injector();
+        for entry in ["src/models/foo.rs", "docs/models.md", "models/bar.rs"] {
+            injector.push(String::from(entry), |e, cols| {
+                cols[0] = e.clone().into();
+            });
+        }
+        drop(injector);
+        matcher.find(pattern);
+        matcher.tick();
+        while matcher.status.running {
+            matcher.tick();
+        }
+        matcher
+            .results(10, 0)
+            .into_iter()
+            .map(|item| item.inner)
+            .collect()
+    }
+
+    #[test]
+    fn test_prefix_bonus() {
+        let ranked = ranked_results(1000, "foo");
+        assert_eq!(ranked[0], "src/models/foo.rs");
+
+        let plain = ranked_results(0, "mod");
+        let ranked = ranked_results(1000, "mod");
+        assert_eq!(plain.len(), ranked.len());
+        assert_eq!(ranked[0], "docs/models.md");
+    }
+
+    #[test]
+    fn test_prefix_bonus_any_word() {
+        let plain = ranked_results(0, "s mod");
+        let ranked = ranked_results(1000, "s mod");
+        assert_ne!(plain[0], "docs/models.md");
+        assert_eq!(ranked[0], "docs/models.md");
+    }
+
+    #[test]
+    fn test_set_prefix_bonus() {
+        let mut matcher = Matcher::<String>::new(
+            config::Config::default().match_paths(true),
+        );
+        let injector = matcher.injector();
+        for entry in ["src/models/foo.rs", "docs/models.md", "models/bar.rs"] {
+            injector.push(String::from(entry), |e, cols| {
+                cols[0] = e.clone().into();
+            });
+        }
+        drop(injector);
+        matcher.find("foo");
+        matcher.wait_for_matches(std::time::Duration::from_secs(5));
+        matcher.set_prefix_bonus(1000);
+        let results = matcher.results(10, 0);
+        assert_eq!(results[0].inner, "src/models/foo.rs");
+        assert_eq!(matcher.matched_item_count, 1);
+    }
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(
+            filtered_results(0, Some(r"\.rs$"), ""),
+            vec!["src/models/foo.rs", "models/bar.rs"]
+        );
+        let mut results = filtered_results(0, Some(r"\.rs$"), "mod");
+        results.sort();
+        assert_eq!(results, vec!["models/bar.rs", "src/models/foo.rs"]);
+        assert!(filtered_results(1000, Some("^docs/"), "bar").is_empty());
+    }
+
+    #[test]
+    fn test_change_filter() {
+        let mut matcher = Matcher::<String>::new(config::Config::default());
+        let injector = matcher.injector();
+        for entry in ["src/models/foo.rs", "docs/models.md", "models/bar.rs"] {
+            injector.push(String::from(entry), |e, cols| {
+                cols[0] = e.clone().into();
+            });
+        }
+        drop(injector);
+        matcher.wait_for_matches(std::time::Duration::from_secs(5));
+        let results = |matcher: &mut Matcher<String>| -> Vec<String> {
+            matcher
+                .results(10, 0)
+                .into_iter()
+                .map(|item| item.inner)
+                .collect()
+        };
+
+        matcher.set_filter(Some(Regex::new(r"\.md$").unwrap()));
+        assert_eq!(results(&mut matcher), vec!["docs/models.md"]);
+        matcher.set_filter(Some(Regex::new("^models/").unwrap()));
+        assert_eq!(results(&mut matcher), vec!["models/bar.rs"]);
+        matcher.set_filter(None);
+        assert_eq!(results(&mut matcher).len(), 3);
+    }
+
+    #[test]
+    fn test_basename_starts_with() {
+        let mut buf = Vec::new();
+        let mut prefix_buf = Vec::new();
+        let prefix = Utf32Str::new("mod", &mut prefix_buf);
+        assert!(basename_starts_with(
+            Utf32Str::new("docs/Models.md", &mut buf),
+            prefix
+        ));
+        assert!(!basename_starts_with(
+            Utf32Str::new("models/bar.rs", &mut buf),
+            prefix
+        ));
+    }
+}
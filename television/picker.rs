@@ -6,6 +6,9 @@ pub struct Picker {
     pub(crate) state: ListState,
     pub(crate) relative_state: ListState,
     inverted: bool,
+    /// The minimum number of rows to keep between the cursor and the edges
+    /// of the list while scrolling.
+    scroll_margin: usize,
     pub(crate) input: Input,
 }
 
@@ -21,6 +24,7 @@ impl Picker {
             state: ListState::default(),
             relative_state: ListState::default(),
             inverted: false,
+            scroll_margin: 0,
             input: Input::new(input.unwrap_or(EMPTY_STRING.to_string())),
         }
     }
@@ -36,6 +40,11 @@ impl Picker {
         self
     }
 
+    pub(crate) fn with_scroll_margin(mut self, scroll_margin: u16) -> Self {
+        self.scroll_margin = scroll_margin.into();
+        self
+    }
+
     pub(crate) fn reset_selection(&mut self) {
         self.state.select(Some(0));
         self.relative_state.select(Some(0));
@@ -95,12 +104,24 @@ impl Picker {
         }
     }
 
+    /// The scroll margin, capped so that it never exceeds half the height.
+    fn effective_scroll_margin(&self, height: usize) -> usize {
+        self.scroll_margin.min(height / 2)
+    }
+
     fn _select_next(&mut self, total_items: usize, height: usize) {
         let selected = self.selected().unwrap_or(0);
         let relative_selected = self.relative_selected().unwrap_or(0);
-        self.select(Some(selected.saturating_add(1) % total_items));
-        self.relative_select(Some((relative_selected + 1).min(height)));
-        if self.selected().unwrap() == 0 {
+        let new_selected = selected.saturating_add(1) % total_items;
+        self.select(Some(new_selected));
+        // keep `scroll_margin` rows below the cursor unless we're close to the
+        // end of the list and can't scroll any further
+        let max_offset = (total_items - 1).saturating_sub(height);
+        let max_relative = height
+            .saturating_sub(self.effective_scroll_margin(height))
+            .max(new_selected.saturating_sub(max_offset));
+        self.relative_select(Some((relative_selected + 1).min(max_relative)));
+        if new_selected == 0 {
             self.relative_select(Some(0));
         }
     }
@@ -108,9 +129,16 @@ impl Picker {
     fn _select_prev(&mut self, total_items: usize, height: usize) {
         let selected = self.selected().unwrap_or(0);
         let relative_selected = self.relative_selected().unwrap_or(0);
-        self.select(Some((selected + (total_items - 1)) % total_items));
-        self.relative_select(Some(relative_selected.saturating_sub(1)));
-        if self.selected().unwrap() == total_items - 1 {
+        let new_selected = (selected + (total_items - 1)) % total_items;
+        self.select(Some(new_selected));
+        // keep `scroll_margin` rows above the cursor unless we're close to the
+        // start of the list and can't scroll any further
+        let min_relative =
+            self.effective_scroll_margin(height).min(new_selected);
+        self.relative_select(Some(
+            relative_selected.saturating_sub(1).max(min_relative),
+        ));
+        if new_selected == total_items - 1 {
             self.relative_select(Some(height));
         }
     }
@@ -280,4 +308,70 @@ mod tests {
         assert_eq!(picker.selected(), Some(0), "selected");
         assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
     }
+
+    /// - item 0         *
+    /// - item 1         *
+    /// - item 2         *
+    /// - item 3 S     R *
+    /// - item 4 next    * height
+    /// - item 5
+    /// - item 6
+    #[test]
+    fn test_picker_select_next_with_scroll_margin() {
+        let mut picker = Picker::default().with_scroll_margin(1);
+        picker.select(Some(3));
+        picker.relative_select(Some(3));
+        picker.select_next(1, 7, 4);
+        assert_eq!(picker.selected(), Some(4), "selected");
+        assert_eq!(picker.relative_selected(), Some(3), "relative_selected");
+    }
+
+    /// - item 0
+    /// - item 1
+    /// - item 2         *
+    /// - item 3         *
+    /// - item 4         *
+    /// - item 5 S     R *
+    /// - item 6 next    * height
+    #[test]
+    fn test_picker_select_next_with_scroll_margin_at_end() {
+        let mut picker = Picker::default().with_scroll_margin(1);
+        picker.select(Some(5));
+        picker.relative_select(Some(3));
+        picker.select_next(1, 7, 4);
+        assert_eq!(picker.selected(), Some(6), "selected");
+        assert_eq!(picker.relative_selected(), Some(4), "relative_selected");
+    }
+
+    /// - item 0
+    /// - item 1
+    /// - item 2 prev    *
+    /// - item 3 S     R *
+    /// - item 4         *
+    /// - item 5         *
+    /// - item 6         * height
+    #[test]
+    fn test_picker_select_prev_with_scroll_margin() {
+        let mut picker = Picker::default().with_scroll_margin(1);
+        picker.select(Some(3));
+        picker.relative_select(Some(1));
+        picker.select_prev(1, 7, 4);
+        assert_eq!(picker.selected(), Some(2), "selected");
+        assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
+    }
+
+    /// - item 0 prev    *
+    /// - item 1 S     R *
+    /// - item 2         *
+    /// - item 3         *
+    /// - item 4         * height
+    #[test]
+    fn test_picker_select_prev_with_scroll_margin_at_start() {
+        let mut picker = Picker::default().with_scroll_margin(1);
+        picker.select(Some(1));
+        picker.relative_select(Some(1));
+        picker.select_prev(1, 7, 4);
+        assert_eq!(picker.selected(), Some(0), "selected");
+        assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
+    }
 }
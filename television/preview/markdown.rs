@@ -0,0 +1,252 @@
+use std::path::Path;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+
+const MARKDOWN_EXTENSIONS: [&str; 3] = ["md", "markdown", "mdown"];
+
+const HEADER_COLOR: Color = Color::Blue;
+const CODE_COLOR: Color = Color::Yellow;
+const BULLET: &str = "• ";
+const RULE_WIDTH: usize = 40;
+
+/// Whether the given entry name looks like a markdown file.
+pub fn is_markdown(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            MARKDOWN_EXTENSIONS
+                .iter()
+                .any(|md| ext.eq_ignore_ascii_case(md))
+        })
+}
+
+/// Render markdown with basic formatting: headers, emphasis, inline code,
+/// code blocks, lists, block quotes and horizontal rules.
+///
+/// Each line of the source gives exactly one rendered line so that line
+/// numbers (scrolling, search) keep matching the source. Documents with an
+/// unterminated code block are rendered as plain text.
+pub fn to_text(markdown: &str) -> Text<'static> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+                lines.push(Line::styled(String::new(), Style::default()));
+            } else {
+                lines.push(Line::styled(line.to_string(), code_style()));
+            }
+        } else if let Some(marker) =
+            ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m))
+        {
+            fence = Some(marker);
+            lines.push(Line::styled(String::new(), Style::default()));
+        } else {
+            lines.push(render_line(line));
+        }
+    }
+    if fence.is_some() {
+        return Text::raw(markdown.to_string());
+    }
+    Text::from(lines)
+}
+
+fn code_style() -> Style {
+    Style::default().fg(CODE_COLOR)
+}
+
+fn render_line(line: &str) -> Line<'static> {
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, rest) = line.split_at(indent_len);
+
+    // headers
+    let level = rest.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level)
+        && (rest.len() == level || rest[level..].starts_with(' '))
+    {
+        let mut style = Style::default()
+            .fg(HEADER_COLOR)
+            .add_modifier(Modifier::BOLD);
+        if level == 1 {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        return Line::from(render_inline(rest[level..].trim(), style));
+    }
+    // horizontal rules
+    let compact = rest.replace(' ', "");
+    if compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|c| compact.chars().all(|ch| ch == *c))
+    {
+        return Line::styled(
+            "─".repeat(RULE_WIDTH),
+            Style::default().add_modifier(Modifier::DIM),
+        );
+    }
+    // block quotes
+    if let Some(quote) = rest.strip_prefix('>') {
+        let style = Style::default().add_modifier(Modifier::ITALIC);
+        let mut spans = vec![Span::styled(
+            format!("{indent}│ "),
+            Style::default().add_modifier(Modifier::DIM),
+        )];
+        spans.extend(render_inline(quote.trim_start(), style));
+        return Line::from(spans);
+    }
+    // unordered lists
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| rest.strip_prefix(marker))
+    {
+        let mut spans = vec![Span::raw(format!("{indent}{BULLET}"))];
+        spans.extend(render_inline(item, Style::default()));
+        return Line::from(spans);
+    }
+    // ordered lists
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(item) = rest[digits..]
+            .strip_prefix(". ")
+            .or_else(|| rest[digits..].strip_prefix(") "))
+        {
+            let mut spans = vec![
+                Span::raw(indent.to_string()),
+                Span::styled(
+                    format!("{}. ", &rest[..digits]),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ];
+            spans.extend(render_inline(item, Style::default()));
+            return Line::from(spans);
+        }
+    }
+    let mut spans = Vec::new();
+    if !indent.is_empty() {
+        spans.push(Span::raw(indent.to_string()));
+    }
+    spans.extend(render_inline(rest, Style::default()));
+    Line::from(spans)
+}
+
+/// Render inline code spans and emphasis on top of `base`.
+///
+/// Markers without a closing counterpart, as well as underscores within
+/// words, are kept as-is.
+fn render_inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        let within_word =
+            c == '_' && previous.is_some_and(char::is_alphanumeric);
+        let styled = match c {
+            _ if within_word => None,
+            '`' => delimited(rest, "`").map(|(inner, len)| {
+                (vec![Span::styled(inner.to_string(), code_style())], len)
+            }),
+            '*' | '_' => {
+                let strong = if c == '*' { "**" } else { "__" };
+                delimited(rest, strong)
+                    .map(|(inner, len)| {
+                        let style = base.add_modifier(Modifier::BOLD);
+                        (render_inline(inner, style), len)
+                    })
+                    .or_else(|| {
+                        delimited(rest, &c.to_string()).map(|(inner, len)| {
+                            let style = base.add_modifier(Modifier::ITALIC);
+                            (render_inline(inner, style), len)
+                        })
+                    })
+            }
+            _ => None,
+        };
+        if let Some((inner, len)) = styled {
+            if !plain.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut plain), base));
+            }
+            spans.extend(inner);
+            rest = &rest[len..];
+        } else {
+            plain.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        previous = Some(c);
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// If `text` starts with a non-empty span enclosed in `marker`, return its
+/// content and the length of the whole span, markers included.
+fn delimited<'a>(text: &'a str, marker: &str) -> Option<(&'a str, usize)> {
+    let body = text.strip_prefix(marker)?;
+    let end = body.find(marker)?;
+    let inner = &body[..end];
+    if inner.is_empty() || inner.starts_with(' ') || inner.ends_with(' ') {
+        return None;
+    }
+    Some((inner, 2 * marker.len() + end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_is_markdown() {
+        assert!(is_markdown("README.md"));
+        assert!(is_markdown("docs/notes.MARKDOWN"));
+        assert!(!is_markdown("main.rs"));
+        assert!(!is_markdown("md"));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let text = to_text(
+            "# Title\nsome **bold** and `code`\n- item\n2. second\n```\nlet x = 1;\n```\nnot *closed or_snake_case_",
+        );
+        let lines: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Title",
+                "some bold and code",
+                "• item",
+                "2. second",
+                "",
+                "let x = 1;",
+                "",
+                "not *closed or_snake_case_",
+            ]
+        );
+        assert!(text.lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert_eq!(text.lines[1].spans[1].content, "bold");
+        assert!(text.lines[1].spans[1]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert_eq!(text.lines[5].style.fg, Some(CODE_COLOR));
+    }
+
+    #[test]
+    fn test_to_text_unterminated_code_block() {
+        let text = to_text("# Title\n```\ncode");
+        let lines: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert_eq!(lines, vec!["# Title", "```", "code"]);
+    }
+}
@@ -1,11 +1,17 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use crate::channels::entry::{Entry, PreviewType};
 use devicons::FileIcon;
+use lazy_static::lazy_static;
+use regex::Regex;
 
 pub mod ansi;
 pub mod cache;
+pub mod markdown;
 pub mod previewers;
+pub mod search;
+pub mod state;
 
 // previewer types
 use crate::utils::cache::RingSet;
@@ -30,6 +36,8 @@ pub enum PreviewContent {
     PlainText(Vec<String>),
     PlainTextWrapped(String),
     AnsiText(String),
+    /// Markdown source, rendered when drawn (see [`markdown::to_text`]).
+    Markdown(String),
 }
 
 impl PreviewContent {
@@ -41,12 +49,77 @@ impl PreviewContent {
             PreviewContent::PlainText(lines) => {
                 lines.len().try_into().unwrap_or(u16::MAX)
             }
-            PreviewContent::AnsiText(text) => {
+            PreviewContent::AnsiText(text)
+            | PreviewContent::Markdown(text) => {
                 text.lines().count().try_into().unwrap_or(u16::MAX)
             }
             _ => 0,
         }
     }
+
+    /// The indices of the lines containing the given pattern (see
+    /// [`search::find_matches`]).
+    pub fn find_lines(&self, pattern: &str) -> Vec<u16> {
+        self.matching_lines(pattern).collect()
+    }
+
+    /// The index of the first line containing the given pattern.
+    pub fn find_first_line(&self, pattern: &str) -> Option<u16> {
+        self.matching_lines(pattern).next()
+    }
+
+    fn matching_lines<'a>(
+        &'a self,
+        pattern: &'a str,
+    ) -> impl Iterator<Item = u16> + 'a {
+        self.searched_lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                !search::find_matches(line, pattern).is_empty()
+            })
+            .map(|(i, _)| u16::try_from(i).unwrap_or(u16::MAX))
+    }
+
+    /// The lines of the preview as displayed, without styling.
+    pub(crate) fn searched_lines(
+        &self,
+    ) -> Box<dyn Iterator<Item = Cow<str>> + '_> {
+        match self {
+            PreviewContent::SyntectHighlightedText(hl_lines) => {
+                Box::new(hl_lines.lines.iter().map(|regions| {
+                    regions
+                        .iter()
+                        .map(|(_, text)| text.as_str())
+                        .collect::<String>()
+                        .into()
+                }))
+            }
+            PreviewContent::PlainText(lines) => {
+                Box::new(lines.iter().map(|line| line.as_str().into()))
+            }
+            PreviewContent::AnsiText(text) => Box::new(
+                text.lines()
+                    .map(|line| ANSI_ESCAPE_RE.replace_all(line, "")),
+            ),
+            // markup is searched as rendered (each line of the source giving
+            // exactly one rendered line)
+            PreviewContent::Markdown(text) => Box::new(
+                markdown::to_text(text).lines.into_iter().map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                        .into()
+                }),
+            ),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+lazy_static! {
+    static ref ANSI_ESCAPE_RE: Regex =
+        Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]").unwrap();
 }
 
 pub const PREVIEW_NOT_SUPPORTED_MSG: &str =
@@ -69,6 +142,9 @@ pub struct Preview {
     /// up to which the preview holds.
     pub partial_offset: Option<usize>,
     pub total_lines: u16,
+    /// The number of lines prepended to the content (e.g. file metadata),
+    /// by which the entry's target line is shifted.
+    pub header_lines: u16,
 }
 
 impl Default for Preview {
@@ -79,6 +155,7 @@ impl Default for Preview {
             icon: None,
             partial_offset: None,
             total_lines: 0,
+            header_lines: 0,
         }
     }
 }
@@ -97,9 +174,16 @@ impl Preview {
             icon,
             partial_offset,
             total_lines,
+            header_lines: 0,
         }
     }
 
+    #[must_use]
+    pub fn with_header_lines(mut self, header_lines: u16) -> Self {
+        self.header_lines = header_lines;
+        self
+    }
+
     pub fn total_lines(&self) -> u16 {
         match &self.content {
             PreviewContent::SyntectHighlightedText(hl_lines) => {
@@ -108,7 +192,8 @@ impl Preview {
             PreviewContent::PlainText(lines) => {
                 lines.len().try_into().unwrap_or(u16::MAX)
             }
-            PreviewContent::AnsiText(text) => {
+            PreviewContent::AnsiText(text)
+            | PreviewContent::Markdown(text) => {
                 text.lines().count().try_into().unwrap_or(u16::MAX)
             }
             _ => 0,
@@ -123,6 +208,9 @@ pub struct Previewer {
     env_var: EnvVarPreviewer,
     command: CommandPreviewer,
     requests: RingSet<Entry>,
+    /// Whether markdown files are rendered by the file previewer whatever
+    /// the entry's preview type.
+    markdown: bool,
 }
 
 #[derive(Debug, Default)]
@@ -148,6 +236,21 @@ impl PreviewerConfig {
         self.env_var = config;
         self
     }
+
+    pub fn command(mut self, config: CommandPreviewerConfig) -> Self {
+        self.command = config;
+        self
+    }
+
+    pub fn markdown(mut self, markdown: bool) -> Self {
+        self.file.markdown = markdown;
+        self
+    }
+
+    pub fn show_meta(mut self, show_meta: bool) -> Self {
+        self.command = self.command.with_show_meta(show_meta);
+        self
+    }
 }
 
 const REQUEST_STACK_SIZE: usize = 20;
@@ -156,6 +259,7 @@ impl Previewer {
     pub fn new(config: Option<PreviewerConfig>) -> Self {
         let config = config.unwrap_or_default();
         Previewer {
+            markdown: config.file.markdown,
             basic: BasicPreviewer::new(Some(config.basic)),
             file: FilePreviewer::new(Some(config.file)),
             env_var: EnvVarPreviewer::new(Some(config.env_var)),
@@ -164,7 +268,21 @@ impl Previewer {
         }
     }
 
+    /// Whether the entry is previewed as rendered markdown.
+    fn renders_markdown(&self, entry: &Entry) -> bool {
+        self.markdown
+            && matches!(
+                entry.preview_type,
+                PreviewType::Files | PreviewType::Command(_)
+            )
+            && markdown::is_markdown(&entry.name)
+            && std::path::Path::new(&entry.name).is_file()
+    }
+
     fn dispatch_request(&mut self, entry: &Entry) -> Option<Arc<Preview>> {
+        if self.renders_markdown(entry) {
+            return self.file.preview(entry);
+        }
         match &entry.preview_type {
             PreviewType::Basic => Some(self.basic.preview(entry)),
             PreviewType::EnvVar => Some(self.env_var.preview(entry)),
@@ -175,6 +293,9 @@ impl Previewer {
     }
 
     fn cached(&self, entry: &Entry) -> Option<Arc<Preview>> {
+        if self.renders_markdown(entry) {
+            return self.file.cached(entry);
+        }
         match &entry.preview_type {
             PreviewType::Files => self.file.cached(entry),
             PreviewType::Command(_) => self.command.cached(entry),
@@ -199,9 +320,33 @@ impl Previewer {
         None
     }
 
+    /// Like `preview`, but without starting any background work: only
+    /// previews that are cheap to compute or already cached are returned
+    /// (falling back to the most recent cached one).
+    pub fn cached_preview(&mut self, entry: &Entry) -> Option<Arc<Preview>> {
+        if matches!(
+            entry.preview_type,
+            PreviewType::Basic | PreviewType::EnvVar
+        ) {
+            return self.dispatch_request(entry);
+        }
+        self.cached(entry).or_else(|| {
+            self.requests
+                .back_to_front()
+                .find_map(|request| self.cached(&request))
+        })
+    }
+
+    /// Set the query made available to preview commands.
+    pub fn set_query(&mut self, query: &str) {
+        self.command.set_query(query);
+    }
+
     pub fn set_config(&mut self, config: PreviewerConfig) {
+        self.markdown = config.file.markdown;
         self.basic = BasicPreviewer::new(Some(config.basic));
         self.file = FilePreviewer::new(Some(config.file));
         self.env_var = EnvVarPreviewer::new(Some(config.env_var));
+        self.command.set_config(config.command);
     }
 }
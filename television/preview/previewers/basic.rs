@@ -25,6 +25,7 @@ impl BasicPreviewer {
             icon: entry.icon,
             partial_offset: None,
             total_lines: 1,
+            header_lines: 0,
         })
     }
 }
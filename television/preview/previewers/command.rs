@@ -1,11 +1,19 @@
-use crate::channels::entry::{Entry, PreviewCommand};
+use crate::channels::entry::{
+    Entry, PreviewCommand, PreviewType, QUERY_ENV_VAR,
+};
 use crate::preview::cache::PreviewCache;
 use crate::preview::{Preview, PreviewContent};
 use crate::utils::command::shell_command;
+use crate::utils::env_file::env_var;
+use crate::utils::files::file_metadata_header;
+use color_eyre::eyre::{bail, Result};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use regex::Regex;
 use rustc_hash::FxHashSet;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::Stdio;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
 use tracing::debug;
@@ -17,20 +25,40 @@ pub struct CommandPreviewer {
     config: CommandPreviewerConfig,
     concurrent_preview_tasks: Arc<AtomicU8>,
     in_flight_previews: Arc<Mutex<FxHashSet<String>>>,
+    /// The current query, passed to preview commands as `TV_QUERY`.
+    query: String,
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct CommandPreviewerConfig {
     delimiter: String,
+    /// The maximum number of preview commands running at the same time.
+    max_concurrent_tasks: u8,
+    /// The maximum number of bytes read from a preview command's output (0
+    /// means no limit).
+    max_bytes: usize,
+    /// Whether to prepend the metadata of previewed files to the output.
+    show_meta: bool,
 }
 
 const DEFAULT_DELIMITER: &str = " ";
 
+pub const DEFAULT_MAX_CONCURRENT_PREVIEW_TASKS: u8 = 3;
+
+pub const DEFAULT_MAX_PREVIEW_BYTES: usize = 10 * 1024 * 1024;
+
+/// The prefix of offset templates searching the preview for a string rather
+/// than evaluating to a line number.
+const SEARCH_OFFSET_PREFIX: char = '/';
+
 impl Default for CommandPreviewerConfig {
     fn default() -> Self {
         CommandPreviewerConfig {
             delimiter: String::from(DEFAULT_DELIMITER),
+            max_concurrent_tasks: DEFAULT_MAX_CONCURRENT_PREVIEW_TASKS,
+            max_bytes: DEFAULT_MAX_PREVIEW_BYTES,
+            show_meta: false,
         }
     }
 }
@@ -39,11 +67,31 @@ impl CommandPreviewerConfig {
     pub fn new(delimiter: &str) -> Self {
         CommandPreviewerConfig {
             delimiter: String::from(delimiter),
+            ..Default::default()
         }
     }
-}
 
-const MAX_CONCURRENT_PREVIEW_TASKS: u8 = 3;
+    /// Set the maximum number of preview commands running at the same time
+    /// (at least 1).
+    pub fn with_max_concurrent_tasks(mut self, max: u8) -> Self {
+        self.max_concurrent_tasks = max.max(1);
+        self
+    }
+
+    /// Set the maximum number of bytes read from a preview command's output
+    /// (0 means no limit).
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Prepend the size, permissions and modification time of previewed
+    /// files to the output of preview commands.
+    pub fn with_show_meta(mut self, show_meta: bool) -> Self {
+        self.show_meta = show_meta;
+        self
+    }
+}
 
 impl CommandPreviewer {
     pub fn new(config: Option<CommandPreviewerConfig>) -> Self {
@@ -53,11 +101,36 @@ impl CommandPreviewer {
             config,
             concurrent_preview_tasks: Arc::new(AtomicU8::new(0)),
             in_flight_previews: Arc::new(Mutex::new(FxHashSet::default())),
+            query: String::new(),
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        query.clone_into(&mut self.query);
+    }
+
+    /// Replace the configuration, dropping the previews computed with the
+    /// previous one.
+    pub fn set_config(&mut self, config: CommandPreviewerConfig) {
+        self.config = config;
+        *self.cache.lock() = PreviewCache::default();
+    }
+
+    /// The key under which the preview of `entry` is cached.
+    ///
+    /// Previews of commands using the query are cached per query.
+    fn cache_key(&self, entry: &Entry) -> String {
+        match &entry.preview_type {
+            PreviewType::Command(command) if command.uses_query() => {
+                // entries are single lines so this can't be ambiguous
+                format!("{}\n{}", entry.name, self.query)
+            }
+            _ => entry.name.clone(),
         }
     }
 
     pub fn cached(&self, entry: &Entry) -> Option<Arc<Preview>> {
-        self.cache.lock().get(&entry.name)
+        self.cache.lock().get(&self.cache_key(entry))
     }
 
     pub fn preview(
@@ -80,15 +153,16 @@ impl CommandPreviewer {
         entry: &Entry,
         command: &PreviewCommand,
     ) {
-        if self.in_flight_previews.lock().contains(&entry.name) {
+        let cache_key = self.cache_key(entry);
+        if self.in_flight_previews.lock().contains(&cache_key) {
             debug!("Preview already in flight for {:?}", entry.name);
             return;
         }
 
         if self.concurrent_preview_tasks.load(Ordering::Relaxed)
-            < MAX_CONCURRENT_PREVIEW_TASKS
+            < self.config.max_concurrent_tasks
         {
-            self.in_flight_previews.lock().insert(entry.name.clone());
+            self.in_flight_previews.lock().insert(cache_key.clone());
             self.concurrent_preview_tasks
                 .fetch_add(1, Ordering::Relaxed);
             let cache = self.cache.clone();
@@ -96,10 +170,17 @@ impl CommandPreviewer {
             let concurrent_tasks = self.concurrent_preview_tasks.clone();
             let command = command.clone();
             let in_flight_previews = self.in_flight_previews.clone();
+            let query = self.query.clone();
+            let max_bytes = self.config.max_bytes;
+            let show_meta = self.config.show_meta;
             tokio::spawn(async move {
                 try_preview(
                     &command,
                     &entry_c,
+                    &query,
+                    max_bytes,
+                    show_meta,
+                    &cache_key,
                     &cache,
                     &concurrent_tasks,
                     &in_flight_previews,
@@ -117,10 +198,94 @@ impl CommandPreviewer {
 lazy_static! {
     static ref COMMAND_PLACEHOLDER_REGEX: Regex =
         Regex::new(r"\{(\d+)\}").unwrap();
+    static ref ENV_VAR_REGEX: Regex =
+        Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+    /// Placeholders padded with whitespace (`{ 1 }`) or missing their
+    /// closing brace (`{1`), which are never substituted.
+    static ref MALFORMED_PLACEHOLDER_REGEX: Regex =
+        Regex::new(r#"\{(?:\s+\d+\s*|\d+\s+)\}|\{\d+(?:$|[\s'"])"#).unwrap();
+}
+
+/// Check that the placeholders of a preview command template are
+/// well-formed.
+pub fn validate_command(template: &str) -> Result<()> {
+    if let Some(m) = MALFORMED_PLACEHOLDER_REGEX.find(template) {
+        bail!(
+            "malformed placeholder `{}` (expected `{{}}` or `{{N}}`)",
+            m.as_str().trim_end()
+        );
+    }
+    Ok(())
+}
+
+/// Check that an offset template (see [`resolve_position`]) evaluates to a
+/// line number, optionally followed by a column, or is a search template
+/// (see [`resolve_search`]).
+pub fn validate_offset(template: &str) -> Result<()> {
+    validate_command(template)?;
+    if let Some(needle) = template.strip_prefix(SEARCH_OFFSET_PREFIX) {
+        if needle.trim().is_empty() {
+            bail!("expected a string to search for after `/`");
+        }
+        return Ok(());
+    }
+    let sample = COMMAND_PLACEHOLDER_REGEX
+        .replace_all(&template.replace("{}", "1"), "1")
+        .to_string();
+    let is_number = |s: &str| s.trim().parse::<u16>().is_ok();
+    let valid = match sample.trim().split_once(':') {
+        Some((line, column)) => is_number(line) && is_number(column),
+        None => is_number(&sample),
+    };
+    if !valid {
+        bail!("expected a line number or `line:column` made of placeholders (e.g. `{{1}}` or `{{1}}:{{2}}`), or `/` followed by a string to search for");
+    }
+    Ok(())
+}
+
+/// Expand `${VAR}` references in the template using the env file and the
+/// process environment.
+///
+/// Undefined variables expand to an empty string.
+fn expand_env_vars(template: &str) -> String {
+    ENV_VAR_REGEX
+        .replace_all(template, |caps: &regex::Captures| {
+            let name = &caps[1];
+            env_var(name).unwrap_or_else(|| {
+                debug!("Undefined environment variable: {:?}", name);
+                String::new()
+            })
+        })
+        .to_string()
+}
+
+/// Split the entry's name into the fields referenced by `{N}` placeholders.
+///
+/// Paths containing the delimiter (e.g. `notes:todo.md:12:text` split on `:`)
+/// are kept whole: if the first field isn't an existing path but the first
+/// few fields joined back together are, they make up the first field.
+fn entry_fields<'a>(name: &'a str, delimiter: &str) -> Vec<&'a str> {
+    let mut parts = name.split(delimiter).collect::<Vec<&str>>();
+    if delimiter.is_empty() || Path::new(parts[0]).exists() {
+        return parts;
+    }
+    let mut end = parts[0].len();
+    for count in 2..parts.len() {
+        end += delimiter.len() + parts[count - 1].len();
+        if Path::new(&name[..end]).exists() {
+            parts.splice(..count, [&name[..end]]);
+            break;
+        }
+    }
+    parts
 }
 
 /// Format the command with the entry name and provided placeholders
 ///
+/// Environment variables referenced as `${VAR}` in the command are expanded
+/// before the placeholders, so that entry names are never subject to
+/// expansion.
+///
 /// # Example
 /// ```
 /// use television::channels::entry::{PreviewCommand, PreviewType, Entry};
@@ -129,6 +294,10 @@ lazy_static! {
 /// let command = PreviewCommand {
 ///     command: "something {} {2} {0}".to_string(),
 ///     delimiter: ":".to_string(),
+///     dir_command: None,
+///     offset: None,
+///     fallback_command: None,
+///     multi_command: None,
 /// };
 /// let entry = Entry::new("a:given:entry:to:preview".to_string(), PreviewType::Command(command.clone()));
 /// let formatted_command = format_command(&command, &entry);
@@ -136,11 +305,12 @@ lazy_static! {
 /// assert_eq!(formatted_command, "something 'a:given:entry:to:preview' 'entry' 'a'");
 /// ```
 pub fn format_command(command: &PreviewCommand, entry: &Entry) -> String {
-    let parts = entry.name.split(&command.delimiter).collect::<Vec<&str>>();
+    let parts = entry_fields(&entry.name, &command.delimiter);
     debug!("Parts: {:?}", parts);
 
-    let mut formatted_command = command
-        .command
+    // expand environment variables before inserting the entry so that
+    // `${VAR}` sequences appearing in entry names are left untouched
+    let mut formatted_command = expand_env_vars(&command.command)
         .replace("{}", format!("'{}'", entry.name).as_str());
 
     formatted_command = COMMAND_PLACEHOLDER_REGEX
@@ -154,47 +324,325 @@ pub fn format_command(command: &PreviewCommand, entry: &Entry) -> String {
     formatted_command
 }
 
-pub fn try_preview(
+/// The separator used to join the names of entries previewed together.
+///
+/// Entries are single lines so this can't appear in their names.
+const MULTI_ENTRY_SEPARATOR: &str = "\n";
+
+/// Build an entry previewing the given entries at once with the command's
+/// `multi_command`.
+///
+/// In `multi_command`, `{N}` is replaced with the N-th entry (in
+/// lexicographic order) and `{}` with all of them. Returns `None` if there's
+/// no such command, if less than two entries are given, or if the command
+/// references a specific number of entries that doesn't match.
+pub fn multi_preview_entry(
+    command: &PreviewCommand,
+    entries: &[&Entry],
+) -> Option<Entry> {
+    let template = command.multi_command.as_ref()?;
+    if entries.len() < 2 {
+        return None;
+    }
+    let expected = COMMAND_PLACEHOLDER_REGEX
+        .captures_iter(template)
+        .filter_map(|caps| caps[1].parse::<usize>().ok())
+        .max()
+        .map(|index| index + 1);
+    if expected.is_some_and(|expected| expected != entries.len()) {
+        debug!(
+            "Multi preview command expects {:?} entries, got {}",
+            expected,
+            entries.len()
+        );
+        return None;
+    }
+    let mut names = entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    let all_placeholders = (0..names.len())
+        .map(|index| format!("{{{index}}}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let command = PreviewCommand::new(
+        &template.replace("{}", &all_placeholders),
+        MULTI_ENTRY_SEPARATOR,
+    );
+    Some(
+        Entry::new(
+            names.join(MULTI_ENTRY_SEPARATOR),
+            PreviewType::Command(command),
+        )
+        .with_display(names.join(", ")),
+    )
+}
+
+/// Evaluate the command's offset template against the given entry.
+///
+/// `{}` is replaced with the entry's name and `{N}` with its N-th field (using
+/// the command's delimiter). Returns `None` if there's no offset template or if
+/// it doesn't evaluate to a line number.
+///
+/// # Example
+/// ```
+/// use television::channels::entry::{PreviewCommand, PreviewType, Entry};
+/// use television::preview::previewers::command::resolve_offset;
+///
+/// let command = PreviewCommand::new("bat {0}", ":").with_offset("{1}");
+/// let entry = Entry::new("src/main.rs:42:fn main()".to_string(), PreviewType::Command(command.clone()));
+///
+/// assert_eq!(resolve_offset(&command, &entry), Some(42));
+/// ```
+pub fn resolve_offset(command: &PreviewCommand, entry: &Entry) -> Option<u16> {
+    resolve_position(command, entry).map(|(line, _)| line)
+}
+
+/// Evaluate the command's offset template against the given entry into a
+/// line and an optional column.
+///
+/// This works like [`resolve_offset`] except that the template may also
+/// evaluate to `line:column` (e.g. `{1}:{2}` for `file:line:column:text`
+/// entries), in which case the column (starting at 1) is returned as well.
+/// Search templates (see [`resolve_search`]) don't yield a position.
+pub fn resolve_position(
+    command: &PreviewCommand,
+    entry: &Entry,
+) -> Option<(u16, Option<u16>)> {
+    let template = command.offset.as_ref()?;
+    if template.starts_with(SEARCH_OFFSET_PREFIX) {
+        return None;
+    }
+    let offset = expand_offset(template, command, entry)?;
+    match offset.trim().split_once(':') {
+        Some((line, column)) => {
+            Some((line.trim().parse().ok()?, column.trim().parse().ok()))
+        }
+        None => Some((offset.trim().parse().ok()?, None)),
+    }
+}
+
+/// Evaluate the command's offset template against the given entry into a
+/// string to search for in the preview.
+///
+/// Offset templates starting with `/` (e.g. `/## {1}`) don't evaluate to a
+/// line number: the preview is instead scrolled to the first line containing
+/// the rest of the template once expanded. Returns `None` if the template
+/// isn't a search template or if it evaluates to an empty string.
+///
+/// # Example
+/// ```
+/// use television::channels::entry::{PreviewCommand, PreviewType, Entry};
+/// use television::preview::previewers::command::resolve_search;
+///
+/// let command = PreviewCommand::new("man {0}", ":").with_offset("/{1}");
+/// let entry = Entry::new("tar:EXAMPLES".to_string(), PreviewType::Command(command.clone()));
+///
+/// assert_eq!(resolve_search(&command, &entry), Some("EXAMPLES".to_string()));
+/// ```
+pub fn resolve_search(
     command: &PreviewCommand,
     entry: &Entry,
+) -> Option<String> {
+    let template = command
+        .offset
+        .as_ref()?
+        .strip_prefix(SEARCH_OFFSET_PREFIX)?;
+    expand_offset(template, command, entry).filter(|needle| !needle.is_empty())
+}
+
+/// Replace the placeholders of an offset template with the entry's fields,
+/// or return `None` if a field is missing.
+fn expand_offset(
+    template: &str,
+    command: &PreviewCommand,
+    entry: &Entry,
+) -> Option<String> {
+    let parts = entry_fields(&entry.name, &command.delimiter);
+    let mut valid = true;
+    let offset = COMMAND_PLACEHOLDER_REGEX
+        .replace_all(
+            &template.replace("{}", &entry.name),
+            |caps: &regex::Captures| {
+                let index =
+                    caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
+                parts.get(index).copied().unwrap_or_else(|| {
+                    valid = false;
+                    ""
+                })
+            },
+        )
+        .to_string();
+    valid.then_some(offset)
+}
+
+/// Resolve the preview command to use for the given entry.
+///
+/// If a `dir_command` is set and the entry points to an existing directory, it
+/// is used in place of the main command. Otherwise the command is returned as
+/// is.
+pub fn resolve_command(
+    command: &PreviewCommand,
+    entry: &Entry,
+) -> PreviewCommand {
+    match &command.dir_command {
+        Some(dir_command) if Path::new(&entry.name).is_dir() => {
+            PreviewCommand::new(dir_command, &command.delimiter)
+        }
+        _ => command.clone(),
+    }
+}
+
+/// The notice appended to previews whose output was cut off.
+const TRUNCATION_NOTICE: &str = "[truncated]";
+
+/// Read at most `max_bytes` bytes from `reader` (everything if `max_bytes` is
+/// 0), returning whether there was more to read.
+fn read_capped(
+    mut reader: impl Read,
+    max_bytes: usize,
+) -> io::Result<(Vec<u8>, bool)> {
+    let mut bytes = Vec::new();
+    if max_bytes == 0 {
+        reader.read_to_end(&mut bytes)?;
+        return Ok((bytes, false));
+    }
+    reader
+        .by_ref()
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut bytes)?;
+    let truncated = bytes.len() > max_bytes;
+    bytes.truncate(max_bytes);
+    Ok((bytes, truncated))
+}
+
+/// The output of a preview command, cut off after a maximum number of bytes.
+struct PreviewOutput {
+    success: bool,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    truncated: bool,
+}
+
+/// Run the preview command, reading at most `max_bytes` bytes of its stdout
+/// and stderr (0 means no limit).
+///
+/// The command is killed once its stdout exceeds the limit, in which case it
+/// is considered successful.
+fn run_preview_command(
+    command: &str,
+    query: &str,
+    max_bytes: usize,
+) -> io::Result<PreviewOutput> {
+    let mut child = shell_command()
+        .arg(command)
+        .env(QUERY_ENV_VAR, query)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stderr = child.stderr.take().expect("stderr is piped");
+    // stderr is read concurrently so that the command can't block on it
+    // (once it exceeds the limit, the pipe is closed and further writes fail)
+    let stderr_reader =
+        std::thread::spawn(move || read_capped(stderr, max_bytes));
+    let stdout =
+        read_capped(child.stdout.take().expect("stdout is piped"), max_bytes);
+    if matches!(stdout, Ok((_, true))) {
+        let _ = child.kill();
+    }
+    let status = child.wait()?;
+    let (stdout, truncated) = stdout?;
+    let (stderr, _) = stderr_reader
+        .join()
+        .unwrap_or_else(|_| Ok((Vec::new(), false)))?;
+    Ok(PreviewOutput {
+        success: truncated || status.success(),
+        stdout,
+        stderr,
+        truncated,
+    })
+}
+
+/// Run the preview command for `entry` and cache its output under
+/// `cache_key`.
+///
+/// The current query is available to the command as `TV_QUERY`. At most
+/// `max_bytes` bytes of the output are kept (0 means no limit), a notice
+/// being appended if the output was cut off. With `show_meta`, the metadata
+/// of the file the entry points at (if any) is prepended to the output.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub fn try_preview(
+    command_spec: &PreviewCommand,
+    entry: &Entry,
+    query: &str,
+    max_bytes: usize,
+    show_meta: bool,
+    cache_key: &str,
     cache: &Arc<Mutex<PreviewCache>>,
     concurrent_tasks: &Arc<AtomicU8>,
     in_flight_previews: &Arc<Mutex<FxHashSet<String>>>,
 ) {
     debug!("Computing preview for {:?}", entry.name);
-    let command = format_command(command, entry);
+    let command = format_command(&resolve_command(command_spec, entry), entry);
     debug!("Formatted preview command: {:?}", command);
 
-    let child = shell_command()
-        .arg(&command)
-        .output()
-        .expect("failed to execute process");
+    let run = |command: &str| run_preview_command(command, query, max_bytes);
+    let output = run(&command).and_then(|output| {
+        match &command_spec.fallback_command {
+            Some(fallback_command) if !output.success => {
+                let fallback_command = format_command(
+                    &PreviewCommand::new(
+                        fallback_command,
+                        &command_spec.delimiter,
+                    ),
+                    entry,
+                );
+                debug!(
+                    "Preview command failed, running {:?}",
+                    fallback_command
+                );
+                run(&fallback_command)
+            }
+            _ => Ok(output),
+        }
+    });
 
-    if child.status.success() {
-        let content = String::from_utf8_lossy(&child.stdout);
-        let preview = Arc::new(Preview::new(
-            entry.name.clone(),
-            PreviewContent::AnsiText(content.to_string()),
-            None,
-            None,
-            u16::try_from(content.lines().count()).unwrap_or(u16::MAX),
-        ));
-
-        cache.lock().insert(entry.name.clone(), &preview);
-    } else {
-        let content = String::from_utf8_lossy(&child.stderr);
-        let preview = Arc::new(Preview::new(
-            entry.name.clone(),
-            PreviewContent::AnsiText(content.to_string()),
+    let content = match output {
+        Ok(output) if output.success => {
+            let mut content =
+                String::from_utf8_lossy(&output.stdout).to_string();
+            if output.truncated {
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str(TRUNCATION_NOTICE);
+            }
+            content
+        }
+        Ok(output) => String::from_utf8_lossy(&output.stderr).to_string(),
+        Err(e) => format!("Failed to run preview command {command:?}: {e}"),
+    };
+    let header = show_meta
+        .then(|| file_metadata_header(Path::new(&entry.name)))
+        .flatten();
+    let header_lines = header.as_ref().map_or(0, |h| h.lines().count());
+    let content = header.unwrap_or_default() + &content;
+    let preview = Arc::new(
+        Preview::new(
+            entry.display_name().to_string(),
+            PreviewContent::AnsiText(content.clone()),
             None,
             None,
             u16::try_from(content.lines().count()).unwrap_or(u16::MAX),
-        ));
-        cache.lock().insert(entry.name.clone(), &preview);
-    }
+        )
+        .with_header_lines(u16::try_from(header_lines).unwrap_or(u16::MAX)),
+    );
+    cache.lock().insert(cache_key.to_string(), &preview);
 
     concurrent_tasks.fetch_sub(1, Ordering::Relaxed);
-    in_flight_previews.lock().remove(&entry.name);
+    in_flight_previews.lock().remove(cache_key);
 }
 
 #[cfg(test)]
@@ -207,6 +655,10 @@ mod tests {
         let command = PreviewCommand {
             command: "something {} {2} {0}".to_string(),
             delimiter: ":".to_string(),
+            dir_command: None,
+            offset: None,
+            fallback_command: None,
+            multi_command: None,
         };
         let entry = Entry::new(
             "an:entry:to:preview".to_string(),
@@ -225,6 +677,10 @@ mod tests {
         let command = PreviewCommand {
             command: "something".to_string(),
             delimiter: ":".to_string(),
+            dir_command: None,
+            offset: None,
+            fallback_command: None,
+            multi_command: None,
         };
         let entry = Entry::new(
             "an:entry:to:preview".to_string(),
@@ -240,6 +696,10 @@ mod tests {
         let command = PreviewCommand {
             command: "something {}".to_string(),
             delimiter: ":".to_string(),
+            dir_command: None,
+            offset: None,
+            fallback_command: None,
+            multi_command: None,
         };
         let entry = Entry::new(
             "an:entry:to:preview".to_string(),
@@ -255,6 +715,10 @@ mod tests {
         let command = PreviewCommand {
             command: "something {0} -t {2}".to_string(),
             delimiter: ":".to_string(),
+            dir_command: None,
+            offset: None,
+            fallback_command: None,
+            multi_command: None,
         };
         let entry = Entry::new(
             "an:entry:to:preview".to_string(),
@@ -264,4 +728,215 @@ mod tests {
 
         assert_eq!(formatted_command, "something 'an' -t 'to'");
     }
+
+    #[test]
+    fn test_format_command_with_env_vars() {
+        std::env::set_var("TV_TEST_PAGER", "less -R");
+        let command = PreviewCommand::new(
+            "${TV_TEST_PAGER} {} ${TV_TEST_UNDEFINED_VAR}",
+            ":",
+        );
+        let entry = Entry::new(
+            "an:entry".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+
+        assert_eq!(format_command(&command, &entry), "less -R 'an:entry' ");
+    }
+
+    #[test]
+    fn test_multi_preview_entry() {
+        let command = PreviewCommand::new("cat {0}", " ")
+            .with_multi_command("diff {1} {0}");
+        let a = Entry::new(
+            "a.txt".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        let b = Entry::new(
+            "b.txt".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        let c = Entry::new(
+            "c.txt".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+
+        let entry = multi_preview_entry(&command, &[&b, &a]).unwrap();
+        assert_eq!(entry.display_name(), "a.txt, b.txt");
+        let PreviewType::Command(multi_command) = &entry.preview_type else {
+            panic!("expected a command preview");
+        };
+        assert_eq!(
+            format_command(multi_command, &entry),
+            "diff 'b.txt' 'a.txt'"
+        );
+        // the command expects exactly two entries
+        assert!(multi_preview_entry(&command, &[&a, &b, &c]).is_none());
+        assert!(multi_preview_entry(&command, &[&a]).is_none());
+
+        let command = command.with_multi_command("cat {}");
+        let entry = multi_preview_entry(&command, &[&c, &a, &b]).unwrap();
+        let PreviewType::Command(multi_command) = &entry.preview_type else {
+            panic!("expected a command preview");
+        };
+        assert_eq!(
+            format_command(multi_command, &entry),
+            "cat 'a.txt' 'b.txt' 'c.txt'"
+        );
+    }
+
+    #[test]
+    fn test_resolve_offset() {
+        let command = PreviewCommand::new("bat {0}", ":").with_offset("{1}");
+        let entry = Entry::new(
+            "src/main.rs:42:let a = 1;".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_offset(&command, &entry), Some(42));
+
+        // missing or non numeric fields don't yield an offset
+        let entry = Entry::new(
+            "src/main.rs".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_offset(&command, &entry), None);
+        let entry = Entry::new(
+            "src/main.rs:main:1".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_offset(&command, &entry), None);
+
+        let command = PreviewCommand::new("bat {0}", ":");
+        assert_eq!(resolve_offset(&command, &entry), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_offset_path_with_delimiter() {
+        let path = std::env::temp_dir()
+            .join(format!("tv-test-notes:{}.md", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        let command = PreviewCommand::new("bat {0}", ":").with_offset("{1}");
+        let entry = Entry::new(
+            format!("{}:12:see: below", path.display()),
+            PreviewType::Command(command.clone()),
+        );
+
+        assert_eq!(resolve_offset(&command, &entry), Some(12));
+        assert_eq!(
+            format_command(&command, &entry),
+            format!("bat '{}'", path.display())
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_position() {
+        let command =
+            PreviewCommand::new("bat {0}", ":").with_offset("{1}:{2}");
+        let entry = Entry::new(
+            "src/main.rs:42:7:fn main()".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_position(&command, &entry), Some((42, Some(7))));
+        assert_eq!(resolve_offset(&command, &entry), Some(42));
+
+        // a non numeric column is ignored
+        let entry = Entry::new(
+            "src/main.rs:42:fn main()".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_position(&command, &entry), Some((42, None)));
+    }
+
+    #[test]
+    fn test_resolve_search() {
+        let command =
+            PreviewCommand::new("cat {0}", ":").with_offset("/## {1}");
+        let entry = Entry::new(
+            "README.md:Installation".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(
+            resolve_search(&command, &entry),
+            Some("## Installation".to_string())
+        );
+        // search templates don't yield a line number
+        assert_eq!(resolve_position(&command, &entry), None);
+
+        // numeric templates don't yield a search string
+        let command = PreviewCommand::new("cat {0}", ":").with_offset("{1}");
+        let entry = Entry::new(
+            "README.md:42".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+        assert_eq!(resolve_search(&command, &entry), None);
+        assert_eq!(resolve_offset(&command, &entry), Some(42));
+
+        // missing fields don't yield a search string
+        let command = PreviewCommand::new("cat {0}", ":").with_offset("/{2}");
+        assert_eq!(resolve_search(&command, &entry), None);
+    }
+
+    #[test]
+    fn test_resolve_command_uses_dir_command_for_directories() {
+        let command =
+            PreviewCommand::new("bat {}", " ").with_dir_command("ls -la {}");
+        let entry =
+            Entry::new(".".to_string(), PreviewType::Command(command.clone()));
+
+        assert_eq!(
+            resolve_command(&command, &entry),
+            PreviewCommand::new("ls -la {}", " ")
+        );
+    }
+
+    #[test]
+    fn test_resolve_command_falls_back_to_main_command() {
+        let command =
+            PreviewCommand::new("bat {}", " ").with_dir_command("ls -la {}");
+        let entry = Entry::new(
+            "an:entry:that:is:not:a:path".to_string(),
+            PreviewType::Command(command.clone()),
+        );
+
+        assert_eq!(resolve_command(&command, &entry), command);
+
+        let command = PreviewCommand::new("bat {}", " ");
+        let entry =
+            Entry::new(".".to_string(), PreviewType::Command(command.clone()));
+
+        assert_eq!(resolve_command(&command, &entry), command);
+    }
+
+    #[test]
+    fn test_validate_command() {
+        assert!(validate_command("bat -n {} --highlight-line {1}").is_ok());
+        assert!(validate_command("awk '{print $1}' {0}").is_ok());
+        assert!(validate_command("echo {1..3} ${HOME}").is_ok());
+        assert!(validate_command("bat { 1 }").is_err());
+        assert!(validate_command("bat {1").is_err());
+        assert!(validate_command("bat '{0' {1}").is_err());
+    }
+
+    #[test]
+    fn test_validate_offset() {
+        assert!(validate_offset("{1}").is_ok());
+        assert!(validate_offset("{1}:{2}").is_ok());
+        assert!(validate_offset("{}").is_ok());
+        assert!(validate_offset("{1}x").is_err());
+        assert!(validate_offset("line {1}").is_err());
+        assert!(validate_offset("{1").is_err());
+        assert!(validate_offset("/## {1}").is_ok());
+        assert!(validate_offset("/").is_err());
+        assert!(validate_offset("/{1").is_err());
+    }
+
+    #[test]
+    fn test_read_capped() {
+        let input = "0123456789".as_bytes();
+        assert_eq!(read_capped(input, 0).unwrap(), (input.to_vec(), false));
+        assert_eq!(read_capped(input, 10).unwrap(), (input.to_vec(), false));
+        assert_eq!(read_capped(input, 4).unwrap(), (b"0123".to_vec(), true));
+    }
 }
@@ -42,6 +42,7 @@ impl EnvVarPreviewer {
             icon: entry.icon,
             partial_offset: None,
             total_lines,
+            header_lines: 0,
         });
         self.cache.insert(entry.clone(), preview.clone());
         preview
@@ -16,7 +16,7 @@ use tracing::{debug, warn};
 
 use crate::channels::entry;
 use crate::preview::cache::PreviewCache;
-use crate::preview::{previewers::meta, Preview, PreviewContent};
+use crate::preview::{markdown, previewers::meta, Preview, PreviewContent};
 use crate::utils::{
     files::FileType,
     strings::preprocess_line,
@@ -28,6 +28,7 @@ pub struct FilePreviewer {
     cache: Arc<Mutex<PreviewCache>>,
     pub syntax_set: Arc<SyntaxSet>,
     pub syntax_theme: Arc<Theme>,
+    markdown: bool,
     concurrent_preview_tasks: Arc<AtomicU8>,
     in_flight_previews: Arc<Mutex<FxHashSet<String>>>,
 }
@@ -35,11 +36,17 @@ pub struct FilePreviewer {
 #[derive(Debug, Clone, Default)]
 pub struct FilePreviewerConfig {
     pub theme: String,
+    /// Whether markdown files are previewed as rendered markdown rather
+    /// than highlighted source.
+    pub markdown: bool,
 }
 
 impl FilePreviewerConfig {
     pub fn new(theme: String) -> Self {
-        FilePreviewerConfig { theme }
+        FilePreviewerConfig {
+            theme,
+            markdown: false,
+        }
     }
 }
 
@@ -52,6 +59,7 @@ impl FilePreviewer {
         let hl_assets = load_highlighting_assets();
         let syntax_set = hl_assets.get_syntax_set().unwrap().clone();
 
+        let markdown = config.as_ref().is_some_and(|c| c.markdown);
         let theme_name = match std::env::var(BAT_THEME_ENV_VAR) {
             Ok(t) => t,
             Err(_) => match config {
@@ -67,6 +75,7 @@ impl FilePreviewer {
             cache: Arc::new(Mutex::new(PreviewCache::default())),
             syntax_set: Arc::new(syntax_set),
             syntax_theme: Arc::new(theme),
+            markdown,
             concurrent_preview_tasks: Arc::new(AtomicU8::new(0)),
             in_flight_previews: Arc::new(Mutex::new(HashSet::with_hasher(
                 FxBuildHasher,
@@ -117,6 +126,14 @@ impl FilePreviewer {
             let syntax_theme = self.syntax_theme.clone();
             let concurrent_tasks = self.concurrent_preview_tasks.clone();
             let in_flight_previews = self.in_flight_previews.clone();
+            if self.markdown && markdown::is_markdown(&entry.name) {
+                tokio::spawn(async move {
+                    markdown_preview(&entry_c, &cache);
+                    concurrent_tasks.fetch_sub(1, Ordering::Relaxed);
+                    in_flight_previews.lock().remove(&entry_c.name);
+                });
+                return;
+            }
             tokio::spawn(async move {
                 try_preview(
                     &entry_c,
@@ -245,6 +262,46 @@ pub fn try_preview(
     in_flight_previews.lock().remove(&entry.name);
 }
 
+/// Preview a markdown file as rendered markdown (see [`markdown::to_text`]).
+///
+/// Only the first chunk of large files is previewed.
+fn markdown_preview(entry: &entry::Entry, cache: &Arc<Mutex<PreviewCache>>) {
+    debug!("Computing markdown preview for {:?}", entry.name);
+    let preview = match File::open(&entry.name)
+        .map_err(|e| format!("{e:?}"))
+        .map(|file| read_into_lines_capped(file, PARTIAL_BUFREAD_SIZE))
+    {
+        Ok(ReadResult::Full(lines)) => markdown_from_lines(entry, &lines),
+        Ok(ReadResult::Partial(p)) => markdown_from_lines(entry, &p.lines),
+        Ok(ReadResult::Error(e)) | Err(e) => {
+            warn!("Error reading file: {:?}", e);
+            meta::not_supported(&entry.name)
+        }
+    };
+    cache.lock().insert(entry.name.clone(), &preview);
+}
+
+fn markdown_from_lines(
+    entry: &entry::Entry,
+    lines: &[String],
+) -> Arc<Preview> {
+    let content = PreviewContent::Markdown(
+        lines
+            .iter()
+            .map(|l| preprocess_line(l).0)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    let total_lines = content.total_lines();
+    Arc::new(Preview::new(
+        entry.name.clone(),
+        content,
+        entry.icon,
+        None,
+        total_lines,
+    ))
+}
+
 fn compute_highlighted_text_preview(
     entry: &entry::Entry,
     lines: &[String],
@@ -0,0 +1,149 @@
+use crate::utils::input::Input;
+
+/// The state of a search within the preview panel.
+#[derive(Debug, Default)]
+pub struct PreviewSearch {
+    /// The input used to type the pattern.
+    pub input: Input,
+    /// Whether the pattern is currently being typed.
+    pub editing: bool,
+    /// The last confirmed pattern, if any.
+    pub pattern: Option<String>,
+}
+
+impl PreviewSearch {
+    /// Start typing a new pattern.
+    pub fn start(&mut self) {
+        self.input.reset();
+        self.editing = true;
+    }
+
+    /// Stop typing and use the current input as the pattern (an empty input
+    /// clears the pattern).
+    pub fn confirm(&mut self) {
+        self.editing = false;
+        self.pattern = Some(self.input.value().to_string())
+            .filter(|pattern| !pattern.is_empty());
+    }
+
+    /// Stop typing and keep the previous pattern.
+    pub fn cancel(&mut self) {
+        self.editing = false;
+    }
+}
+
+/// Whether the pattern should be matched case-sensitively, i.e. whether it
+/// contains uppercase characters.
+fn is_case_sensitive(pattern: &str) -> bool {
+    pattern.chars().any(char::is_uppercase)
+}
+
+/// Find the byte ranges of all occurrences of `pattern` in `haystack`.
+///
+/// Matching is case-insensitive unless the pattern contains uppercase
+/// characters.
+pub fn find_matches(haystack: &str, pattern: &str) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    let (haystack, pattern) = if is_case_sensitive(pattern) {
+        (haystack.to_string(), pattern.to_string())
+    } else {
+        // ascii lowercasing preserves byte offsets
+        (haystack.to_ascii_lowercase(), pattern.to_ascii_lowercase())
+    };
+    haystack
+        .match_indices(&pattern)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+/// Find the matching line to jump to from `current`.
+///
+/// `lines` must be sorted. The search wraps around and `current` itself is
+/// only considered if `inclusive` is set.
+pub fn next_match(
+    lines: &[u16],
+    current: u16,
+    forward: bool,
+    inclusive: bool,
+) -> Option<u16> {
+    let is_candidate = |line: u16| {
+        (inclusive && line == current)
+            || if forward {
+                line > current
+            } else {
+                line < current
+            }
+    };
+    if forward {
+        lines.iter().copied().find(|l| is_candidate(*l))
+    } else {
+        lines.iter().rev().copied().find(|l| is_candidate(*l))
+    }
+    .or_else(|| {
+        if forward {
+            lines.first().copied()
+        } else {
+            lines.last().copied()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preview::PreviewContent;
+
+    #[test]
+    fn test_find_matches_smart_case() {
+        assert_eq!(
+            find_matches("Foo foo FOO", "foo"),
+            [(0, 3), (4, 7), (8, 11)]
+        );
+        assert_eq!(find_matches("Foo foo FOO", "Foo"), [(0, 3)]);
+        assert!(find_matches("foo", "").is_empty());
+    }
+
+    #[test]
+    fn test_next_match() {
+        let lines = [2, 5, 9];
+        assert_eq!(next_match(&lines, 5, true, true), Some(5));
+        assert_eq!(next_match(&lines, 5, true, false), Some(9));
+        assert_eq!(next_match(&lines, 5, false, false), Some(2));
+        // wraps around
+        assert_eq!(next_match(&lines, 9, true, false), Some(2));
+        assert_eq!(next_match(&lines, 2, false, false), Some(9));
+        assert_eq!(next_match(&[], 0, true, true), None);
+    }
+
+    #[test]
+    fn test_find_lines_in_rendered_markdown() {
+        let content = PreviewContent::Markdown(
+            "# Title\n\nsome **bold** text\n```\ncode\n```\nbold".to_string(),
+        );
+        assert_eq!(content.find_lines("some bold"), [2]);
+        assert_eq!(content.find_lines("bold"), [2, 6]);
+        assert_eq!(content.find_first_line("bold"), Some(2));
+        assert_eq!(content.find_first_line("# Title"), None);
+        assert_eq!(content.find_first_line("code"), Some(4));
+    }
+
+    #[test]
+    fn test_preview_search_confirm() {
+        let mut search = PreviewSearch::default();
+        search.start();
+        search.input = Input::new("pattern".to_string());
+        search.confirm();
+        assert!(!search.editing);
+        assert_eq!(search.pattern.as_deref(), Some("pattern"));
+
+        search.start();
+        search.cancel();
+        assert_eq!(search.pattern.as_deref(), Some("pattern"));
+
+        search.start();
+        search.confirm();
+        assert_eq!(search.pattern, None);
+    }
+}
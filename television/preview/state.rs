@@ -0,0 +1,80 @@
+/// Whether the preview panel is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewState {
+    /// Previews are never computed and the panel can't be shown.
+    Disabled,
+    /// The panel is hidden but previews are still computed, so that showing
+    /// it displays the current preview right away.
+    Hidden,
+    /// The panel is shown.
+    Visible,
+}
+
+impl PreviewState {
+    pub fn new(disabled: bool, visible: bool) -> Self {
+        if disabled {
+            PreviewState::Disabled
+        } else if visible {
+            PreviewState::Visible
+        } else {
+            PreviewState::Hidden
+        }
+    }
+
+    /// Show a hidden panel or hide a visible one (a disabled preview stays
+    /// disabled).
+    pub fn toggle(&mut self) {
+        *self = match self {
+            PreviewState::Disabled => PreviewState::Disabled,
+            PreviewState::Hidden => PreviewState::Visible,
+            PreviewState::Visible => PreviewState::Hidden,
+        };
+    }
+
+    pub fn is_visible(self) -> bool {
+        self == PreviewState::Visible
+    }
+
+    /// Whether previews should be computed.
+    pub fn is_enabled(self) -> bool {
+        self != PreviewState::Disabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_disabled() {
+        let mut state = PreviewState::new(true, true);
+        assert_eq!(state, PreviewState::Disabled);
+        state.toggle();
+        assert_eq!(state, PreviewState::Disabled);
+        assert!(!state.is_visible());
+        assert!(!state.is_enabled());
+    }
+
+    #[test]
+    fn test_toggle_hidden() {
+        let mut state = PreviewState::new(false, false);
+        assert_eq!(state, PreviewState::Hidden);
+        assert!(!state.is_visible());
+        assert!(state.is_enabled());
+        state.toggle();
+        assert_eq!(state, PreviewState::Visible);
+        state.toggle();
+        assert_eq!(state, PreviewState::Hidden);
+    }
+
+    #[test]
+    fn test_toggle_visible() {
+        let mut state = PreviewState::new(false, true);
+        assert_eq!(state, PreviewState::Visible);
+        assert!(state.is_visible());
+        assert!(state.is_enabled());
+        state.toggle();
+        assert_eq!(state, PreviewState::Hidden);
+        assert!(state.is_enabled());
+    }
+}
@@ -1,10 +1,10 @@
 use color_eyre::Result;
-use ratatui::layout::Rect;
+use ratatui::{buffer::Buffer, layout::Rect};
 use std::{
     io::{stderr, stdout, LineWriter},
     sync::Arc,
 };
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 use tokio::{
     select,
@@ -18,6 +18,8 @@ use crate::{action::Action, tui::Tui};
 pub enum RenderingTask {
     ClearScreen,
     Render,
+    /// Log the next rendered frame.
+    DumpFrame,
     Resize(u16, u16),
     Resume,
     Suspend,
@@ -53,7 +55,8 @@ pub async fn render(
         debug!("Rendering to stderr");
         IoStream::BufferedStderr.to_stream()
     };
-    let mut tui = Tui::new(stream)?.frame_rate(frame_rate);
+    let mouse = television.lock().await.config.ui.mouse;
+    let mut tui = Tui::new(stream)?.frame_rate(frame_rate).mouse(mouse);
 
     debug!("Entering tui");
     tui.enter()?;
@@ -64,6 +67,8 @@ pub async fn render(
         .await
         .register_action_handler(action_tx.clone())?;
 
+    let mut dump_next_frame = false;
+
     // Rendering loop
     loop {
         select! {
@@ -84,19 +89,31 @@ pub async fn render(
                                 // buffer with a `u16` index which means we can't support
                                 // terminal areas larger than `u16::MAX`.
                                 if size.width.checked_mul(size.height).is_some() {
-                                    tui.terminal.draw(|frame| {
+                                    let frame = tui.terminal.draw(|frame| {
                                         if let Err(err) = television.draw(frame, frame.area()) {
                                             warn!("Failed to draw: {:?}", err);
                                             let _ = action_tx
                                                 .send(Action::Error(format!("Failed to draw: {err:?}")));
                                         }
                                     })?;
+                                    if dump_next_frame {
+                                        dump_next_frame = false;
+                                        info!(
+                                            "Frame dump ({})\n{}",
+                                            television.debug_summary(),
+                                            buffer_to_string(frame.buffer)
+                                        );
+                                    }
 
                                 } else {
                                     warn!("Terminal area too large");
                                 }
                             }
                         }
+                        RenderingTask::DumpFrame => {
+                            dump_next_frame = true;
+                            action_tx.send(Action::Render)?;
+                        }
                         RenderingTask::Resize(w, h) => {
                             tui.resize(Rect::new(0, 0, w, h))?;
                             action_tx.send(Action::Render)?;
@@ -121,3 +138,18 @@ pub async fn render(
         }
     }
 }
+
+/// Convert a rendered buffer to plain text, one line per terminal row.
+fn buffer_to_string(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -70,6 +70,11 @@ impl<'a> RenderedPreviewCache<'a> {
             self.previews.remove(&oldest_key);
         }
     }
+
+    /// Drop all rendered previews (the last one is kept as a placeholder).
+    pub fn clear(&mut self) {
+        self.previews.clear();
+    }
 }
 
 impl Default for RenderedPreviewCache<'_> {
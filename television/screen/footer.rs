@@ -0,0 +1,73 @@
+use lazy_static::lazy_static;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::Paragraph,
+    Frame,
+};
+use regex::Regex;
+
+use crate::action::Action;
+use crate::config::{parse_action, Binding};
+use crate::screen::colors::Colorscheme;
+
+lazy_static! {
+    static ref KEY_TOKEN_RE: Regex = Regex::new(r"\{key:([^}]+)\}").unwrap();
+}
+
+/// Expand the footer template for the current frame.
+///
+/// Supported tokens are `{channel}` (the current channel's name) and
+/// `{key:<action>}` (the keys bound to the given action in the current mode,
+/// e.g. `{key:toggle_preview}`). Tokens referring to an unknown action are
+/// left as-is.
+pub fn format_footer(
+    template: &str,
+    channel: &str,
+    binding_for: impl Fn(&Action) -> Option<Binding>,
+) -> String {
+    KEY_TOKEN_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            match parse_action(&caps[1]) {
+                Ok(action) => binding_for(&action)
+                    .map(|binding| binding.to_string())
+                    .unwrap_or_default(),
+                Err(_) => caps[0].to_string(),
+            }
+        })
+        .replace("{channel}", channel)
+}
+
+pub fn draw_footer(
+    f: &mut Frame,
+    rect: Rect,
+    footer: &str,
+    colorscheme: &Colorscheme,
+) {
+    let paragraph = Paragraph::new(footer.to_string())
+        .style(Style::default().fg(colorscheme.help.metadata_field_value_fg))
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, rect);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Key;
+
+    #[test]
+    fn test_format_footer() {
+        let binding_for = |action: &Action| match action {
+            Action::ToggleHelp => Some(Binding::SingleKey(Key::Ctrl('g'))),
+            _ => None,
+        };
+        assert_eq!(
+            format_footer(
+                "{channel} | help: {key:toggle_help} | quit: {key:quit} | {key:nope}",
+                "files",
+                binding_for,
+            ),
+            "files | help: Ctrl-g | quit:  | {key:nope}"
+        );
+    }
+}
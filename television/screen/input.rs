@@ -9,19 +9,44 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, ListState, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::screen::{
     colors::Colorscheme,
     spinner::{Spinner, SpinnerState},
 };
 
+/// The counts displayed in the input bar.
+#[derive(Debug, Clone, Copy)]
+pub struct ResultCounts {
+    pub matched: u32,
+    pub total: u32,
+    pub selected: usize,
+}
+
+/// Expand the result count template.
+///
+/// Supported tokens are `{position}` (the position of the entry under the
+/// cursor), `{matched}`, `{total}` and `{selected}`.
+pub fn format_result_count(
+    template: &str,
+    position: usize,
+    counts: ResultCounts,
+) -> String {
+    template
+        .replace("{position}", &position.to_string())
+        .replace("{matched}", &counts.matched.to_string())
+        .replace("{total}", &counts.total.to_string())
+        .replace("{selected}", &counts.selected.to_string())
+}
+
 // TODO: refactor arguments (e.g. use a struct for the spinner+state, same
 #[allow(clippy::too_many_arguments)]
 pub fn draw_input_box(
     f: &mut Frame,
     rect: Rect,
-    results_count: u32,
-    total_count: u32,
+    counts: ResultCounts,
+    count_template: Option<&str>,
     input_state: &mut Input,
     results_picker_state: &mut ListState,
     matcher_running: bool,
@@ -45,6 +70,18 @@ pub fn draw_input_box(
 
     f.render_widget(input_block, rect);
 
+    let position = if counts.matched == 0 {
+        0
+    } else {
+        results_picker_state.selected().unwrap_or(0) + 1
+    };
+    let result_count = match count_template {
+        Some(template) => {
+            format!(" {} ", format_result_count(template, position, counts))
+        }
+        None => format!(" {position} / {} ", counts.matched),
+    };
+
     // split input block into 4 parts: prompt symbol, input, result count, spinner
     let inner_input_chunks = RatatuiLayout::default()
         .direction(Direction::Horizontal)
@@ -54,13 +91,17 @@ pub fn draw_input_box(
             // input field
             Constraint::Fill(1),
             // result count
-            Constraint::Length(
-                3 * (u16::try_from((total_count.max(1)).ilog10()).unwrap()
-                    + 1)
-                    + 3,
-            ),
+            Constraint::Length(match count_template {
+                Some(_) => u16::try_from(result_count.width()).unwrap_or(0),
+                None => {
+                    3 * (u16::try_from((counts.total.max(1)).ilog10())
+                        .unwrap()
+                        + 1)
+                        + 3
+                }
+            }),
             // spinner
-            Constraint::Length(1),
+            Constraint::Length(spinner.width()),
         ])
         .split(input_block_inner);
 
@@ -75,9 +116,13 @@ pub fn draw_input_box(
     let interactive_input_block = Block::default();
     // keep 2 for borders and 1 for cursor
     let width = inner_input_chunks[1].width.max(3) - 3;
-    let scroll = input_state.visual_scroll(width as usize);
+    // multi-line queries scroll to the line holding the cursor
+    let (cursor_row, cursor_line) = input_state.cursor_line();
+    let rows = usize::from(inner_input_chunks[1].height.max(1));
+    let vertical_scroll = (cursor_row + 1).saturating_sub(rows);
+    let scroll = cursor_line.visual_scroll(width as usize);
     let input = Paragraph::new(input_state.value())
-        .scroll((0, u16::try_from(scroll)?))
+        .scroll((u16::try_from(vertical_scroll)?, u16::try_from(scroll)?))
         .block(interactive_input_block)
         .style(
             Style::default()
@@ -98,15 +143,7 @@ pub fn draw_input_box(
 
     let result_count_block = Block::default();
     let result_count_paragraph = Paragraph::new(Span::styled(
-        format!(
-            " {} / {} ",
-            if results_count == 0 {
-                0
-            } else {
-                results_picker_state.selected().unwrap_or(0) + 1
-            },
-            results_count,
-        ),
+        result_count,
         Style::default()
             .fg(colorscheme.input.results_count_fg)
             .italic(),
@@ -120,9 +157,81 @@ pub fn draw_input_box(
     f.set_cursor_position((
         // Put cursor past the end of the input text
         inner_input_chunks[1].x
-            + u16::try_from(input_state.visual_cursor().max(scroll) - scroll)?,
-        // Move one line down, from the border to the input line
-        inner_input_chunks[1].y,
+            + u16::try_from(cursor_line.visual_cursor().max(scroll) - scroll)?,
+        // Move down to the line holding the cursor
+        inner_input_chunks[1].y + u16::try_from(cursor_row - vertical_scroll)?,
     ));
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "{position} / {matched} ({total})";
+
+    fn counts(matched: u32, total: u32) -> ResultCounts {
+        ResultCounts {
+            matched,
+            total,
+            selected: 0,
+        }
+    }
+
+    #[test]
+    fn test_format_result_count_zero() {
+        assert_eq!(
+            format_result_count(TEMPLATE, 0, counts(0, 0)),
+            "0 / 0 (0)"
+        );
+    }
+
+    #[test]
+    fn test_format_result_count_singular() {
+        assert_eq!(
+            format_result_count(TEMPLATE, 1, counts(1, 1)),
+            "1 / 1 (1)"
+        );
+    }
+
+    #[test]
+    fn test_format_result_count_plural() {
+        assert_eq!(
+            format_result_count(TEMPLATE, 3, counts(42, 42)),
+            "3 / 42 (42)"
+        );
+    }
+
+    #[test]
+    fn test_format_result_count_filtered() {
+        assert_eq!(
+            format_result_count(TEMPLATE, 1, counts(7, 1000)),
+            "1 / 7 (1000)"
+        );
+        // nothing matches the query, but the channel isn't empty
+        assert_eq!(
+            format_result_count(TEMPLATE, 0, counts(0, 1000)),
+            "0 / 0 (1000)"
+        );
+    }
+
+    #[test]
+    fn test_format_result_count_template() {
+        let counts = ResultCounts {
+            matched: 12,
+            total: 340,
+            selected: 2,
+        };
+        assert_eq!(
+            format_result_count("[{selected}] {matched}/{total}", 5, counts),
+            "[2] 12/340"
+        );
+        // tokens may be repeated, unknown ones are kept as is
+        assert_eq!(
+            format_result_count("{matched} {matched} {nope}", 5, counts),
+            "12 12 {nope}"
+        );
+        assert_eq!(format_result_count("results", 5, counts), "results");
+        assert_eq!(format_result_count("", 5, counts), "");
+    }
+}
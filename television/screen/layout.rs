@@ -27,6 +27,48 @@ impl Default for Dimensions {
     }
 }
 
+/// Empty space left around the whole UI, in cells.
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct UiMargin {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl UiMargin {
+    /// Inset `area` by the margin, shrinking the margin (proportionally on
+    /// both sides) where it would leave less than a usable area.
+    pub fn apply(self, area: Rect) -> Rect {
+        let (left, right) =
+            clamp_margin(self.left, self.right, area.width, MIN_UI_WIDTH);
+        let (top, bottom) =
+            clamp_margin(self.top, self.bottom, area.height, MIN_UI_HEIGHT);
+        Rect {
+            x: area.x + left,
+            y: area.y + top,
+            width: area.width - left - right,
+            height: area.height - top - bottom,
+        }
+    }
+}
+
+/// Shrink the margins on both ends of a `size` long axis so that at least
+/// `min` cells are left between them.
+fn clamp_margin(start: u16, end: u16, size: u16, min: u16) -> (u16, u16) {
+    let available = u32::from(size.saturating_sub(min));
+    let total = u32::from(start) + u32::from(end);
+    if total <= available {
+        return (start, end);
+    }
+    // both values are at most `available`, which fits in a u16
+    let start = u16::try_from(u32::from(start) * available / total)
+        .unwrap_or_default();
+    let end = u16::try_from(available).unwrap_or_default() - start;
+    (start, end)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct HelpBarLayout {
     pub left: Rect,
@@ -80,12 +122,37 @@ impl Display for PreviewTitlePosition {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq)]
+pub enum PreviewPosition {
+    #[serde(rename = "top")]
+    Top,
+    #[serde(rename = "bottom")]
+    Bottom,
+    #[serde(rename = "left")]
+    Left,
+    #[serde(rename = "right")]
+    #[default]
+    Right,
+}
+
+impl Display for PreviewPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewPosition::Top => write!(f, "top"),
+            PreviewPosition::Bottom => write!(f, "bottom"),
+            PreviewPosition::Left => write!(f, "left"),
+            PreviewPosition::Right => write!(f, "right"),
+        }
+    }
+}
+
 pub struct Layout {
     pub help_bar: Option<HelpBarLayout>,
     pub results: Rect,
     pub input: Rect,
     pub preview_window: Option<Rect>,
     pub remote_control: Option<Rect>,
+    pub footer: Option<Rect>,
 }
 
 impl Layout {
@@ -96,6 +163,7 @@ impl Layout {
         input: Rect,
         preview_window: Option<Rect>,
         remote_control: Option<Rect>,
+        footer: Option<Rect>,
     ) -> Self {
         Self {
             help_bar,
@@ -103,18 +171,35 @@ impl Layout {
             input,
             preview_window,
             remote_control,
+            footer,
         }
     }
 
+    #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
     pub fn build(
         dimensions: &Dimensions,
         area: Rect,
         with_remote: bool,
         with_help_bar: bool,
+        with_footer: bool,
         with_preview: bool,
+        preview_size: u16,
+        input_rows: u16,
         input_position: InputPosition,
+        preview_position: PreviewPosition,
     ) -> Self {
-        let main_block = centered_rect(dimensions.x, dimensions.y, area);
+        let mut main_block = centered_rect(dimensions.x, dimensions.y, area);
+        // reserve the last row of the main block for the footer
+        let footer = if with_footer {
+            let chunks = layout::Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Fill(1), Constraint::Length(1)])
+                .split(main_block);
+            main_block = chunks[0];
+            Some(chunks[1])
+        } else {
+            None
+        };
         // split the main block into two vertical chunks (help bar + rest)
         let main_rect: Rect;
         let help_bar_layout: Option<HelpBarLayout>;
@@ -149,12 +234,9 @@ impl Layout {
             help_bar_layout = None;
         }
 
-        // split the main block into 1, 2, or 3 vertical chunks
-        // (results + preview + remote)
+        // split the main block into 1 or 2 vertical chunks
+        // (results and preview + remote)
         let mut constraints = vec![Constraint::Fill(1)];
-        if with_preview {
-            constraints.push(Constraint::Fill(1));
-        }
         if with_remote {
             // in order to fit with the help bar logo
             constraints.push(Constraint::Length(24));
@@ -164,9 +246,47 @@ impl Layout {
             .constraints(constraints)
             .split(main_rect);
 
+        // split the first chunk into results and preview according to the
+        // preview position
+        let (results_rect, preview_window) = if with_preview {
+            let chunks = layout::Layout::default()
+                .direction(match preview_position {
+                    PreviewPosition::Top | PreviewPosition::Bottom => {
+                        Direction::Vertical
+                    }
+                    PreviewPosition::Left | PreviewPosition::Right => {
+                        Direction::Horizontal
+                    }
+                })
+                .constraints(match preview_position {
+                    PreviewPosition::Top | PreviewPosition::Left => [
+                        Constraint::Fill(preview_size),
+                        Constraint::Fill(100 - preview_size),
+                    ],
+                    PreviewPosition::Bottom | PreviewPosition::Right => [
+                        Constraint::Fill(100 - preview_size),
+                        Constraint::Fill(preview_size),
+                    ],
+                })
+                .split(vt_chunks[0]);
+            match preview_position {
+                PreviewPosition::Top | PreviewPosition::Left => {
+                    (chunks[1], Some(chunks[0]))
+                }
+                PreviewPosition::Bottom | PreviewPosition::Right => {
+                    (chunks[0], Some(chunks[1]))
+                }
+            }
+        } else {
+            (vt_chunks[0], None)
+        };
+
         // left block: results + input field
-        let results_constraints =
-            vec![Constraint::Min(3), Constraint::Length(3)];
+        let results_constraints = vec![
+            Constraint::Min(3),
+            // 2 for the borders
+            Constraint::Length(input_rows + 2),
+        ];
 
         let left_chunks = layout::Layout::default()
             .direction(Direction::Vertical)
@@ -176,23 +296,14 @@ impl Layout {
                 }
                 InputPosition::Bottom => results_constraints,
             })
-            .split(vt_chunks[0]);
+            .split(results_rect);
         let (input, results) = match input_position {
             InputPosition::Bottom => (left_chunks[1], left_chunks[0]),
             InputPosition::Top => (left_chunks[0], left_chunks[1]),
         };
 
-        // right block: preview title + preview
-        let mut remote_idx = 1;
-        let preview_window = if with_preview {
-            remote_idx += 1;
-            Some(vt_chunks[1])
-        } else {
-            None
-        };
-
         let remote_control = if with_remote {
-            Some(vt_chunks[remote_idx])
+            Some(vt_chunks[1])
         } else {
             None
         };
@@ -203,6 +314,7 @@ impl Layout {
             input,
             preview_window,
             remote_control,
+            footer,
         )
     }
 }
@@ -233,3 +345,51 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 // UI size
 const UI_WIDTH_PERCENT: u16 = 95;
 const UI_HEIGHT_PERCENT: u16 = 95;
+
+/// The smallest area (in cells) the UI is given once the margin is applied,
+/// unless the terminal itself is smaller.
+const MIN_UI_WIDTH: u16 = 30;
+const MIN_UI_HEIGHT: u16 = 8;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_margin() {
+        let margin = UiMargin {
+            top: 1,
+            right: 4,
+            bottom: 2,
+            left: 3,
+        };
+        assert_eq!(
+            margin.apply(Rect::new(0, 0, 100, 40)),
+            Rect::new(3, 1, 93, 37)
+        );
+        assert_eq!(
+            UiMargin::default().apply(Rect::new(0, 0, 100, 40)),
+            Rect::new(0, 0, 100, 40)
+        );
+    }
+
+    #[test]
+    fn test_margin_is_clamped() {
+        let margin = UiMargin {
+            top: 10,
+            right: 30,
+            bottom: 10,
+            left: 10,
+        };
+        // 10 columns and 4 rows can be spared
+        assert_eq!(
+            margin.apply(Rect::new(0, 0, 40, 12)),
+            Rect::new(2, 2, MIN_UI_WIDTH, MIN_UI_HEIGHT)
+        );
+        // a terminal smaller than the minimum gets no margin at all
+        assert_eq!(
+            margin.apply(Rect::new(0, 0, 20, 5)),
+            Rect::new(0, 0, 20, 5)
+        );
+    }
+}
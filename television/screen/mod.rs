@@ -1,5 +1,6 @@
 pub mod cache;
 pub mod colors;
+pub mod footer;
 pub mod help;
 pub mod input;
 pub mod keybindings;
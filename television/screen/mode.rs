@@ -18,3 +18,8 @@ pub enum Mode {
     RemoteControl,
     SendToChannel,
 }
+
+impl Mode {
+    pub const ALL: [Mode; 3] =
+        [Mode::Channel, Mode::RemoteControl, Mode::SendToChannel];
+}
@@ -1,7 +1,7 @@
 use crate::channels::entry::Entry;
 use crate::preview::{
-    ansi::IntoText, Preview, PreviewContent, FILE_TOO_LARGE_MSG, LOADING_MSG,
-    PREVIEW_NOT_SUPPORTED_MSG, TIMEOUT_MSG,
+    ansi::IntoText, markdown, search::find_matches, Preview, PreviewContent,
+    FILE_TOO_LARGE_MSG, LOADING_MSG, PREVIEW_NOT_SUPPORTED_MSG, TIMEOUT_MSG,
 };
 use crate::screen::{
     cache::RenderedPreviewCache,
@@ -32,6 +32,7 @@ pub fn build_preview_paragraph<'a>(
     preview_content: PreviewContent,
     target_line: Option<u16>,
     preview_scroll: u16,
+    sanitize: bool,
     colorscheme: Colorscheme,
 ) -> Paragraph<'a> {
     let preview_block =
@@ -42,8 +43,14 @@ pub fn build_preview_paragraph<'a>(
             left: 1,
         });
     match preview_content {
-        PreviewContent::AnsiText(text) => {
-            build_ansi_text_paragraph(text, preview_block, preview_scroll)
+        PreviewContent::AnsiText(text) => build_ansi_text_paragraph(
+            text,
+            preview_block,
+            preview_scroll,
+            sanitize,
+        ),
+        PreviewContent::Markdown(text) => {
+            Paragraph::new(markdown::to_text(&text)).block(preview_block)
         }
         PreviewContent::PlainText(content) => build_plain_text_paragraph(
             content,
@@ -109,6 +116,7 @@ fn build_ansi_text_paragraph(
     text: String,
     preview_block: Block,
     preview_scroll: u16,
+    sanitize: bool,
 ) -> Paragraph {
     let lines = text.lines();
     let skip =
@@ -120,17 +128,21 @@ fn build_ansi_text_paragraph(
         .join("\n");
 
     let mut text = "\n".repeat(skip);
-    text.push_str(
-        &replace_non_printable(
-            context.as_bytes(),
-            &ReplaceNonPrintableConfig {
-                replace_line_feed: false,
-                replace_control_characters: false,
-                ..Default::default()
-            },
-        )
-        .0,
-    );
+    if sanitize {
+        text.push_str(
+            &replace_non_printable(
+                context.as_bytes(),
+                &ReplaceNonPrintableConfig {
+                    replace_line_feed: false,
+                    replace_control_characters: false,
+                    ..Default::default()
+                },
+            )
+            .0,
+        );
+    } else {
+        text.push_str(&context);
+    }
 
     Paragraph::new(text.into_text().unwrap())
         .block(preview_block)
@@ -159,7 +171,7 @@ fn build_plain_text_paragraph(
                     colorscheme.gutter_fg
                 },
             )),
-            Span::styled(" │ ",
+            Span::styled(GUTTER_SEPARATOR,
                          Style::default().fg(colorscheme.gutter_fg).dim()),
             Span::styled(
                 line.to_string(),
@@ -269,12 +281,42 @@ pub fn build_meta_preview_paragraph<'a>(
     Paragraph::new(Text::from(lines))
 }
 
+/// Expand the preview footer template for the current frame.
+///
+/// Supported tokens are `{}` (the entry's name), `{scroll}` (the first
+/// visible line) and `{total_lines}`.
+pub fn format_preview_footer(
+    template: &str,
+    entry: &Entry,
+    preview_scroll: u16,
+    total_lines: u16,
+) -> String {
+    template
+        .replace("{scroll}", &(preview_scroll + 1).to_string())
+        .replace("{total_lines}", &total_lines.to_string())
+        .replace("{}", &entry.name)
+}
+
+/// The icon and title displayed in the preview border: a fixed label if one
+/// is set, otherwise those of the previewed entry.
+fn border_title<'a>(
+    label: Option<&'a str>,
+    icon: Option<FileIcon>,
+    title: &'a str,
+) -> (Option<FileIcon>, &'a str) {
+    match label {
+        Some(label) => (None, label),
+        None => (icon, title),
+    }
+}
+
 fn draw_content_outer_block(
     f: &mut Frame,
     rect: Rect,
     colorscheme: &Colorscheme,
     icon: Option<FileIcon>,
     title: &str,
+    footer: Option<&str>,
     use_nerd_font_icons: bool,
 ) -> Result<Rect> {
     let mut preview_title_spans = vec![Span::from(" ")];
@@ -305,12 +347,22 @@ fn draw_content_outer_block(
     preview_title_spans.push(Span::from(" "));
 
     // build the preview block
-    let preview_outer_block = Block::default()
-        .title_top(
-            Line::from(preview_title_spans)
-                .alignment(Alignment::Center)
-                .style(Style::default().fg(colorscheme.preview.title_fg)),
-        )
+    let mut preview_outer_block = Block::default().title_top(
+        Line::from(preview_title_spans)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(colorscheme.preview.title_fg)),
+    );
+    // optional footer
+    if let Some(footer) = footer {
+        preview_outer_block = preview_outer_block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {footer} "),
+                Style::default().fg(colorscheme.preview.title_fg),
+            ))
+            .alignment(Alignment::Right),
+        );
+    }
+    let preview_outer_block = preview_outer_block
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(colorscheme.general.border_fg))
@@ -333,16 +385,24 @@ pub fn draw_preview_content_block(
     preview: &Option<Arc<Preview>>,
     rendered_preview_cache: &Arc<Mutex<RenderedPreviewCache<'static>>>,
     preview_scroll: u16,
+    label: Option<&str>,
+    wrap: bool,
+    sanitize: bool,
+    target_column: Option<(u16, u16)>,
+    footer: Option<&str>,
+    search_pattern: Option<&str>,
     use_nerd_font_icons: bool,
     colorscheme: &Colorscheme,
 ) -> Result<()> {
     if let Some(preview) = preview {
+        let (icon, title) = border_title(label, preview.icon, &preview.title);
         let inner = draw_content_outer_block(
             f,
             rect,
             colorscheme,
-            preview.icon,
-            &preview.title,
+            icon,
+            title,
+            footer,
             use_nerd_font_icons,
         )?;
 
@@ -354,20 +414,36 @@ pub fn draw_preview_content_block(
             // we got a hit, render the cached preview content
             let p = rp.paragraph.as_ref().clone();
             f.render_widget(p.scroll((preview_scroll, 0)), inner);
+            if !wrap {
+                highlight_target_column(
+                    f,
+                    inner,
+                    &preview.content,
+                    preview_scroll,
+                    target_column,
+                );
+            }
+            highlight_search_matches(f, inner, search_pattern);
             return Ok(());
         }
         // render the preview content and cache it
-        let rp = build_preview_paragraph(
+        let mut rp = build_preview_paragraph(
             //preview_inner_block,
             inner,
             preview.content.clone(),
             entry.line_number.map(|l| u16::try_from(l).unwrap_or(0)),
             preview_scroll,
+            sanitize,
             colorscheme.clone(),
         );
+        if wrap {
+            rp = rp.wrap(Wrap { trim: false });
+        }
         // only cache the preview content if it's not a partial preview
         // and the preview title matches the entry name
-        if preview.partial_offset.is_none() && preview.title == entry.name {
+        if preview.partial_offset.is_none()
+            && preview.title == entry.display_name()
+        {
             rendered_preview_cache.lock().unwrap().insert(
                 cache_key,
                 preview.icon,
@@ -376,18 +452,31 @@ pub fn draw_preview_content_block(
             );
         }
         f.render_widget(rp.scroll((preview_scroll, 0)), inner);
+        if !wrap {
+            highlight_target_column(
+                f,
+                inner,
+                &preview.content,
+                preview_scroll,
+                target_column,
+            );
+        }
+        highlight_search_matches(f, inner, search_pattern);
         return Ok(());
     }
     // else if last_preview exists
     if let Some(last_preview) =
         &rendered_preview_cache.lock().unwrap().last_preview
     {
+        let (icon, title) =
+            border_title(label, last_preview.icon, &last_preview.title);
         let inner = draw_content_outer_block(
             f,
             rect,
             colorscheme,
-            last_preview.icon,
-            &last_preview.title,
+            icon,
+            title,
+            footer,
             use_nerd_font_icons,
         )?;
 
@@ -399,6 +488,7 @@ pub fn draw_preview_content_block(
                 .scroll((preview_scroll, 0)),
             inner,
         );
+        highlight_search_matches(f, inner, search_pattern);
         return Ok(());
     }
     // otherwise render empty preview
@@ -407,7 +497,8 @@ pub fn draw_preview_content_block(
         rect,
         colorscheme,
         None,
-        "",
+        label.unwrap_or_default(),
+        None,
         use_nerd_font_icons,
     )?;
     let preview_outer_block = Block::default()
@@ -428,8 +519,16 @@ pub fn draw_preview_content_block(
     Ok(())
 }
 
+/// The minimum width of the line numbers in the gutter of plain and
+/// highlighted text previews.
+const LINE_NUMBER_WIDTH: usize = 5;
+
+/// What separates the gutter from the text of plain and highlighted text
+/// previews.
+const GUTTER_SEPARATOR: &str = " │ ";
+
 fn build_line_number_span<'a>(line_number: usize) -> Span<'a> {
-    Span::from(format!("{line_number:5} "))
+    Span::from(format!("{line_number:LINE_NUMBER_WIDTH$} "))
 }
 
 fn compute_paragraph_from_highlighted_lines(
@@ -454,7 +553,7 @@ fn compute_paragraph_from_highlighted_lines(
             Line::from_iter(
                 std::iter::once(line_number)
                     .chain(std::iter::once(Span::styled(
-                        " │ ",
+                        GUTTER_SEPARATOR,
                         Style::default().fg(colorscheme.gutter_fg).dim(),
                     )))
                     .chain(l.iter().cloned().map(|sr| {
@@ -502,6 +601,134 @@ fn convert_syn_color_to_ratatui_color(
     Color::Rgb(color.r, color.g, color.b)
 }
 
+/// Highlight the occurrences of the search pattern in the rendered preview.
+///
+/// This works on the rendered cells so that it applies to all kinds of
+/// previews.
+fn highlight_search_matches(f: &mut Frame, area: Rect, pattern: Option<&str>) {
+    let Some(pattern) = pattern.filter(|p| !p.is_empty()) else {
+        return;
+    };
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        // the row's text along with the byte offset of each cell
+        let mut row = String::new();
+        let mut offsets = Vec::with_capacity(area.width.into());
+        for x in area.left()..area.right() {
+            offsets.push((row.len(), x));
+            row.push_str(buffer[(x, y)].symbol());
+        }
+        for (start, end) in find_matches(&row, pattern) {
+            for (_, x) in offsets
+                .iter()
+                .filter(|(offset, _)| (start..end).contains(offset))
+            {
+                buffer[(*x, y)].modifier.insert(Modifier::REVERSED);
+            }
+        }
+    }
+}
+
+/// The width available to the text of a preview panel of the given width:
+/// 2 for the borders, 1 for the panel's right padding and 2 for the
+/// content's horizontal padding.
+fn preview_text_width(panel_width: u16) -> u16 {
+    panel_width.saturating_sub(5)
+}
+
+/// The number of rows each line of the preview takes once wrapped in a
+/// preview panel of the given width, estimated from the lines' width.
+fn wrapped_line_rows(
+    content: &PreviewContent,
+    panel_width: u16,
+) -> impl Iterator<Item = u16> + '_ {
+    let text_width = usize::from(preview_text_width(panel_width).max(1));
+    let has_gutter = matches!(
+        content,
+        PreviewContent::PlainText(_)
+            | PreviewContent::SyntectHighlightedText(_)
+    );
+    content.searched_lines().enumerate().map(move |(i, line)| {
+        let gutter = if has_gutter { gutter_width(i + 1) } else { 0 };
+        let width = usize::from(gutter) + Span::raw(line).width();
+        u16::try_from(width.div_ceil(text_width).max(1)).unwrap_or(u16::MAX)
+    })
+}
+
+/// Convert the preview scroll when toggling wrapping, so that the same line
+/// stays at the top: it counts lines when lines aren't wrapped, but rows
+/// when they are.
+pub fn convert_preview_scroll(
+    content: &PreviewContent,
+    panel_width: u16,
+    scroll: u16,
+    wrap: bool,
+) -> u16 {
+    let mut rows = wrapped_line_rows(content, panel_width);
+    if wrap {
+        // the rows taken by the lines above the top one
+        rows.take(usize::from(scroll)).fold(0, u16::saturating_add)
+    } else {
+        // the line the top row belongs to
+        let mut row: u16 = 0;
+        let line = rows
+            .position(|line_rows| {
+                row = row.saturating_add(line_rows);
+                row > scroll
+            })
+            .unwrap_or_else(|| usize::from(content.total_lines()));
+        u16::try_from(line).unwrap_or(u16::MAX)
+    }
+}
+
+/// The width of the gutter in front of the given line of plain and
+/// highlighted text previews.
+fn gutter_width(line_number: usize) -> u16 {
+    let width = build_line_number_span(line_number).width()
+        + Span::raw(GUTTER_SEPARATOR).width();
+    u16::try_from(width).unwrap_or(u16::MAX)
+}
+
+/// Highlight the cell at the given line and column (both starting at 1) of
+/// the rendered preview, if visible.
+fn highlight_target_column(
+    f: &mut Frame,
+    area: Rect,
+    content: &PreviewContent,
+    preview_scroll: u16,
+    target_column: Option<(u16, u16)>,
+) {
+    let Some((line, column)) = target_column else {
+        return;
+    };
+    // the content block has a padding of 1 on the left
+    let left = match content {
+        PreviewContent::PlainText(_)
+        | PreviewContent::SyntectHighlightedText(_) => {
+            1 + gutter_width(usize::from(line))
+        }
+        PreviewContent::AnsiText(_) => 1,
+        _ => return,
+    };
+    let Some(row) = line
+        .checked_sub(1)
+        .and_then(|l| l.checked_sub(preview_scroll))
+        .filter(|row| *row < area.height)
+    else {
+        return;
+    };
+    let Some(x) = column
+        .checked_sub(1)
+        .map(|c| area.x.saturating_add(left).saturating_add(c))
+        .filter(|x| *x < area.right())
+    else {
+        return;
+    };
+    f.buffer_mut()[(x, area.y + row)]
+        .modifier
+        .insert(Modifier::REVERSED | Modifier::BOLD);
+}
+
 fn compute_cache_key(entry: &Entry) -> String {
     let mut cache_key = entry.name.clone();
     if let Some(line_number) = entry.line_number {
@@ -509,3 +736,40 @@ fn compute_cache_key(entry: &Entry) -> String {
     }
     cache_key
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_preview_scroll() {
+        // lines of 0, 30 and 10 characters, with a 9 columns gutter and 20
+        // columns of text: 1, 2 and 1 rows once wrapped
+        let content = PreviewContent::PlainText(vec![
+            String::new(),
+            "a".repeat(30),
+            "b".repeat(10),
+            "c".repeat(10),
+        ]);
+        let width = 25;
+
+        assert_eq!(convert_preview_scroll(&content, width, 0, true), 0);
+        assert_eq!(convert_preview_scroll(&content, width, 2, true), 3);
+        assert_eq!(convert_preview_scroll(&content, width, 3, true), 4);
+
+        assert_eq!(convert_preview_scroll(&content, width, 3, false), 2);
+        // the second row of a wrapped line belongs to that line
+        assert_eq!(convert_preview_scroll(&content, width, 2, false), 1);
+        assert_eq!(convert_preview_scroll(&content, width, 1, false), 1);
+        // toggling back and forth keeps the same line at the top
+        for line in 0..4 {
+            let rows = convert_preview_scroll(&content, width, line, true);
+            assert_eq!(
+                convert_preview_scroll(&content, width, rows, false),
+                line
+            );
+        }
+        // rows past the end are clamped to the last line
+        assert_eq!(convert_preview_scroll(&content, width, 99, false), 4);
+    }
+}
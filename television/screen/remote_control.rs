@@ -86,6 +86,10 @@ fn draw_rc_channels(
         use_nerd_font_icons,
         icon_color_cache,
         &colorscheme.results,
+        None,
+        None,
+        None,
+        &[],
     );
 
     f.render_stateful_widget(channel_list, area, picker_state);
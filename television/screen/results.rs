@@ -1,25 +1,273 @@
+use crate::channels::cable::format_display;
 use crate::channels::entry::Entry;
+use crate::config::{
+    parse_style, ColumnAlignment, ColumnConfig, HighlightRuleConfig,
+};
 use crate::screen::colors::{Colorscheme, ResultsColorscheme};
 use crate::screen::layout::InputPosition;
 use crate::utils::strings::{
     make_matched_string_printable, next_char_boundary,
     slice_at_char_boundaries,
 };
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Context, Result};
 use ratatui::layout::{Alignment, Rect};
-use ratatui::prelude::{Color, Line, Span, Style};
+use ratatui::prelude::{Color, Line, Span, Style, Text};
 use ratatui::style::Stylize;
 use ratatui::widgets::{
     Block, BorderType, Borders, List, ListDirection, ListState, Padding,
 };
 use ratatui::Frame;
+use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::str::FromStr;
+use unicode_width::UnicodeWidthChar;
 
 const POINTER_SYMBOL: &str = "> ";
 const SELECTED_SYMBOL: &str = "● ";
 const DESELECTED_SYMBOL: &str = "  ";
 
+/// A template for a second line displayed below each entry and the delimiter
+/// used to split entries into fields.
+#[derive(Debug, Clone, Copy)]
+pub struct SecondaryLine<'a> {
+    pub template: &'a str,
+    pub delimiter: &'a str,
+}
+
+impl SecondaryLine<'_> {
+    /// The number of terminal rows used by each entry.
+    pub fn rows_per_entry(secondary_line: Option<&Self>) -> u16 {
+        if secondary_line.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// A style applied to the rows of the entries matching a pattern.
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    pattern: Regex,
+    style: Style,
+}
+
+impl HighlightRule {
+    pub fn new(config: &HighlightRuleConfig) -> Result<Self> {
+        let pattern = if config.regex {
+            config.pattern.clone()
+        } else {
+            regex::escape(&config.pattern)
+        };
+        Ok(Self {
+            pattern: Regex::new(&pattern).wrap_err_with(|| {
+                format!("Invalid highlight pattern `{}`", config.pattern)
+            })?,
+            style: parse_style(&config.style),
+        })
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        self.pattern.is_match(&entry.name)
+            || entry
+                .value
+                .as_deref()
+                .is_some_and(|value| self.pattern.is_match(value))
+    }
+
+    /// The style of the first rule matching the given entry, if any.
+    pub fn style_for(rules: &[Self], entry: &Entry) -> Option<Style> {
+        rules
+            .iter()
+            .find(|rule| rule.matches(entry))
+            .map(|rule| rule.style)
+    }
+}
+
+/// Split a line made of `spans` into lines at most `width` columns wide.
+///
+/// Continuation lines are indented by `indent` columns.
+fn wrap_spans(
+    spans: Vec<Span<'_>>,
+    width: usize,
+    indent: usize,
+) -> Vec<Line<'static>> {
+    let indent = indent.min(width.saturating_sub(1));
+    let mut lines = Vec::new();
+    let mut current: Vec<Span<'static>> = Vec::new();
+    let mut current_width = 0;
+    for span in spans {
+        let mut chunk = String::new();
+        for c in span.content.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if current_width + char_width > width && current_width > indent {
+                if !chunk.is_empty() {
+                    current.push(Span::styled(
+                        std::mem::take(&mut chunk),
+                        span.style,
+                    ));
+                }
+                lines.push(Line::from(std::mem::replace(
+                    &mut current,
+                    vec![Span::raw(" ".repeat(indent))],
+                )));
+                current_width = indent;
+            }
+            chunk.push(c);
+            current_width += char_width;
+        }
+        if !chunk.is_empty() {
+            current.push(Span::styled(chunk, span.style));
+        }
+    }
+    lines.push(Line::from(current));
+    lines
+}
+
+/// The layout of entries as aligned columns.
+#[derive(Debug, Clone, Copy)]
+pub struct Columns<'a> {
+    pub columns: &'a [ColumnConfig],
+    /// The delimiter splitting entries into fields (runs of whitespace if
+    /// unset).
+    pub delimiter: Option<&'a str>,
+}
+
+/// The gap between two columns.
+const COLUMN_GAP: &str = "  ";
+
+/// Split `text` into the byte ranges of its fields.
+fn split_fields(text: &str, delimiter: Option<&str>) -> Vec<(usize, usize)> {
+    let mut fields = Vec::new();
+    if let Some(delimiter) = delimiter.filter(|d| !d.is_empty()) {
+        let mut start = 0;
+        for (i, _) in text.match_indices(delimiter) {
+            fields.push((start, i));
+            start = i + delimiter.len();
+        }
+        fields.push((start, text.len()));
+    } else {
+        // runs of non-whitespace characters
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            match (c.is_whitespace(), start) {
+                (true, Some(s)) => {
+                    fields.push((s, i));
+                    start = None;
+                }
+                (false, None) => start = Some(i),
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            fields.push((s, text.len()));
+        }
+    }
+    fields
+}
+
+/// Fit the characters of a field (along with whether they are matched) to
+/// the column's width, truncating or padding them as needed.
+fn fit_column(
+    field: Vec<(char, bool)>,
+    column: &ColumnConfig,
+) -> Vec<(char, bool)> {
+    let width = usize::from(column.width);
+    let char_width = |c: &char| c.width().unwrap_or(0);
+    let field_width = field.iter().map(|(c, _)| char_width(c)).sum::<usize>();
+    if field_width > width {
+        let mut fitted = Vec::new();
+        let mut fitted_width = 0;
+        for (c, matched) in field {
+            if fitted_width + char_width(&c) + 1 > width {
+                break;
+            }
+            fitted_width += char_width(&c);
+            fitted.push((c, matched));
+        }
+        if width > 0 {
+            fitted.push(('…', false));
+        }
+        return fitted;
+    }
+    let padding = width - field_width;
+    let (left, right) = match column.align {
+        ColumnAlignment::Left => (0, padding),
+        ColumnAlignment::Right => (padding, 0),
+        ColumnAlignment::Center => (padding / 2, padding - padding / 2),
+    };
+    std::iter::repeat((' ', false))
+        .take(left)
+        .chain(field)
+        .chain(std::iter::repeat((' ', false)).take(right))
+        .collect()
+}
+
+/// Lay out `text` as aligned columns, returning the aligned text along with
+/// the match ranges mapped onto it.
+///
+/// Fields beyond the configured columns are kept as is after the last column.
+fn align_columns(
+    text: &str,
+    match_ranges: &[(u32, u32)],
+    columns: Columns,
+) -> (String, Vec<(u32, u32)>) {
+    let is_matched = |i: usize| {
+        match_ranges
+            .iter()
+            .any(|(s, e)| (*s as usize..*e as usize).contains(&i))
+    };
+    let chars_from = |start: usize, end: usize| {
+        text[start..end]
+            .char_indices()
+            .map(|(i, c)| (c, is_matched(start + i)))
+            .collect::<Vec<_>>()
+    };
+    let mut chars = Vec::new();
+    for (i, (start, end)) in split_fields(text, columns.delimiter)
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            chars.extend(COLUMN_GAP.chars().map(|c| (c, false)));
+        }
+        if let Some(column) = columns.columns.get(i) {
+            chars.extend(fit_column(chars_from(start, end), column));
+        } else {
+            // the remaining fields are kept as is
+            chars.extend(chars_from(start, text.len()));
+            break;
+        }
+    }
+    // rebuild the match ranges from the runs of matched characters
+    let mut aligned = String::new();
+    let mut ranges = Vec::new();
+    let mut range_start = None;
+    for (c, matched) in chars {
+        match (matched, range_start) {
+            (true, None) => range_start = Some(aligned.len()),
+            (false, Some(start)) => {
+                ranges.push((start, aligned.len()));
+                range_start = None;
+            }
+            _ => {}
+        }
+        aligned.push(c);
+    }
+    if let Some(start) = range_start {
+        ranges.push((start, aligned.len()));
+    }
+    let to_u32 = |i: usize| u32::try_from(i).unwrap_or(u32::MAX);
+    (
+        aligned,
+        ranges
+            .into_iter()
+            .map(|(s, e)| (to_u32(s), to_u32(e)))
+            .collect(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn build_results_list<'a, 'b>(
     results_block: Block<'b>,
     entries: &'a [Entry],
@@ -28,129 +276,265 @@ pub fn build_results_list<'a, 'b>(
     use_icons: bool,
     icon_color_cache: &mut FxHashMap<String, Color>,
     colorscheme: &ResultsColorscheme,
+    secondary_line: Option<SecondaryLine>,
+    columns: Option<Columns>,
+    wrap_width: Option<usize>,
+    highlight_rules: &[HighlightRule],
 ) -> List<'a>
 where
     'b: 'a,
 {
-    List::new(entries.iter().map(|entry| {
-        let mut spans = Vec::new();
-        // optional selection symbol
-        if let Some(selected_entries) = selected_entries {
-            if !selected_entries.is_empty() {
-                spans.push(if selected_entries.contains(entry) {
-                    Span::styled(
-                        SELECTED_SYMBOL,
-                        Style::default().fg(colorscheme.result_selected_fg),
-                    )
-                } else {
-                    Span::from(DESELECTED_SYMBOL)
-                });
-            }
-        }
-        // optional icon
-        if let Some(icon) = entry.icon.as_ref() {
-            if use_icons {
-                if let Some(icon_color) = icon_color_cache.get(icon.color) {
-                    spans.push(Span::styled(
-                        icon.to_string(),
-                        Style::default().fg(*icon_color),
-                    ));
-                } else {
-                    let icon_color = Color::from_str(icon.color).unwrap();
-                    icon_color_cache
-                        .insert(icon.color.to_string(), icon_color);
-                    spans.push(Span::styled(
-                        icon.to_string(),
-                        Style::default().fg(icon_color),
-                    ));
-                }
+    results_list(
+        results_block,
+        build_result_items(
+            entries,
+            selected_entries,
+            use_icons,
+            icon_color_cache,
+            colorscheme,
+            secondary_line,
+            columns,
+            wrap_width,
+            highlight_rules,
+        ),
+        list_direction,
+        colorscheme,
+    )
+}
 
-                spans.push(Span::raw(" "));
-            }
-        }
-        // entry name
-        let (entry_name, name_match_ranges) = make_matched_string_printable(
-            &entry.name,
-            entry.name_match_ranges.as_deref(),
-        );
-        let mut last_match_end = 0;
-        for (start, end) in name_match_ranges
-            .iter()
-            .map(|(s, e)| (*s as usize, *e as usize))
-        {
-            // from the end of the last match to the start of the current one
-            spans.push(Span::styled(
-                slice_at_char_boundaries(&entry_name, last_match_end, start)
-                    .to_string(),
-                Style::default().fg(colorscheme.result_name_fg),
-            ));
-            // the current match
-            spans.push(Span::styled(
-                slice_at_char_boundaries(&entry_name, start, end).to_string(),
+fn results_list<'a, 'b>(
+    results_block: Block<'b>,
+    items: Vec<Text<'a>>,
+    list_direction: ListDirection,
+    colorscheme: &ResultsColorscheme,
+) -> List<'a>
+where
+    'b: 'a,
+{
+    List::new(items)
+        .direction(list_direction)
+        .highlight_style(
+            Style::default().bg(colorscheme.result_selected_bg).bold(),
+        )
+        .highlight_symbol(POINTER_SYMBOL)
+        .block(results_block)
+}
+
+/// Build the text displayed for each entry.
+#[allow(clippy::too_many_arguments)]
+fn build_result_items<'a>(
+    entries: &'a [Entry],
+    selected_entries: Option<&FxHashSet<Entry>>,
+    use_icons: bool,
+    icon_color_cache: &mut FxHashMap<String, Color>,
+    colorscheme: &ResultsColorscheme,
+    secondary_line: Option<SecondaryLine>,
+    columns: Option<Columns>,
+    wrap_width: Option<usize>,
+    highlight_rules: &[HighlightRule],
+) -> Vec<Text<'a>> {
+    entries
+        .iter()
+        .map(|entry| {
+            // matches keep their own color on highlighted rows
+            let row_style = HighlightRule::style_for(highlight_rules, entry)
+                .unwrap_or_default();
+            let plain =
+                |color: Color| Style::default().fg(color).patch(row_style);
+            let matched = row_style.patch(
                 Style::default().fg(colorscheme.match_foreground_color),
-            ));
-            last_match_end = end;
-        }
-        // we need to push a span for the remainder of the entry name
-        // but only if there's something left
-        let next_boundary = next_char_boundary(&entry_name, last_match_end);
-        if next_boundary < entry_name.len() {
-            let remainder = entry_name[next_boundary..].to_string();
-            spans.push(Span::styled(
-                remainder,
-                Style::default().fg(colorscheme.result_name_fg),
-            ));
-        }
-        // optional line number
-        if let Some(line_number) = entry.line_number {
-            spans.push(Span::styled(
-                format!(":{line_number}"),
-                Style::default().fg(colorscheme.result_line_number_fg),
-            ));
-        }
-        // optional preview
-        if let Some(preview) = &entry.value {
-            spans.push(Span::raw(": "));
+            );
+            let mut spans = Vec::new();
+            // optional selection symbol
+            if let Some(selected_entries) = selected_entries {
+                if !selected_entries.is_empty() {
+                    spans.push(if selected_entries.contains(entry) {
+                        Span::styled(
+                            SELECTED_SYMBOL,
+                            Style::default()
+                                .fg(colorscheme.result_selected_fg),
+                        )
+                    } else {
+                        Span::from(DESELECTED_SYMBOL)
+                    });
+                }
+            }
+            // optional icon
+            if let Some(icon) = entry.icon.as_ref() {
+                if use_icons {
+                    if let Some(icon_color) = icon_color_cache.get(icon.color)
+                    {
+                        spans.push(Span::styled(
+                            icon.to_string(),
+                            Style::default().fg(*icon_color),
+                        ));
+                    } else {
+                        let icon_color = Color::from_str(icon.color).unwrap();
+                        icon_color_cache
+                            .insert(icon.color.to_string(), icon_color);
+                        spans.push(Span::styled(
+                            icon.to_string(),
+                            Style::default().fg(icon_color),
+                        ));
+                    }
 
-            let (preview, preview_match_ranges) =
+                    spans.push(Span::raw(" "));
+                }
+            }
+            let prefix_width = spans.iter().map(Span::width).sum::<usize>();
+            // entry name
+            let (entry_name, name_match_ranges) =
                 make_matched_string_printable(
-                    preview,
-                    entry.value_match_ranges.as_deref(),
+                    entry.display_name(),
+                    entry.name_match_ranges.as_deref(),
                 );
+            let (entry_name, name_match_ranges) = match columns {
+                Some(columns) => {
+                    align_columns(&entry_name, &name_match_ranges, columns)
+                }
+                None => (entry_name, name_match_ranges),
+            };
             let mut last_match_end = 0;
-            for (start, end) in preview_match_ranges
+            for (start, end) in name_match_ranges
                 .iter()
                 .map(|(s, e)| (*s as usize, *e as usize))
             {
+                // from the end of the last match to the start of the current one
                 spans.push(Span::styled(
-                    slice_at_char_boundaries(&preview, last_match_end, start)
-                        .to_string(),
-                    Style::default().fg(colorscheme.result_preview_fg),
+                    slice_at_char_boundaries(
+                        &entry_name,
+                        last_match_end,
+                        start,
+                    )
+                    .to_string(),
+                    plain(colorscheme.result_name_fg),
                 ));
+                // the current match
                 spans.push(Span::styled(
-                    slice_at_char_boundaries(&preview, start, end).to_string(),
-                    Style::default().fg(colorscheme.match_foreground_color),
+                    slice_at_char_boundaries(&entry_name, start, end)
+                        .to_string(),
+                    matched,
                 ));
                 last_match_end = end;
             }
-            let next_boundary = next_char_boundary(&preview, last_match_end);
-            if next_boundary < preview.len() {
+            // we need to push a span for the remainder of the entry name
+            // but only if there's something left
+            let next_boundary =
+                next_char_boundary(&entry_name, last_match_end);
+            if next_boundary < entry_name.len() {
+                let remainder = entry_name[next_boundary..].to_string();
                 spans.push(Span::styled(
-                    preview[next_boundary..].to_string(),
-                    Style::default().fg(colorscheme.result_preview_fg),
+                    remainder,
+                    plain(colorscheme.result_name_fg),
                 ));
             }
-        }
-        Line::from(spans)
-    }))
-    .direction(list_direction)
-    .highlight_style(
-        Style::default().bg(colorscheme.result_selected_bg).bold(),
-    )
-    .highlight_symbol(POINTER_SYMBOL)
-    .block(results_block)
+            // optional line number
+            if let Some(line_number) = entry.line_number {
+                spans.push(Span::styled(
+                    format!(":{line_number}"),
+                    plain(colorscheme.result_line_number_fg),
+                ));
+            }
+            // optional preview
+            if let Some(preview) = &entry.value {
+                spans.push(Span::styled(": ", row_style));
+
+                let (preview, preview_match_ranges) =
+                    make_matched_string_printable(
+                        preview,
+                        entry.value_match_ranges.as_deref(),
+                    );
+                let mut last_match_end = 0;
+                for (start, end) in preview_match_ranges
+                    .iter()
+                    .map(|(s, e)| (*s as usize, *e as usize))
+                {
+                    spans.push(Span::styled(
+                        slice_at_char_boundaries(
+                            &preview,
+                            last_match_end,
+                            start,
+                        )
+                        .to_string(),
+                        plain(colorscheme.result_preview_fg),
+                    ));
+                    spans.push(Span::styled(
+                        slice_at_char_boundaries(&preview, start, end)
+                            .to_string(),
+                        matched,
+                    ));
+                    last_match_end = end;
+                }
+                let next_boundary =
+                    next_char_boundary(&preview, last_match_end);
+                if next_boundary < preview.len() {
+                    spans.push(Span::styled(
+                        preview[next_boundary..].to_string(),
+                        plain(colorscheme.result_preview_fg),
+                    ));
+                }
+            }
+            // long entries are optionally wrapped, aligned with the entry name
+            let mut lines = match wrap_width {
+                Some(width) => wrap_spans(spans, width, prefix_width),
+                None => vec![Line::from(spans)],
+            };
+            // optional secondary line, aligned with the entry name
+            match secondary_line {
+                Some(SecondaryLine {
+                    template,
+                    delimiter,
+                }) => {
+                    lines.push(Line::from(vec![
+                        Span::raw(" ".repeat(prefix_width)),
+                        Span::styled(
+                            format_display(template, delimiter, &entry.name),
+                            Style::default()
+                                .fg(colorscheme.result_preview_fg)
+                                .dim(),
+                        ),
+                    ]));
+                    Text::from(lines)
+                }
+                None => Text::from(lines),
+            }
+        })
+        .collect()
+}
+
+/// Given the number of rows each item takes, find how many of the first
+/// items to skip so that the `selected` one fits in `rows`.
+///
+/// Returns the number of skipped items along with the number of items that
+/// then fully fit (at least one, which may be cut if it's too tall).
+fn fit_items(
+    heights: &[usize],
+    selected: usize,
+    rows: usize,
+) -> (usize, usize) {
+    let selected = selected.min(heights.len().saturating_sub(1));
+    let mut skipped = 0;
+    while skipped < selected
+        && heights[skipped..=selected].iter().sum::<usize>() > rows
+    {
+        skipped += 1;
+    }
+    let mut used = 0;
+    let fitting = heights[skipped..]
+        .iter()
+        .take_while(|height| {
+            used += *height;
+            used <= rows
+        })
+        .count();
+    (skipped, fitting.max(1))
 }
 
+/// Draw the results list, returning the number of entries that fit in it.
+///
+/// Wrapped entries take a varying number of rows, so the first entries may be
+/// skipped (moving the relative selection accordingly) to keep the selected
+/// one in view.
 #[allow(clippy::too_many_arguments)]
 pub fn draw_results_list(
     f: &mut Frame,
@@ -164,13 +548,21 @@ pub fn draw_results_list(
     colorscheme: &Colorscheme,
     help_keybinding: &str,
     preview_keybinding: &str,
-) -> Result<()> {
+    status_message: Option<&str>,
+    secondary_line: Option<SecondaryLine>,
+    columns: Option<Columns>,
+    wrap: bool,
+    highlight_rules: &[HighlightRule],
+) -> Result<usize> {
     let results_block = Block::default()
         .title_top(Line::from(" Results ").alignment(Alignment::Center))
         .title_bottom(
-            Line::from(format!(
-                " help: <{help_keybinding}>  preview: <{preview_keybinding}> "
-            ))
+            Line::from(match status_message {
+                Some(message) => format!(" {message} "),
+                None => format!(
+                    " help: <{help_keybinding}>  preview: <{preview_keybinding}> "
+                ),
+            })
             .alignment(Alignment::Center),
         )
         .borders(Borders::ALL)
@@ -182,19 +574,168 @@ pub fn draw_results_list(
         )
         .padding(Padding::right(1));
 
-    let results_list = build_results_list(
-        results_block,
+    let mut items = build_result_items(
         entries,
         Some(selected_entries),
+        use_nerd_font_icons,
+        icon_color_cache,
+        &colorscheme.results,
+        secondary_line,
+        columns,
+        // the borders, right padding and pointer symbol take up some room
+        wrap.then(|| {
+            usize::from(rect.width.saturating_sub(3))
+                .saturating_sub(POINTER_SYMBOL.len())
+                .max(1)
+        }),
+        highlight_rules,
+    );
+    let mut fitting = items.len();
+    if wrap && !items.is_empty() {
+        let heights = items.iter().map(Text::height).collect::<Vec<_>>();
+        let selected = relative_picker_state.selected().unwrap_or(0);
+        let (skipped, fit) = fit_items(
+            &heights,
+            selected,
+            usize::from(rect.height.saturating_sub(2)), // the borders
+        );
+        items.drain(..skipped);
+        relative_picker_state.select(Some(selected - skipped));
+        *relative_picker_state.offset_mut() = 0;
+        fitting = fit;
+    }
+
+    let results_list = results_list(
+        results_block,
+        items,
         match input_bar_position {
             InputPosition::Bottom => ListDirection::BottomToTop,
             InputPosition::Top => ListDirection::TopToBottom,
         },
-        use_nerd_font_icons,
-        icon_color_cache,
         &colorscheme.results,
     );
 
     f.render_stateful_widget(results_list, rect, relative_picker_state);
-    Ok(())
+    Ok(fitting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_contents(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_rules() {
+        let rules = [
+            HighlightRule::new(&HighlightRuleConfig {
+                pattern: "FAIL".to_string(),
+                style: "red".to_string(),
+                regex: false,
+            })
+            .unwrap(),
+            HighlightRule::new(&HighlightRuleConfig {
+                pattern: r"^ok\b".to_string(),
+                style: "green".to_string(),
+                regex: true,
+            })
+            .unwrap(),
+        ];
+        let style_for = |name: &str| {
+            HighlightRule::style_for(
+                &rules,
+                &Entry::new(
+                    name.to_string(),
+                    crate::channels::entry::PreviewType::None,
+                ),
+            )
+            .and_then(|style| style.fg)
+        };
+        assert_eq!(style_for("test a ... FAIL"), Some(Color::Indexed(1)));
+        assert_eq!(style_for("ok test b"), Some(Color::Indexed(2)));
+        assert_eq!(style_for("not ok"), None);
+        assert!(HighlightRule::new(&HighlightRuleConfig {
+            pattern: "(".to_string(),
+            style: "red".to_string(),
+            regex: true,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_wrap_spans() {
+        let spans = vec![
+            Span::raw("● "),
+            Span::styled("abcdef", Style::default().bold()),
+            Span::raw("ghij"),
+        ];
+        let lines = wrap_spans(spans, 5, 2);
+        assert_eq!(
+            lines.iter().map(line_contents).collect::<Vec<_>>(),
+            ["● abc", "  def", "  ghi", "  j"]
+        );
+        // styles are kept across the split
+        assert_eq!(lines[1].spans[1].style, Style::default().bold());
+    }
+
+    #[test]
+    fn test_align_columns() {
+        let columns = [
+            ColumnConfig {
+                width: 6,
+                align: ColumnAlignment::Right,
+            },
+            ColumnConfig {
+                width: 3,
+                align: ColumnAlignment::Left,
+            },
+        ];
+        let columns = Columns {
+            columns: &columns,
+            delimiter: None,
+        };
+        // "42" and "use" are matched, "user" is truncated
+        let (aligned, ranges) =
+            align_columns("42  user   bash -l", &[(0, 2), (4, 7)], columns);
+        assert_eq!(aligned, "    42  us…  bash -l");
+        assert_eq!(ranges, [(4, 6), (8, 10)]);
+    }
+
+    #[test]
+    fn test_align_columns_with_delimiter() {
+        let columns = [ColumnConfig {
+            width: 5,
+            align: ColumnAlignment::Center,
+        }];
+        let columns = Columns {
+            columns: &columns,
+            delimiter: Some("|"),
+        };
+        let (aligned, ranges) = align_columns("ab|c d|e", &[(3, 4)], columns);
+        assert_eq!(aligned, " ab    c d|e");
+        assert_eq!(ranges, [(7, 8)]);
+    }
+
+    #[test]
+    fn test_fit_items() {
+        // everything fits
+        assert_eq!(fit_items(&[1, 2, 1], 2, 5), (0, 3));
+        // the selected item is pushed out by the taller ones above it
+        assert_eq!(fit_items(&[3, 3, 1, 2], 2, 5), (1, 2));
+        assert_eq!(fit_items(&[3, 3, 1, 2], 3, 5), (2, 2));
+        // an item taller than the panel is still displayed
+        assert_eq!(fit_items(&[1, 8, 1], 1, 5), (1, 1));
+    }
+
+    #[test]
+    fn test_wrap_spans_short_line() {
+        let lines = wrap_spans(vec![Span::raw("abc")], 10, 0);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(line_contents(&lines[0]), "abc");
+    }
 }
@@ -1,28 +1,71 @@
 use ratatui::{
-    buffer::Buffer, layout::Rect, style::Style, widgets::StatefulWidget,
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Span,
+    widgets::{Block, StatefulWidget, Widget},
+    Frame,
 };
 
+use unicode_width::UnicodeWidthStr;
+
+use crate::screen::colors::Colorscheme;
+
+use crate::config::SpinnerConfig;
+
 const FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 /// A spinner widget.
-#[derive(Debug, Clone, Copy)]
+///
+/// A spinner without frames is disabled and doesn't render anything.
+#[derive(Debug, Clone)]
 pub struct Spinner {
-    frames: &'static [&'static str],
+    frames: Vec<String>,
 }
 
 impl Spinner {
-    pub fn new(frames: &'static [&str]) -> Spinner {
+    pub fn new(frames: Vec<String>) -> Spinner {
         Spinner { frames }
     }
 
+    /// A spinner that doesn't render anything.
+    pub fn disabled() -> Spinner {
+        Spinner::new(Vec::new())
+    }
+
     pub fn frame(&self, index: usize) -> &str {
-        self.frames[index]
+        self.frames.get(index).map_or("", String::as_str)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    /// The width of the widest frame.
+    pub fn width(&self) -> u16 {
+        self.frames
+            .iter()
+            .map(|frame| u16::try_from(frame.width()).unwrap_or(u16::MAX))
+            .max()
+            .unwrap_or(0)
     }
 }
 
 impl Default for Spinner {
     fn default() -> Spinner {
-        Spinner::new(FRAMES)
+        Spinner::new(FRAMES.iter().map(ToString::to_string).collect())
+    }
+}
+
+impl From<&SpinnerConfig> for Spinner {
+    fn from(config: &SpinnerConfig) -> Spinner {
+        if !config.enabled {
+            Spinner::disabled()
+        } else if config.frames.is_empty() {
+            Spinner::default()
+        } else {
+            Spinner::new(config.frames.clone())
+        }
     }
 }
 
@@ -41,7 +84,8 @@ impl SpinnerState {
     }
 
     fn tick(&mut self) {
-        self.current_frame = (self.current_frame + 1) % self.total_frames;
+        self.current_frame =
+            (self.current_frame + 1) % self.total_frames.max(1);
     }
 }
 
@@ -56,13 +100,7 @@ impl StatefulWidget for Spinner {
 
     /// Renders the spinner in the given area.
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        buf.set_string(
-            area.left(),
-            area.top(),
-            self.frame(state.current_frame),
-            Style::default(),
-        );
-        state.tick();
+        (&self).render(area, buf, state);
     }
 }
 impl StatefulWidget for &Spinner {
@@ -79,3 +117,73 @@ impl StatefulWidget for &Spinner {
         state.tick();
     }
 }
+
+const LOADING_MESSAGE: &str = " Loading...";
+
+/// Draw a spinner and a loading message in the middle of the given area.
+pub fn draw_loading_indicator(
+    f: &mut Frame,
+    area: Rect,
+    spinner: &Spinner,
+    spinner_state: &mut SpinnerState,
+    colorscheme: &Colorscheme,
+) {
+    if area.is_empty() {
+        return;
+    }
+    Block::default()
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .render(area, f.buffer_mut());
+    let spinner_width = spinner.width();
+    #[allow(clippy::cast_possible_truncation)]
+    let width = (LOADING_MESSAGE.len() as u16 + spinner_width).min(area.width);
+    let rect = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + area.height / 2,
+        width,
+        1.min(area.height),
+    );
+    f.render_stateful_widget(spinner, rect, spinner_state);
+    f.render_widget(
+        Span::styled(
+            LOADING_MESSAGE,
+            Style::default().fg(colorscheme.input.results_count_fg),
+        ),
+        Rect::new(
+            rect.x + spinner_width.min(rect.width),
+            rect.y,
+            rect.width.saturating_sub(spinner_width),
+            rect.height,
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinner_from_config() {
+        let spinner = Spinner::from(&SpinnerConfig::default());
+        assert_eq!(spinner.frame(0), FRAMES[0]);
+        assert_eq!(spinner.width(), 1);
+
+        let spinner = Spinner::from(&SpinnerConfig {
+            enabled: true,
+            frames: vec!["[=  ]".to_string(), "[ = ]".to_string()],
+        });
+        assert_eq!(spinner.frame(1), "[ = ]");
+        assert_eq!(spinner.width(), 5);
+
+        let spinner = Spinner::from(&SpinnerConfig {
+            enabled: false,
+            frames: vec!["-".to_string()],
+        });
+        assert!(!spinner.is_enabled());
+        assert_eq!(spinner.width(), 0);
+        assert_eq!(spinner.frame(0), "");
+    }
+}
@@ -1,37 +1,97 @@
 use crate::action::Action;
 use crate::channels::entry::{Entry, PreviewType, ENTRY_PLACEHOLDER};
 use crate::channels::{
+    cable::DEFAULT_DELIMITER,
     remote_control::{load_builtin_channels, RemoteControl},
     OnAir, TelevisionChannel, UnitChannel,
 };
-use crate::config::{Config, KeyBindings, Theme};
+use crate::config::{
+    Config, KeyBindings, OnEmptyConfirm, PreviewRender, Theme,
+};
+use crate::event::Key;
 use crate::input::convert_action_to_input_request;
+use crate::matcher::config::MatchingScheme;
 use crate::picker::Picker;
-use crate::preview::Previewer;
+use crate::preview::{
+    previewers::command::{
+        multi_preview_entry, resolve_position, resolve_search,
+    },
+    search::{next_match, PreviewSearch},
+    state::PreviewState,
+    Preview, Previewer, PreviewerConfig,
+};
 use crate::screen::cache::RenderedPreviewCache;
 use crate::screen::colors::Colorscheme;
+use crate::screen::footer::{draw_footer, format_footer};
 use crate::screen::help::draw_help_bar;
-use crate::screen::input::draw_input_box;
+use crate::screen::input::{draw_input_box, ResultCounts};
 use crate::screen::keybindings::{
     build_keybindings_table, DisplayableAction, DisplayableKeybindings,
 };
 use crate::screen::layout::{Dimensions, InputPosition, Layout};
 use crate::screen::mode::Mode;
-use crate::screen::preview::draw_preview_content_block;
+use crate::screen::preview::{
+    convert_preview_scroll, draw_preview_content_block, format_preview_footer,
+};
 use crate::screen::remote_control::draw_remote_control;
-use crate::screen::results::draw_results_list;
-use crate::screen::spinner::{Spinner, SpinnerState};
+use crate::screen::results::{
+    draw_results_list, Columns, HighlightRule, SecondaryLine,
+};
+use crate::screen::spinner::{draw_loading_indicator, Spinner, SpinnerState};
+use crate::utils::clipboard::copy_to_clipboard;
+use crate::utils::input::{Input, InputRequest};
 use crate::utils::metadata::{AppMetadata, BuildMetadata};
+use crate::utils::pipe::SelectionPipe;
+use crate::utils::scroll::ScrollAccelerator;
 use crate::utils::strings::EMPTY_STRING;
 use crate::{cable::load_cable_channels, keymap::Keymap};
 use color_eyre::Result;
-use copypasta::{ClipboardContext, ClipboardProvider};
 use ratatui::{layout::Rect, style::Color, Frame};
+use regex::Regex;
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+/// How long status messages stay on screen.
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(3);
+
+/// Shown in the preview footer while long lines are wrapped.
+const WRAP_INDICATOR: &str = "[wrap]";
+
+/// The number of entries fetched from the channel at a time when exporting.
+const EXPORT_BATCH_SIZE: u32 = 1000;
+
+/// How long to wait for the results of the current query when they are
+/// needed right away (e.g. by an action typed before a debounced query was
+/// matched, or once the source is done loading).
+const MATCHES_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// An entry to jump to once the channel has finished loading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GotoTarget {
+    /// The raw value of the entry to select.
+    pub value: String,
+    /// Whether to confirm the selection and exit right away.
+    pub confirm: bool,
+    /// Whether to use the value as the query if no entry matches it.
+    pub fallback_to_query: bool,
+}
+
+/// A channel left by drilling into one of its entries.
+struct DrillState {
+    channel: TelevisionChannel,
+    /// The query at the time.
+    query: String,
+    /// The entry that was drilled into, selected again when coming back.
+    entry: String,
+}
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Television {
     action_tx: Option<UnboundedSender<Action>>,
     pub config: Config,
@@ -46,6 +106,7 @@ pub struct Television {
     pub previewer: Previewer,
     pub preview_scroll: Option<u16>,
     pub preview_pane_height: u16,
+    preview_pane_width: u16,
     current_preview_total_lines: u16,
     pub icon_color_cache: FxHashMap<String, Color>,
     pub rendered_preview_cache: Arc<Mutex<RenderedPreviewCache<'static>>>,
@@ -53,6 +114,47 @@ pub struct Television {
     pub(crate) spinner_state: SpinnerState,
     pub app_metadata: AppMetadata,
     pub colorscheme: Colorscheme,
+    /// A transient message displayed at the bottom of the results panel.
+    status_message: Option<(String, Instant)>,
+    /// An entry to select once the channel has finished loading.
+    goto: Option<GotoTarget>,
+    /// The state of the search within the preview panel.
+    pub preview_search: PreviewSearch,
+    /// Only entries matching this regex are listed, whatever the query.
+    filter: Option<Regex>,
+    /// The scoring scheme given on the command line, which takes precedence
+    /// over the one of every channel.
+    matching_scheme: Option<MatchingScheme>,
+    /// The input used to type a new filter pattern, while it's being edited.
+    filter_input: Option<Input>,
+    /// Whether matching and previewing are suspended, the current results
+    /// being kept as is until resuming (or switching channels).
+    paused: bool,
+    /// Whether the preview panel is disabled, hidden or visible.
+    ///
+    /// This is the user's preference, which is kept across channel switches:
+    /// channels without a preview hide the panel without changing it.
+    pub preview_state: PreviewState,
+    /// Whether to hold off the first interactive render until the channel
+    /// has finished loading.
+    sync: bool,
+    /// Speeds up repeated scrolling actions.
+    scroll_accelerator: ScrollAccelerator,
+    /// Whether the input bar spans several rows and accepts newlines.
+    multiline_input: bool,
+    /// Whether the results and preview panels exchanged their sizes, the
+    /// preview then receiving the page scrolling actions.
+    panels_swapped: bool,
+    /// When to match the query typed last, if matching was deferred until
+    /// the user pauses typing.
+    pending_find: Option<Instant>,
+    /// The channels left by drilling into entries, most recent last.
+    drill_stack: Vec<DrillState>,
+    /// Where confirmed selections are sent, if television shouldn't exit
+    /// on confirmation.
+    selection_pipe: Option<SelectionPipe>,
+    /// Styles applied to the rows of matching entries.
+    highlight_rules: Vec<HighlightRule>,
 }
 
 impl Television {
@@ -62,16 +164,26 @@ impl Television {
         config: Config,
         input: Option<String>,
     ) -> Self {
-        let mut results_picker = Picker::new(input.clone());
+        // the channel's default query only applies if no input was provided
+        let input =
+            input.or_else(|| channel.default_query().map(ToString::to_string));
+        let mut results_picker = Picker::new(input.clone())
+            .with_scroll_margin(config.ui.results_panel.scroll_margin);
         if config.ui.input_bar_position == InputPosition::Bottom {
             results_picker = results_picker.inverted();
         }
-        let previewer = Previewer::new(Some(config.previewers.clone().into()));
+        let mut previewer = Previewer::new(Some(previewer_config(&config)));
         let keymap = Keymap::from(&config.keybindings);
         let cable_channels = load_cable_channels().unwrap_or_default();
         let builtin_channels = load_builtin_channels(Some(
             &cable_channels.keys().collect::<Vec<_>>(),
         ));
+        let remote_control =
+            TelevisionChannel::RemoteControl(RemoteControl::new(
+                builtin_channels,
+                Some(cable_channels),
+                &config.remote_control.pinned,
+            ));
 
         let app_metadata = AppMetadata::new(
             env!("CARGO_PKG_VERSION").to_string(),
@@ -86,35 +198,130 @@ impl Television {
                 .to_string(),
         );
         let colorscheme = (&Theme::from_name(&config.ui.theme)).into();
+        let scroll_accelerator = scroll_accelerator(&config);
 
-        channel.find(&input.unwrap_or(EMPTY_STRING.to_string()));
-        let spinner = Spinner::default();
+        let current_pattern = input.unwrap_or(EMPTY_STRING.to_string());
+        channel.set_prefix_bonus(config.matcher.prefix_bonus);
+        channel.find(&current_pattern);
+        previewer.set_query(&current_pattern);
+        let spinner = Spinner::from(&config.ui.spinner);
+        let preview_state =
+            PreviewState::new(false, config.ui.show_preview_panel);
+        let selection_pipe =
+            config.config.selection_pipe.clone().map(SelectionPipe::new);
+        let highlight_rules = highlight_rules(&config);
         Self {
             action_tx: None,
             config,
             keymap,
             channel,
-            remote_control: TelevisionChannel::RemoteControl(
-                RemoteControl::new(builtin_channels, Some(cable_channels)),
-            ),
+            remote_control,
             mode: Mode::Channel,
-            current_pattern: EMPTY_STRING.to_string(),
+            current_pattern,
             results_picker,
             rc_picker: Picker::default(),
             results_area_height: 0,
             previewer,
             preview_scroll: None,
             preview_pane_height: 0,
+            preview_pane_width: 0,
             current_preview_total_lines: 0,
             icon_color_cache: FxHashMap::default(),
             rendered_preview_cache: Arc::new(Mutex::new(
                 RenderedPreviewCache::default(),
             )),
-            spinner,
             spinner_state: SpinnerState::from(&spinner),
+            spinner,
             app_metadata,
             colorscheme,
+            status_message: None,
+            goto: None,
+            preview_search: PreviewSearch::default(),
+            filter: None,
+            matching_scheme: None,
+            filter_input: None,
+            paused: false,
+            preview_state,
+            sync: false,
+            scroll_accelerator,
+            multiline_input: false,
+            panels_swapped: false,
+            pending_find: None,
+            drill_stack: Vec::new(),
+            selection_pipe,
+            highlight_rules,
+        }
+    }
+
+    #[must_use]
+    pub fn with_goto(mut self, goto: Option<GotoTarget>) -> Self {
+        self.goto = goto;
+        self
+    }
+
+    /// Disable the preview panel altogether: previews are never computed and
+    /// the panel can't be toggled.
+    #[must_use]
+    pub fn with_preview_disabled(mut self, disabled: bool) -> Self {
+        if disabled {
+            self.preview_state = PreviewState::Disabled;
         }
+        self
+    }
+
+    #[must_use]
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Match entries with the given scoring scheme in every channel, rather
+    /// than with the channels' own.
+    #[must_use]
+    pub fn with_matching_scheme(
+        mut self,
+        matching_scheme: Option<MatchingScheme>,
+    ) -> Self {
+        self.matching_scheme = matching_scheme;
+        self.configure_channel();
+        self
+    }
+
+    /// Only list the entries matching `filter`, whatever the query.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Option<Regex>) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    /// Apply a reloaded configuration, keeping the current channel, query,
+    /// results and panel visibility.
+    pub fn reload_config(&mut self, mut config: Config) {
+        // settings toggled at runtime keep their current state
+        config.ui.show_help_bar = self.config.ui.show_help_bar;
+        config.ui.preview_panel.wrap = self.config.ui.preview_panel.wrap;
+        config.ui.preview_panel.tail = self.config.ui.preview_panel.tail;
+        let mut results_picker = std::mem::take(&mut self.results_picker)
+            .with_scroll_margin(config.ui.results_panel.scroll_margin);
+        if (config.ui.input_bar_position == InputPosition::Bottom)
+            != (self.config.ui.input_bar_position == InputPosition::Bottom)
+        {
+            results_picker = results_picker.inverted();
+        }
+        self.results_picker = results_picker;
+        self.previewer.set_config(previewer_config(&config));
+        self.keymap = Keymap::from(&config.keybindings);
+        self.colorscheme = (&Theme::from_name(&config.ui.theme)).into();
+        self.scroll_accelerator = scroll_accelerator(&config);
+        self.spinner = Spinner::from(&config.ui.spinner);
+        self.spinner_state = SpinnerState::from(&self.spinner);
+        self.highlight_rules = highlight_rules(&config);
+        // these depend on the colorscheme and preview settings
+        self.icon_color_cache.clear();
+        self.rendered_preview_cache.lock().unwrap().clear();
+        self.preview_scroll = None;
+        self.channel.set_prefix_bonus(config.matcher.prefix_bonus);
+        self.config = config;
     }
 
     pub fn init_remote_control(&mut self) {
@@ -122,28 +329,62 @@ impl Television {
         let builtin_channels = load_builtin_channels(Some(
             &cable_channels.keys().collect::<Vec<_>>(),
         ));
-        self.remote_control = TelevisionChannel::RemoteControl(
-            RemoteControl::new(builtin_channels, Some(cable_channels)),
-        );
+        self.remote_control =
+            TelevisionChannel::RemoteControl(RemoteControl::new(
+                builtin_channels,
+                Some(cable_channels),
+                &self.config.remote_control.pinned,
+            ));
     }
 
     pub fn current_channel(&self) -> UnitChannel {
         UnitChannel::from(&self.channel)
     }
 
+    /// Switch to `channel`, keeping the preview panel's visibility.
     pub fn change_channel(&mut self, channel: TelevisionChannel) {
         self.reset_preview_scroll();
         self.reset_picker_selection();
         self.reset_picker_input();
         self.current_pattern = EMPTY_STRING.to_string();
+        self.pending_find = None;
         self.channel.shutdown();
+        for state in self.drill_stack.drain(..) {
+            state.channel.shutdown();
+        }
         self.channel = channel;
+        self.configure_channel();
+        self.paused = false;
+        if let Some(query) = self.channel.default_query() {
+            let query = query.to_string();
+            self.results_picker.input = Input::new(query.clone());
+            self.channel.find(&query);
+            self.current_pattern = query;
+        }
+        self.previewer.set_query(&self.current_pattern);
+    }
+
+    /// Apply the settings that outlive channel switches (filter, prefix
+    /// bonus, scoring scheme) to the current channel.
+    fn configure_channel(&mut self) {
+        self.channel.set_filter(self.filter.clone());
+        self.channel
+            .set_prefix_bonus(self.config.matcher.prefix_bonus);
+        if let Some(scheme) = self.matching_scheme {
+            self.channel.set_matching_scheme(scheme);
+        }
     }
 
     fn find(&mut self, pattern: &str) {
         match self.mode {
             Mode::Channel => {
-                self.channel.find(pattern);
+                let pattern = if self.config.ui.input_bar.keep_newlines {
+                    pattern.to_string()
+                } else {
+                    pattern.replace('\n', " ")
+                };
+                self.channel.find(&pattern);
+                self.set_preview_query(&pattern);
             }
             Mode::RemoteControl | Mode::SendToChannel => {
                 self.remote_control.find(pattern);
@@ -151,6 +392,22 @@ impl Television {
         }
     }
 
+    /// Make the query available to preview commands.
+    ///
+    /// Rendered previews are discarded if they depend on the query.
+    fn set_preview_query(&mut self, query: &str) {
+        self.previewer.set_query(query);
+        if let Some(Entry {
+            preview_type: PreviewType::Command(command),
+            ..
+        }) = self.get_selected_entry(Some(Mode::Channel))
+        {
+            if command.uses_query() {
+                self.rendered_preview_cache.lock().unwrap().clear();
+            }
+        }
+    }
+
     #[must_use]
     pub fn get_selected_entry(&mut self, mode: Option<Mode>) -> Option<Entry> {
         match mode.unwrap_or(self.mode) {
@@ -171,6 +428,33 @@ impl Television {
         }
     }
 
+    /// Copy the selected entries (or the entry under the cursor) to the
+    /// clipboard, separated by spaces, showing what went wrong in the status
+    /// message if that failed.
+    ///
+    /// Returns whether the entries were copied. See [`copy_to_clipboard`]
+    /// for `persistent`.
+    fn copy_selected_entries_to_clipboard(
+        &mut self,
+        persistent: bool,
+    ) -> bool {
+        let Some(entries) = self.get_selected_entries(None) else {
+            return false;
+        };
+        let content = entries
+            .iter()
+            .map(|e| e.name.clone())
+            .collect::<Vec<_>>()
+            .join(" ");
+        match copy_to_clipboard(&content, persistent) {
+            Ok(()) => true,
+            Err(e) => {
+                self.set_status_message(e.to_string());
+                false
+            }
+        }
+    }
+
     #[must_use]
     pub fn get_selected_entries(
         &mut self,
@@ -188,6 +472,31 @@ impl Television {
         Some(self.channel.selected_entries().clone())
     }
 
+    /// The rank of the given entries among the current results.
+    ///
+    /// Entries that don't match the current query are left out.
+    pub fn entry_ranks(
+        &mut self,
+        entries: &FxHashSet<Entry>,
+    ) -> FxHashMap<Entry, u32> {
+        // the entry under the cursor is found without going through all the
+        // results
+        if let Some(i) = self.results_picker.selected() {
+            let i = u32::try_from(i).unwrap_or(u32::MAX);
+            if let Some(entry) = self.channel.get_result(i) {
+                if entries.len() == 1 && entries.contains(&entry) {
+                    return FxHashMap::from_iter([(entry, i)]);
+                }
+            }
+        }
+        self.channel
+            .results(self.channel.result_count(), 0)
+            .into_iter()
+            .zip(0..)
+            .filter(|(entry, _)| entries.contains(entry))
+            .collect()
+    }
+
     pub fn select_prev_entry(&mut self, step: u32) {
         let (result_count, picker) = match self.mode {
             Mode::Channel => {
@@ -226,10 +535,89 @@ impl Television {
         );
     }
 
+    /// Select the `n`th entry of the current list (counting from 1 for the
+    /// first listed entry, wherever the list is scrolled), returning whether
+    /// there is such an entry.
+    fn select_nth_entry(&mut self, n: u16) -> bool {
+        let (result_count, picker) = match self.mode {
+            Mode::Channel => {
+                (self.channel.result_count(), &mut self.results_picker)
+            }
+            Mode::RemoteControl | Mode::SendToChannel => {
+                (self.remote_control.result_count(), &mut self.rc_picker)
+            }
+        };
+        let Some(index) = usize::from(n).checked_sub(1) else {
+            return false;
+        };
+        if index >= result_count as usize {
+            return false;
+        }
+        picker.select(Some(index));
+        picker.relative_select(Some(
+            index.min((self.results_area_height as usize).saturating_sub(1)),
+        ));
+        true
+    }
+
+    /// Keep the same line at the top of the preview after toggling
+    /// wrapping, the scroll counting rows rather than lines when wrapped.
+    fn rewrap_preview_scroll(&mut self) {
+        let Some(scroll) = self.preview_scroll else {
+            return;
+        };
+        let Some(preview) = self
+            .get_selected_entry(Some(Mode::Channel))
+            .and_then(|entry| self.preview(&entry))
+        else {
+            return;
+        };
+        self.preview_scroll = Some(convert_preview_scroll(
+            &preview.content,
+            self.preview_pane_width,
+            scroll,
+            self.config.ui.preview_panel.wrap,
+        ));
+    }
+
     fn reset_preview_scroll(&mut self) {
         self.preview_scroll = None;
     }
 
+    /// Match the given pattern once the user paused typing for
+    /// `matcher.input_debounce_ms`, or right away if debouncing is disabled.
+    fn debounced_find(&mut self, pattern: &str) {
+        let debounce = self.config.matcher.input_debounce_ms;
+        if debounce == 0 || self.mode != Mode::Channel {
+            self.find(pattern);
+        } else {
+            self.pending_find =
+                Some(Instant::now() + Duration::from_millis(debounce));
+        }
+    }
+
+    /// Match the current pattern if a debounced match is due.
+    fn find_pending_if_due(&mut self) {
+        if self.mode == Mode::Channel
+            && self
+                .pending_find
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.pending_find = None;
+            self.find(&self.current_pattern.clone());
+        }
+    }
+
+    /// Match the current pattern right away if a debounced match is
+    /// pending, and wait for its results so that the action about to be
+    /// handled doesn't act on those of the previous query.
+    pub fn flush_pending_find(&mut self) {
+        if self.pending_find.take().is_some() {
+            self.find(&self.current_pattern.clone());
+            self.channel.wait_for_matches(MATCHES_TIMEOUT);
+        }
+    }
+
     fn reset_picker_selection(&mut self) {
         match self.mode {
             Mode::Channel => self.results_picker.reset_selection(),
@@ -239,6 +627,123 @@ impl Television {
         }
     }
 
+    /// The preview of `entry`, which is only computed if not paused (the
+    /// most recent cached preview being returned otherwise).
+    fn preview(&mut self, entry: &Entry) -> Option<Arc<Preview>> {
+        if self.paused {
+            self.previewer.cached_preview(entry)
+        } else {
+            self.previewer.preview(entry)
+        }
+    }
+
+    /// Scroll the preview to the next (or previous) line matching the
+    /// preview search pattern.
+    fn jump_to_preview_match(&mut self, forward: bool, inclusive: bool) {
+        let Some(pattern) = self.preview_search.pattern.clone() else {
+            return;
+        };
+        let Some(preview) = self
+            .get_selected_entry(Some(Mode::Channel))
+            .and_then(|entry| self.preview(&entry))
+        else {
+            return;
+        };
+        let lines = preview.content.find_lines(&pattern);
+        match next_match(
+            &lines,
+            self.preview_scroll.unwrap_or(0),
+            forward,
+            inclusive,
+        ) {
+            Some(line) => self.preview_scroll = Some(line),
+            None => {
+                self.set_status_message(format!(
+                    "Pattern not found: {pattern}"
+                ));
+            }
+        }
+    }
+
+    /// Switch to the channel of the given remote control entry.
+    fn zap(&mut self, entry: &Entry) -> Result<()> {
+        let new_channel = self.remote_control.zap(entry.name.as_str())?;
+        // this resets the RC picker
+        self.reset_picker_selection();
+        self.reset_picker_input();
+        self.remote_control.find(EMPTY_STRING);
+        self.mode = Mode::Channel;
+        self.change_channel(new_channel);
+        Ok(())
+    }
+
+    /// Replace the channel with the one listing the entries of the selected
+    /// entry, if the channel supports it.
+    fn drill_into(&mut self) {
+        let Some(entry) = self.get_selected_entry(Some(Mode::Channel)) else {
+            return;
+        };
+        let Some(channel) = self.channel.drill_into(&entry) else {
+            return;
+        };
+        let previous = std::mem::replace(&mut self.channel, channel);
+        self.configure_channel();
+        self.paused = false;
+        self.drill_stack.push(DrillState {
+            channel: previous,
+            query: std::mem::take(&mut self.current_pattern),
+            entry: entry.name,
+        });
+        self.pending_find = None;
+        self.results_picker.reset_input();
+        self.results_picker.reset_selection();
+        self.reset_preview_scroll();
+    }
+
+    /// Go back to the channel left by the last `drill_into`, restoring its
+    /// query and selecting the entry that was drilled into.
+    fn drill_back(&mut self) {
+        let Some(state) = self.drill_stack.pop() else {
+            return;
+        };
+        std::mem::replace(&mut self.channel, state.channel).shutdown();
+        // the filter might have changed since drilling into the entry
+        self.configure_channel();
+        self.channel.set_paused(false);
+        self.paused = false;
+        self.pending_find = None;
+        self.results_picker.input = Input::new(state.query.clone());
+        self.results_picker.reset_selection();
+        self.channel.find(&state.query);
+        self.set_preview_query(&state.query);
+        self.current_pattern = state.query;
+        self.reset_preview_scroll();
+        self.goto = Some(GotoTarget {
+            value: state.entry,
+            confirm: false,
+            fallback_to_query: false,
+        });
+    }
+
+    /// The share (in percent) of the space given to the preview panel.
+    fn preview_size(&self) -> u16 {
+        let size = self.config.ui.preview_panel.size.clamp(1, 99);
+        if self.panels_swapped {
+            100 - size
+        } else {
+            size
+        }
+    }
+
+    /// The number of rows of the input field.
+    fn input_rows(&self) -> u16 {
+        if self.multiline_input {
+            self.config.ui.input_bar.multiline_rows.max(1)
+        } else {
+            1
+        }
+    }
+
     fn reset_picker_input(&mut self) {
         match self.mode {
             Mode::Channel => self.results_picker.reset_input(),
@@ -248,6 +753,11 @@ impl Television {
         }
     }
 
+    /// The (possibly accelerated) step for a repeatable scrolling action.
+    fn scroll_step(&mut self, action: &Action) -> u16 {
+        self.scroll_accelerator.step(action, Instant::now())
+    }
+
     pub fn scroll_preview_down(&mut self, offset: u16) {
         if self.preview_scroll.is_none() {
             self.preview_scroll = Some(0);
@@ -267,6 +777,200 @@ impl Television {
             self.preview_scroll = Some(scroll.saturating_sub(offset));
         }
     }
+
+    /// A one-line description of the current state, used for debugging.
+    pub fn debug_summary(&self) -> String {
+        format!(
+            "mode: {:?}, query: {:?}, results: {}/{}, selected: {:?}",
+            self.mode,
+            self.current_pattern,
+            self.channel.result_count(),
+            self.channel.total_count(),
+            self.results_picker.selected(),
+        )
+    }
+
+    /// Only list the entries matching `filter` (or all of them with `None`),
+    /// whatever the query.
+    pub fn set_filter(&mut self, filter: Option<Regex>) {
+        self.channel.set_filter(filter.clone());
+        self.filter = filter;
+        self.reset_picker_selection();
+        self.reset_preview_scroll();
+    }
+
+    /// Apply the filter pattern being edited, unless it isn't a valid regex.
+    fn confirm_filter_pattern(&mut self) {
+        let Some(input) = self.filter_input.take() else {
+            return;
+        };
+        match input.value() {
+            "" => self.set_filter(None),
+            pattern => match Regex::new(pattern) {
+                Ok(filter) => self.set_filter(Some(filter)),
+                Err(e) => {
+                    // the last line holds the actual error, the first ones
+                    // point at its location in the pattern
+                    let error = e.to_string();
+                    self.set_status_message(format!(
+                        "Invalid filter pattern: {}",
+                        error
+                            .lines()
+                            .last()
+                            .unwrap_or_default()
+                            .trim_start_matches("error: ")
+                    ));
+                }
+            },
+        }
+    }
+
+    /// Whether a prompt (preview search or filter pattern) is being typed
+    /// into instead of the query.
+    fn editing_prompt(&self) -> bool {
+        self.preview_search.editing || self.filter_input.is_some()
+    }
+
+    /// Display a transient message at the bottom of the results panel.
+    pub fn set_status_message(&mut self, message: String) {
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    fn current_status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, since)| since.elapsed() < STATUS_MESSAGE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Write all currently matched entries to the configured export file.
+    ///
+    /// Entries are fetched from the channel in batches to avoid allocating
+    /// the whole result set at once.
+    ///
+    /// # Returns
+    /// The number of exported entries.
+    pub fn export_results(&mut self) -> Result<u32> {
+        let Some(path) = self.config.config.export_file.clone() else {
+            return Err(color_eyre::eyre::eyre!(
+                "No export file set (see `--export-file`)"
+            ));
+        };
+        let mut writer = BufWriter::new(File::create(&path)?);
+        let mut offset = 0;
+        loop {
+            let entries = self.channel.results(EXPORT_BATCH_SIZE, offset);
+            if entries.is_empty() {
+                break;
+            }
+            for entry in &entries {
+                writeln!(writer, "{}", entry.stdout_repr())?;
+            }
+            offset += u32::try_from(entries.len())?;
+            if offset >= self.channel.result_count() {
+                break;
+            }
+        }
+        writer.flush()?;
+        Ok(offset)
+    }
+
+    /// Send the selected entries to the selection pipe and report the
+    /// outcome in the status bar.
+    fn send_selection_to_pipe(&mut self) {
+        let Some(entries) = self.get_selected_entries(Some(Mode::Channel))
+        else {
+            return;
+        };
+        let Some(pipe) = self.selection_pipe.as_mut() else {
+            return;
+        };
+        let message = match pipe.send(entries.iter().map(Entry::stdout_repr)) {
+            Ok(()) => format!(
+                "Sent {} entries to {}",
+                entries.len(),
+                pipe.path().display()
+            ),
+            Err(e) => format!("{e:#}"),
+        };
+        self.set_status_message(message);
+    }
+
+    /// Replace the selection with the matched entries that aren't currently
+    /// selected.
+    ///
+    /// Selected entries that don't match the current query are deselected.
+    pub fn invert_selection(&mut self) -> Result<()> {
+        let previously_selected = self.channel.selected_entries().clone();
+        for entry in &previously_selected {
+            self.channel.toggle_selection(entry);
+        }
+        let mut offset = 0;
+        while offset < self.channel.result_count() {
+            let entries = self.channel.results(EXPORT_BATCH_SIZE, offset);
+            if entries.is_empty() {
+                break;
+            }
+            for entry in &entries {
+                if !previously_selected.contains(entry) {
+                    self.channel.toggle_selection(entry);
+                }
+            }
+            offset += u32::try_from(entries.len())?;
+        }
+        Ok(())
+    }
+
+    /// Find the index of the matched entry whose name is exactly `value`.
+    fn find_exact_match(&mut self, value: &str) -> Result<Option<usize>> {
+        let mut offset = 0;
+        while offset < self.channel.result_count() {
+            let entries = self.channel.results(EXPORT_BATCH_SIZE, offset);
+            if entries.is_empty() {
+                break;
+            }
+            if let Some(i) = entries.iter().position(|e| e.name == value) {
+                return Ok(Some(usize::try_from(offset)? + i));
+            }
+            offset += u32::try_from(entries.len())?;
+        }
+        Ok(None)
+    }
+
+    /// Handle a pending `--goto` request once the channel is done loading.
+    ///
+    /// If an entry exactly matches the requested value, it gets selected (and
+    /// optionally confirmed). Otherwise, the value is optionally used as the
+    /// query.
+    fn maybe_goto(&mut self) -> Result<()> {
+        if self.goto.is_none() || self.channel.loading() {
+            return Ok(());
+        }
+        // the last entries loaded might not be matched yet, in which case
+        // this is tried again on the next tick
+        self.channel.wait_for_matches(Duration::ZERO);
+        if self.channel.running() {
+            return Ok(());
+        }
+        let goto = self.goto.take().unwrap();
+        if let Some(index) = self.find_exact_match(&goto.value)? {
+            self.results_picker.select(Some(index));
+            self.results_picker.relative_select(Some(
+                index.min(self.results_area_height as usize),
+            ));
+            self.reset_preview_scroll();
+            if goto.confirm {
+                self.send_action(Action::SelectAndExit)?;
+            }
+        } else if goto.fallback_to_query {
+            self.results_picker.input = Input::new(goto.value.clone());
+            self.current_pattern = goto.value;
+            self.find(&self.current_pattern.clone());
+            self.reset_picker_selection();
+            self.reset_preview_scroll();
+        }
+        Ok(())
+    }
 }
 
 impl Television {
@@ -285,6 +989,112 @@ impl Television {
         Ok(())
     }
 
+    /// Send an action to the registered action handler, if any.
+    fn send_action(&self, action: Action) -> Result<()> {
+        if let Some(tx) = &self.action_tx {
+            tx.send(action)?;
+        }
+        Ok(())
+    }
+
+    /// Convert a key press into the action it triggers, using the
+    /// television's own keymap.
+    pub fn action_for_key(&self, key: Key) -> Action {
+        self.action_for_key_with(&self.keymap, key)
+    }
+
+    /// Convert a key press into the action it triggers given the current
+    /// state of the television (mode, preview search) and the given keymap.
+    pub fn action_for_key_with(&self, keymap: &Keymap, key: Key) -> Action {
+        // keys ending the preview search and filter pattern prompts
+        if self.preview_search.editing {
+            match key {
+                Key::Enter | Key::ShiftEnter => {
+                    return Action::ConfirmPreviewSearch
+                }
+                Key::Esc => return Action::CancelPreviewSearch,
+                _ => {}
+            }
+        }
+        if self.filter_input.is_some() {
+            match key {
+                Key::Enter | Key::ShiftEnter => {
+                    return Action::ConfirmFilterPattern
+                }
+                Key::Esc => return Action::CancelFilterPattern,
+                _ => {}
+            }
+        }
+        // key sequences take precedence over the actions of their last key
+        if !self.editing_prompt() {
+            if let Some(action) = keymap.sequence_action(self.mode, key) {
+                return action;
+            }
+        }
+        // newlines can only be inserted in the multi-line query
+        if key == Key::ShiftEnter
+            && self.multiline_input
+            && self.mode == Mode::Channel
+            && !self.editing_prompt()
+        {
+            return Action::AddInputChar('\n');
+        }
+        // text input events
+        match key {
+            Key::Backspace => return Action::DeletePrevChar,
+            Key::Ctrl('w') => return Action::DeletePrevWord,
+            Key::Delete => return Action::DeleteNextChar,
+            Key::Alt('d') => return Action::DeleteNextWord,
+            Key::Left => return Action::GoToPrevChar,
+            Key::Right => return Action::GoToNextChar,
+            Key::Alt('b') => return Action::GoToPrevWord,
+            Key::Alt('f') => return Action::GoToNextWord,
+            Key::Home | Key::Ctrl('a') => return Action::GoToInputStart,
+            Key::End | Key::Ctrl('e') => return Action::GoToInputEnd,
+            Key::Char(c) => return Action::AddInputChar(c),
+            _ => {}
+        }
+        // get action based on keybindings
+        keymap
+            .get(&self.mode)
+            .and_then(|keymap| {
+                keymap
+                    .get(&key)
+                    .or_else(|| keymap.get(&key.fallback()?))
+                    .cloned()
+            })
+            .unwrap_or(if let Key::Char(c) = key {
+                Action::AddInputChar(c)
+            } else {
+                Action::NoOp
+            })
+    }
+
+    /// Handle a key press: the action it triggers is applied and returned.
+    ///
+    /// This lets another application drive the television with its own
+    /// event loop and react to the returned action (e.g. `Action::Quit`).
+    /// Actions the television emits by itself, such as `Action::SelectAndExit`
+    /// when a selection is confirmed, are sent to the handler registered with
+    /// [`Television::register_action_handler`].
+    pub async fn handle_key(&mut self, key: Key) -> Result<Action> {
+        let action = self.action_for_key(key);
+        let mut next = self.update(action.clone()).await?;
+        while let Some(action) = next {
+            next = self.update(action).await?;
+        }
+        Ok(action)
+    }
+
+    /// Stop any process still producing entries for the current channel
+    /// (or the channels left by drilling into entries).
+    pub fn shutdown(&mut self) {
+        self.channel.shutdown();
+        for state in &self.drill_stack {
+            state.channel.shutdown();
+        }
+    }
+
     #[allow(clippy::unused_async)]
     /// Update the state of the component based on a received action.
     ///
@@ -294,33 +1104,65 @@ impl Television {
     /// # Returns
     /// * `Result<Option<Action>>` - An action to be processed or none.
     pub async fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if acts_on_results(&action) {
+            self.flush_pending_find();
+        }
         match action {
+            Action::ConfirmEntry(n) => {
+                if self.select_nth_entry(n) {
+                    return Ok(Some(Action::ConfirmSelection));
+                }
+            }
             // handle input actions
             Action::AddInputChar(_)
+            | Action::InsertString(_)
             | Action::DeletePrevChar
             | Action::DeletePrevWord
             | Action::DeleteNextChar
+            | Action::DeleteNextWord
+            | Action::ClearQuery
             | Action::GoToInputEnd
             | Action::GoToInputStart
             | Action::GoToNextChar
-            | Action::GoToPrevChar => {
-                let input = match self.mode {
-                    Mode::Channel => &mut self.results_picker.input,
-                    Mode::RemoteControl | Mode::SendToChannel => {
-                        &mut self.rc_picker.input
+            | Action::GoToPrevChar
+            | Action::GoToNextWord
+            | Action::GoToPrevWord => {
+                let editing_prompt = self.editing_prompt();
+                let input = if self.preview_search.editing {
+                    &mut self.preview_search.input
+                } else if let Some(input) = &mut self.filter_input {
+                    input
+                } else {
+                    match self.mode {
+                        Mode::Channel => &mut self.results_picker.input,
+                        Mode::RemoteControl | Mode::SendToChannel => {
+                            &mut self.rc_picker.input
+                        }
                     }
                 };
-                input
-                    .handle(convert_action_to_input_request(&action).unwrap());
+                if let Action::InsertString(text) = &action {
+                    for c in text.chars() {
+                        input.handle(InputRequest::InsertChar(c));
+                    }
+                } else {
+                    input.handle(
+                        convert_action_to_input_request(&action).unwrap(),
+                    );
+                }
                 match action {
                     Action::AddInputChar(_)
+                    | Action::InsertString(_)
                     | Action::DeletePrevChar
                     | Action::DeletePrevWord
-                    | Action::DeleteNextChar => {
+                    | Action::DeleteNextChar
+                    | Action::DeleteNextWord
+                    | Action::ClearQuery
+                        if !editing_prompt =>
+                    {
                         let new_pattern = input.value().to_string();
                         if new_pattern != self.current_pattern {
                             self.current_pattern.clone_from(&new_pattern);
-                            self.find(&new_pattern);
+                            self.debounced_find(&new_pattern);
                             self.reset_picker_selection();
                             self.reset_preview_scroll();
                         }
@@ -328,13 +1170,55 @@ impl Television {
                     _ => {}
                 }
             }
+            Action::PreviewSearch => {
+                if self.mode == Mode::Channel
+                    && self.preview_state.is_visible()
+                {
+                    self.preview_search.start();
+                }
+            }
+            Action::ConfirmPreviewSearch => {
+                self.preview_search.confirm();
+                self.jump_to_preview_match(true, true);
+            }
+            Action::CancelPreviewSearch => self.preview_search.cancel(),
+            Action::EditFilterPattern => {
+                if self.mode == Mode::Channel && !self.editing_prompt() {
+                    self.filter_input = Some(Input::new(
+                        self.filter
+                            .as_ref()
+                            .map(|filter| filter.as_str().to_string())
+                            .unwrap_or_default(),
+                    ));
+                }
+            }
+            Action::ConfirmFilterPattern => self.confirm_filter_pattern(),
+            Action::CancelFilterPattern => self.filter_input = None,
+            Action::PreviewSearchNext => {
+                self.jump_to_preview_match(true, false);
+            }
+            Action::PreviewSearchPrev => {
+                self.jump_to_preview_match(false, false);
+            }
             Action::SelectNextEntry => {
                 self.reset_preview_scroll();
-                self.select_next_entry(1);
+                let step = self.scroll_step(&action);
+                self.select_next_entry(step.into());
             }
             Action::SelectPrevEntry => {
                 self.reset_preview_scroll();
-                self.select_prev_entry(1);
+                let step = self.scroll_step(&action);
+                self.select_prev_entry(step.into());
+            }
+            Action::SelectNextPage
+                if self.panels_swapped && self.mode == Mode::Channel =>
+            {
+                self.scroll_preview_down(self.preview_pane_height);
+            }
+            Action::SelectPrevPage
+                if self.panels_swapped && self.mode == Mode::Channel =>
+            {
+                self.scroll_preview_up(self.preview_pane_height);
             }
             Action::SelectNextPage => {
                 self.reset_preview_scroll();
@@ -344,8 +1228,14 @@ impl Television {
                 self.reset_preview_scroll();
                 self.select_prev_entry(self.results_area_height);
             }
-            Action::ScrollPreviewDown => self.scroll_preview_down(1),
-            Action::ScrollPreviewUp => self.scroll_preview_up(1),
+            Action::ScrollPreviewDown => {
+                let step = self.scroll_step(&action);
+                self.scroll_preview_down(step);
+            }
+            Action::ScrollPreviewUp => {
+                let step = self.scroll_step(&action);
+                self.scroll_preview_up(step);
+            }
             Action::ScrollPreviewHalfPageDown => self.scroll_preview_down(20),
             Action::ScrollPreviewHalfPageUp => self.scroll_preview_up(20),
             Action::ToggleRemoteControl => match self.mode {
@@ -375,58 +1265,81 @@ impl Television {
                     }
                 }
             }
-            Action::ConfirmSelection => {
-                match self.mode {
-                    Mode::Channel => {
-                        self.action_tx
-                            .as_ref()
-                            .unwrap()
-                            .send(Action::SelectAndExit)?;
-                    }
-                    Mode::RemoteControl => {
-                        if let Some(entry) =
-                            self.get_selected_entry(Some(Mode::RemoteControl))
-                        {
-                            let new_channel = self
-                                .remote_control
-                                .zap(entry.name.as_str())?;
-                            // this resets the RC picker
-                            self.reset_picker_selection();
-                            self.reset_picker_input();
-                            self.remote_control.find(EMPTY_STRING);
-                            self.mode = Mode::Channel;
-                            self.change_channel(new_channel);
+            Action::InvertSelection => {
+                if matches!(self.mode, Mode::Channel) {
+                    self.invert_selection()?;
+                }
+            }
+            Action::ConfirmSelection => match self.mode {
+                Mode::Channel => {
+                    if self.channel.result_count() == 0
+                        && self.channel.selected_entries().is_empty()
+                    {
+                        match self.config.config.on_empty_confirm {
+                            OnEmptyConfirm::Abort => {
+                                self.send_action(Action::Quit)?;
+                            }
+                            OnEmptyConfirm::UseQuery => {
+                                self.send_action(Action::AbortWithQuery)?;
+                            }
+                            OnEmptyConfirm::Noop => {}
                         }
+                    } else if self.selection_pipe.is_some() {
+                        self.send_selection_to_pipe();
+                    } else {
+                        self.send_action(Action::SelectAndExit)?;
                     }
-                    Mode::SendToChannel => {
-                        if let Some(entry) =
-                            self.get_selected_entry(Some(Mode::RemoteControl))
-                        {
-                            let new_channel = self.channel.transition_to(
-                                entry.name.as_str().try_into().unwrap(),
-                            );
-                            self.reset_picker_selection();
-                            self.reset_picker_input();
-                            self.remote_control.find(EMPTY_STRING);
-                            self.mode = Mode::Channel;
-                            self.change_channel(new_channel);
-                        }
+                }
+                Mode::RemoteControl => {
+                    if let Some(entry) =
+                        self.get_selected_entry(Some(Mode::RemoteControl))
+                    {
+                        self.zap(&entry)?;
                     }
                 }
-            }
+                Mode::SendToChannel => {
+                    if let Some(entry) =
+                        self.get_selected_entry(Some(Mode::RemoteControl))
+                    {
+                        let new_channel = self.channel.transition_to(
+                            entry.name.as_str().try_into().unwrap(),
+                        );
+                        self.reset_picker_selection();
+                        self.reset_picker_input();
+                        self.remote_control.find(EMPTY_STRING);
+                        self.mode = Mode::Channel;
+                        self.change_channel(new_channel);
+                    }
+                }
+            },
             Action::CopyEntryToClipboard => {
                 if self.mode == Mode::Channel {
-                    if let Some(entries) = self.get_selected_entries(None) {
-                        let mut ctx = ClipboardContext::new().unwrap();
-                        ctx.set_contents(
-                            entries
-                                .iter()
-                                .map(|e| e.name.clone())
-                                .collect::<Vec<_>>()
-                                .join(" "),
-                        )
-                        .unwrap();
-                    }
+                    self.copy_selected_entries_to_clipboard(false);
+                }
+            }
+            Action::YankAndExit => {
+                // stay open on failure so that the error can be read
+                if self.mode == Mode::Channel
+                    && self.copy_selected_entries_to_clipboard(true)
+                {
+                    self.send_action(Action::SelectAndExit)?;
+                }
+            }
+            Action::ExportResults => {
+                if self.mode == Mode::Channel {
+                    let message = match self.export_results() {
+                        Ok(count) => format!(
+                            "Exported {count} entries to {}",
+                            self.config
+                                .config
+                                .export_file
+                                .as_ref()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_default()
+                        ),
+                        Err(e) => format!("Export failed: {e}"),
+                    };
+                    self.set_status_message(message);
                 }
             }
             Action::ToggleSendToChannel => match self.mode {
@@ -447,8 +1360,66 @@ impl Television {
                 self.config.ui.show_help_bar = !self.config.ui.show_help_bar;
             }
             Action::TogglePreview => {
-                self.config.ui.show_preview_panel =
-                    !self.config.ui.show_preview_panel;
+                // the panel isn't shown for channels without a preview, so
+                // toggling it there would silently flip the preference
+                if self.channel.has_preview() {
+                    self.preview_state.toggle();
+                }
+            }
+            Action::TogglePreviewWrap => {
+                self.config.ui.preview_panel.wrap =
+                    !self.config.ui.preview_panel.wrap;
+                // rendered previews are laid out for the previous setting
+                self.rendered_preview_cache.lock().unwrap().clear();
+                self.rewrap_preview_scroll();
+            }
+            Action::TogglePreviewTail => {
+                self.config.ui.preview_panel.tail =
+                    !self.config.ui.preview_panel.tail;
+                if self.config.ui.preview_panel.tail {
+                    // re-anchor the scroll on the next frame
+                    self.reset_preview_scroll();
+                }
+            }
+            Action::DrillInto if self.mode == Mode::Channel => {
+                self.drill_into();
+            }
+            Action::DrillBack if self.mode == Mode::Channel => {
+                self.drill_back();
+            }
+            Action::SwapPanels => self.panels_swapped = !self.panels_swapped,
+            Action::ToggleMultilineInput if self.mode == Mode::Channel => {
+                self.multiline_input = !self.multiline_input;
+                // a single-line query can't hold newlines
+                if !self.multiline_input && self.current_pattern.contains('\n')
+                {
+                    let pattern = self.current_pattern.replace('\n', " ");
+                    self.results_picker.input = Input::new(pattern.clone());
+                    self.find(&pattern);
+                    self.current_pattern = pattern;
+                }
+            }
+            Action::TogglePause => {
+                self.paused = !self.paused;
+                self.channel.set_paused(self.paused);
+            }
+            Action::ReloadSource if self.mode == Mode::Channel => {
+                self.paused = false;
+                self.channel.set_paused(false);
+                // the query is kept by the channel's matcher
+                self.channel.reload();
+                self.reset_picker_selection();
+                self.reset_preview_scroll();
+            }
+            // the query typed while paused is matched once resumed
+            Action::Tick if !self.paused => {
+                self.find_pending_if_due();
+                // with `--sync`, no results are drawn (which is what
+                // otherwise moves matching forward) until matching is done
+                if self.sync {
+                    self.channel.wait_for_matches(Duration::ZERO);
+                }
+                self.maybe_goto()?;
             }
             _ => {}
         }
@@ -464,18 +1435,39 @@ impl Television {
     /// # Returns
     /// * `Result<()>` - An Ok result or an error.
     pub fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
-        let selected_entry = self
-            .get_selected_entry(Some(Mode::Channel))
-            .unwrap_or(ENTRY_PLACEHOLDER);
+        let area = self.config.ui.margin.apply(area);
+        if self.sync {
+            if self.channel.loading() || self.channel.running() {
+                draw_loading_indicator(
+                    f,
+                    area,
+                    &self.spinner,
+                    &mut self.spinner_state,
+                    &self.colorscheme,
+                );
+                return Ok(());
+            }
+            self.sync = false;
+        }
+
+        let selected_entry = self.get_selected_entry(Some(Mode::Channel));
+        // the placeholder previewed until an entry gets selected mustn't
+        // decide the preview scroll of that entry
+        let has_selection = selected_entry.is_some();
+        let selected_entry = selected_entry.unwrap_or(ENTRY_PLACEHOLDER);
 
         let layout = Layout::build(
             &Dimensions::from(self.config.ui.ui_scale),
             area,
             !matches!(self.mode, Mode::Channel),
             self.config.ui.show_help_bar,
-            self.config.ui.show_preview_panel
+            self.config.ui.footer.is_some(),
+            self.preview_state.is_visible()
                 && !matches!(selected_entry.preview_type, PreviewType::None),
+            self.preview_size(),
+            self.input_rows(),
             self.config.ui.input_bar_position,
+            self.config.ui.preview_panel.position,
         );
 
         // help bar (metadata, keymaps, logo)
@@ -493,12 +1485,44 @@ impl Television {
             &self.colorscheme,
         );
 
-        self.results_area_height =
-            u32::from(layout.results.height.saturating_sub(2)); // 2 for the borders
-        self.preview_pane_height = match layout.preview_window {
-            Some(preview) => preview.height,
-            None => 0,
-        };
+        // footer
+        if let (Some(rect), Some(template)) =
+            (layout.footer, self.config.ui.footer.as_ref())
+        {
+            let bindings = self.config.keybindings.get(&self.mode);
+            let footer =
+                format_footer(template, &self.channel.name(), |action| {
+                    bindings.and_then(|b| b.get(action)).cloned()
+                });
+            draw_footer(f, rect, &footer, &self.colorscheme);
+        }
+
+        let results_panel = &self.config.ui.results_panel;
+        let secondary_line = results_panel
+            .secondary_line_template
+            .as_deref()
+            .map(|template| SecondaryLine {
+                template,
+                delimiter: results_panel
+                    .secondary_line_delimiter
+                    .as_deref()
+                    .unwrap_or(DEFAULT_DELIMITER),
+            });
+        let columns = (!results_panel.columns.is_empty()).then(|| Columns {
+            columns: &results_panel.columns,
+            delimiter: results_panel.column_delimiter.as_deref(),
+        });
+        // the results area height is expressed in entries rather than rows
+        // (see below for wrapped entries)
+        self.results_area_height = u32::from(
+            layout.results.height.saturating_sub(2) // 2 for the borders
+                / SecondaryLine::rows_per_entry(secondary_line.as_ref()),
+        );
+        (self.preview_pane_height, self.preview_pane_width) =
+            match layout.preview_window {
+                Some(preview) => (preview.height, preview.width),
+                None => (0, 0),
+            };
 
         // results list
         let result_count = self.channel.result_count();
@@ -510,7 +1534,19 @@ impl Television {
             self.results_area_height,
             u32::try_from(self.results_picker.offset())?,
         );
-        draw_results_list(
+        let status_message = match &self.filter_input {
+            Some(input) => Some(format!("edit filter: {}", input.value())),
+            None => self
+                .current_status_message()
+                .map(ToString::to_string)
+                .or_else(|| self.paused.then(|| String::from("paused")))
+                .or_else(|| {
+                    self.filter
+                        .as_ref()
+                        .map(|filter| format!("filter: {}", filter.as_str()))
+                }),
+        };
+        let fitting_entries = draw_results_list(
             f,
             layout.results,
             &entries,
@@ -538,41 +1574,139 @@ impl Television {
                 // just display the first keybinding
                 .unwrap()
                 .to_string(),
+            status_message.as_deref(),
+            secondary_line,
+            columns,
+            self.config.ui.results_panel.wrap,
+            &self.highlight_rules,
         )?;
+        // wrapped entries can take several rows, so fewer of them fit
+        if fitting_entries < entries.len() {
+            self.results_area_height = u32::try_from(fitting_entries)?;
+        }
 
         // input box
         draw_input_box(
             f,
             layout.input,
-            result_count,
-            self.channel.total_count(),
+            ResultCounts {
+                matched: result_count,
+                total: self.channel.total_count(),
+                selected: self.channel.selected_entries().len(),
+            },
+            self.config.ui.input_bar.count_template.as_deref(),
             &mut self.results_picker.input,
             &mut self.results_picker.state,
-            self.channel.running(),
+            // the spinner would keep spinning while paused
+            self.channel.running() && !self.paused,
             &self.spinner,
             &mut self.spinner_state,
             &self.colorscheme,
         )?;
 
-        if self.config.ui.show_preview_panel
+        if self.preview_state == PreviewState::Hidden
             && !matches!(selected_entry.preview_type, PreviewType::None)
         {
+            // keep the preview ready for when the panel is shown
+            let _ = self.preview(&selected_entry);
+        }
+
+        if self.preview_state.is_visible()
+            && !matches!(selected_entry.preview_type, PreviewType::None)
+        {
+            // preview all selected entries at once if the channel supports it
+            let selected_entry = match &selected_entry.preview_type {
+                PreviewType::Command(command)
+                    if self.channel.selected_entries().len() > 1 =>
+                {
+                    multi_preview_entry(
+                        command,
+                        &self
+                            .channel
+                            .selected_entries()
+                            .iter()
+                            .collect::<Vec<_>>(),
+                    )
+                }
+                _ => None,
+            }
+            .unwrap_or(selected_entry);
+
             // preview content
-            let maybe_preview = self.previewer.preview(&selected_entry);
+            let maybe_preview = self.preview(&selected_entry);
 
-            let _ = self.previewer.preview(&selected_entry);
+            let _ = self.preview(&selected_entry);
 
+            let target_position = selected_entry
+                .line_number
+                .map(|l| (u16::try_from(l).unwrap_or(0), None))
+                .or_else(|| match &selected_entry.preview_type {
+                    PreviewType::Command(command) => {
+                        resolve_position(command, &selected_entry)
+                    }
+                    _ => None,
+                })
+                .map(|(line, column)| {
+                    let header_lines =
+                        maybe_preview.as_ref().map_or(0, |p| p.header_lines);
+                    (line.saturating_add(header_lines), column)
+                });
             if let Some(preview) = &maybe_preview {
-                self.current_preview_total_lines = preview.total_lines;
-                // initialize preview scroll
-                self.maybe_init_preview_scroll(
-                    selected_entry
-                        .line_number
-                        .map(|l| u16::try_from(l).unwrap_or(0)),
-                    layout.preview_window.unwrap().height,
-                );
+                let height = layout.preview_window.unwrap().height;
+                if self.config.ui.preview_panel.tail {
+                    // tail mode wins over the entry's target line
+                    self.maybe_tail_preview_scroll(
+                        preview.total_lines,
+                        height,
+                    );
+                    self.current_preview_total_lines = preview.total_lines;
+                } else if has_selection {
+                    self.current_preview_total_lines = preview.total_lines;
+                    // initialize preview scroll
+                    self.maybe_init_preview_scroll(
+                        || {
+                            target_position.map(|(line, _)| line).or_else(
+                                || {
+                                    search_target_line(
+                                        &selected_entry,
+                                        preview,
+                                    )
+                                },
+                            )
+                        },
+                        height,
+                    );
+                }
             }
 
+            // the footer is recomputed on each frame to follow the scroll
+            // the search prompt replaces the footer while typing
+            let footer = if self.preview_search.editing {
+                Some(format!("/{}", self.preview_search.input.value()))
+            } else {
+                let footer =
+                    self.config.ui.preview_footer.as_ref().map(|template| {
+                        format_preview_footer(
+                            template,
+                            &selected_entry,
+                            self.preview_scroll.unwrap_or(0),
+                            self.current_preview_total_lines,
+                        )
+                    });
+                if self.config.ui.preview_panel.wrap {
+                    Some(match footer {
+                        Some(footer) => format!("{footer} {WRAP_INDICATOR}"),
+                        None => WRAP_INDICATOR.to_string(),
+                    })
+                } else {
+                    footer
+                }
+            };
+            let search_pattern = if self.preview_search.editing {
+                Some(self.preview_search.input.value())
+            } else {
+                self.preview_search.pattern.as_deref()
+            };
             draw_preview_content_block(
                 f,
                 layout.preview_window.unwrap(),
@@ -580,6 +1714,13 @@ impl Television {
                 &maybe_preview,
                 &self.rendered_preview_cache,
                 self.preview_scroll.unwrap_or(0),
+                self.config.ui.preview_panel.label.as_deref(),
+                self.config.ui.preview_panel.wrap,
+                self.config.ui.preview_panel.sanitize,
+                target_position
+                    .and_then(|(line, column)| Some((line, column?))),
+                footer.as_deref(),
+                search_pattern,
                 self.config.ui.use_nerd_font_icons,
                 &self.colorscheme,
             )?;
@@ -614,12 +1755,28 @@ impl Television {
 
     pub fn maybe_init_preview_scroll(
         &mut self,
-        target_line: Option<u16>,
+        target_line: impl FnOnce() -> Option<u16>,
         height: u16,
     ) {
         if self.preview_scroll.is_none() && !self.channel.running() {
             self.preview_scroll =
-                Some(target_line.unwrap_or(0).saturating_sub(height / 3));
+                Some(target_line().unwrap_or(0).saturating_sub(height / 3));
+        }
+    }
+
+    /// Scroll the preview so that its last line sits at the bottom of the
+    /// preview window whenever a new preview (or a new version of the
+    /// current one) arrives.
+    ///
+    /// Unlike the initial scroll, this doesn't wait for the channel to be
+    /// loaded since streaming sources never are.
+    fn maybe_tail_preview_scroll(&mut self, total_lines: u16, height: u16) {
+        if self.preview_scroll.is_none()
+            || total_lines != self.current_preview_total_lines
+        {
+            // 2 for the borders, 1 for the top padding
+            self.preview_scroll =
+                Some(total_lines.saturating_sub(height.saturating_sub(3)));
         }
     }
 }
@@ -766,3 +1923,250 @@ fn serialized_keys_for_actions(
         })
         .collect()
 }
+
+fn previewer_config(config: &Config) -> PreviewerConfig {
+    PreviewerConfig::from(config.previewers.clone())
+        .markdown(config.ui.preview_panel.render == PreviewRender::Markdown)
+        .show_meta(config.ui.preview_panel.show_meta)
+}
+
+fn scroll_accelerator(config: &Config) -> ScrollAccelerator {
+    let acceleration = &config.ui.scroll_acceleration;
+    ScrollAccelerator::new(
+        acceleration.steps.clone(),
+        acceleration.repeats_per_step,
+        Duration::from_millis(acceleration.reset_after_ms),
+    )
+}
+
+/// Compile the configured highlight rules, skipping (and logging) the
+/// invalid ones.
+fn highlight_rules(config: &Config) -> Vec<HighlightRule> {
+    config
+        .ui
+        .results_panel
+        .highlight_rules
+        .iter()
+        .filter_map(|rule| {
+            HighlightRule::new(rule)
+                .inspect_err(|e| warn!("{e:#}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// The line a search offset points at, i.e. the first line of the preview
+/// containing the offset's string (see `resolve_search`).
+fn search_target_line(entry: &Entry, preview: &Preview) -> Option<u16> {
+    let PreviewType::Command(command) = &entry.preview_type else {
+        return None;
+    };
+    let needle = resolve_search(command, entry)?;
+    let line = preview.content.find_first_line(&needle)?;
+    Some(line.saturating_add(1))
+}
+
+/// Whether the action uses the results of the query, in which case a
+/// debounced query must be matched before handling it.
+fn acts_on_results(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::ConfirmSelection
+            | Action::ConfirmEntry(_)
+            | Action::SelectAndExit
+            | Action::SelectPassthrough(_)
+            | Action::YankAndExit
+            | Action::CopyEntryToClipboard
+            | Action::ToggleSelectionDown
+            | Action::ToggleSelectionUp
+            | Action::InvertSelection
+            | Action::SelectNextEntry
+            | Action::SelectPrevEntry
+            | Action::SelectNextPage
+            | Action::SelectPrevPage
+            | Action::ExportResults
+            | Action::DrillInto
+            | Action::ToggleRemoteControl
+            | Action::ToggleSendToChannel
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::{cable, entry::PreviewCommand};
+
+    fn cable_channel(preview: bool) -> TelevisionChannel {
+        TelevisionChannel::Cable(cable::Channel::new(
+            "test",
+            "echo entry",
+            preview.then(|| PreviewCommand::new("echo {}", ":")),
+            cable::SourceOptions::default(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_goto_waits_for_the_source() {
+        let channel = TelevisionChannel::Cable(cable::Channel::new(
+            "test",
+            "sleep 0.5; echo a; echo b",
+            None,
+            cable::SourceOptions::default(),
+        ));
+        let mut television = Television::new(channel, Config::default(), None)
+            .with_goto(Some(GotoTarget {
+                value: "b".to_string(),
+                confirm: false,
+                fallback_to_query: false,
+            }));
+
+        television.update(Action::Tick).await.unwrap();
+        assert!(television.goto.is_some());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while television.goto.is_some() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+            television.update(Action::Tick).await.unwrap();
+        }
+        assert_eq!(television.results_picker.selected(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_shift_enter() {
+        let config = Config {
+            keybindings: toml::from_str(
+                r#"
+                [Channel]
+                confirm_selection = "enter"
+                "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        let mut television =
+            Television::new(cable_channel(false), config, None);
+        assert_eq!(
+            television.action_for_key(Key::ShiftEnter),
+            Action::ConfirmSelection
+        );
+
+        television
+            .update(Action::ToggleMultilineInput)
+            .await
+            .unwrap();
+        assert_eq!(
+            television.action_for_key(Key::ShiftEnter),
+            Action::AddInputChar('\n')
+        );
+        assert_eq!(
+            television.action_for_key(Key::Enter),
+            Action::ConfirmSelection
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reload_config() {
+        let mut television =
+            Television::new(cable_channel(false), Config::default(), None);
+        television.update(Action::ToggleHelp).await.unwrap();
+        television.update(Action::TogglePreviewWrap).await.unwrap();
+        let show_help_bar = television.config.ui.show_help_bar;
+        let preview_wrap = television.config.ui.preview_panel.wrap;
+
+        let mut config = Config {
+            keybindings: toml::from_str(
+                r#"
+                [Channel]
+                quit = "ctrl-q"
+                "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        config.ui.theme = "dracula".to_string();
+        television.reload_config(config);
+
+        assert_eq!(television.action_for_key(Key::Ctrl('q')), Action::Quit);
+        let dracula: Colorscheme = (&Theme::from_name("dracula")).into();
+        let default: Colorscheme = (&Theme::default()).into();
+        assert_eq!(
+            television.colorscheme.results.result_selected_bg,
+            dracula.results.result_selected_bg
+        );
+        assert_ne!(
+            television.colorscheme.results.result_selected_bg,
+            default.results.result_selected_bg
+        );
+        // runtime toggles survive the reload
+        assert_eq!(television.config.ui.show_help_bar, show_help_bar);
+        assert_eq!(television.config.ui.preview_panel.wrap, preview_wrap);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_entry() {
+        let config: Config =
+            toml::from_str(include_str!("../.config/config.toml")).unwrap();
+        let mut television =
+            Television::new(cable_channel(false), config, None);
+        television
+            .update(Action::ToggleRemoteControl)
+            .await
+            .unwrap();
+        television
+            .remote_control
+            .wait_for_matches(Duration::from_secs(5));
+        television.results_area_height = 3;
+
+        // digits are typed into the remote control's query, alt-digits
+        // switch to the listed channels
+        assert_eq!(
+            television.action_for_key(Key::Char('2')),
+            Action::AddInputChar('2')
+        );
+        assert_eq!(
+            television.action_for_key(Key::Alt('2')),
+            Action::ConfirmEntry(2)
+        );
+        assert_eq!(
+            television
+                .update(Action::ConfirmEntry(u16::MAX))
+                .await
+                .unwrap(),
+            None
+        );
+
+        // entries are counted from the start of the list, wherever it's
+        // scrolled
+        television.rc_picker.select(Some(5));
+        television.rc_picker.relative_select(Some(2));
+        assert!(television.rc_picker.offset() > 0);
+        assert_eq!(
+            television.update(Action::ConfirmEntry(2)).await.unwrap(),
+            Some(Action::ConfirmSelection)
+        );
+        assert_eq!(television.rc_picker.selected(), Some(1));
+        assert_eq!(television.rc_picker.offset(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_preview_toggle_survives_channel_switches() {
+        let mut television =
+            Television::new(cable_channel(true), Config::default(), None);
+        assert_eq!(television.preview_state, PreviewState::Visible);
+
+        television.update(Action::TogglePreview).await.unwrap();
+        assert_eq!(television.preview_state, PreviewState::Hidden);
+        television.change_channel(cable_channel(true));
+        assert_eq!(television.preview_state, PreviewState::Hidden);
+
+        // toggling has no effect in a channel without a preview
+        television.change_channel(cable_channel(false));
+        television.update(Action::TogglePreview).await.unwrap();
+        assert_eq!(television.preview_state, PreviewState::Hidden);
+
+        television.change_channel(cable_channel(true));
+        assert_eq!(television.preview_state, PreviewState::Hidden);
+        television.update(Action::TogglePreview).await.unwrap();
+        assert_eq!(television.preview_state, PreviewState::Visible);
+    }
+}
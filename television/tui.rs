@@ -5,7 +5,12 @@ use std::{
 
 use color_eyre::Result;
 use crossterm::{
-    cursor, execute,
+    cursor,
+    event::{
+        DisableFocusChange, DisableMouseCapture, EnableFocusChange,
+        EnableMouseCapture,
+    },
+    execute,
     terminal::{
         disable_raw_mode, enable_raw_mode, is_raw_mode_enabled,
         EnterAlternateScreen, LeaveAlternateScreen,
@@ -15,6 +20,16 @@ use ratatui::{backend::CrosstermBackend, layout::Size};
 use tokio::task::JoinHandle;
 use tracing::debug;
 
+/// Resets the scroll region (DECSTBM) to the whole screen and performs a
+/// soft terminal reset (DECSTR) so that no state leaks into the host shell.
+const TERMINAL_RESET_SEQUENCE: &str = "\x1b[r\x1b[!p";
+
+/// Write the escape sequences restoring the terminal to a clean state.
+fn reset_terminal_state<W: Write>(writer: &mut W) -> std::io::Result<()> {
+    writer.write_all(TERMINAL_RESET_SEQUENCE.as_bytes())?;
+    writer.flush()
+}
+
 #[allow(dead_code)]
 pub struct Tui<W>
 where
@@ -22,6 +37,7 @@ where
 {
     pub task: JoinHandle<()>,
     pub frame_rate: f64,
+    pub mouse: bool,
     pub terminal: ratatui::Terminal<CrosstermBackend<W>>,
 }
 
@@ -34,6 +50,7 @@ where
         Ok(Self {
             task: tokio::spawn(async {}),
             frame_rate: 60.0,
+            mouse: false,
             terminal: ratatui::Terminal::new(CrosstermBackend::new(writer))?,
         })
     }
@@ -43,6 +60,11 @@ where
         self
     }
 
+    pub fn mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+
     pub fn size(&self) -> Result<Size> {
         Ok(self.terminal.size()?)
     }
@@ -53,6 +75,10 @@ where
         execute!(buffered_stderr, EnterAlternateScreen)?;
         self.terminal.clear()?;
         execute!(buffered_stderr, cursor::Hide)?;
+        execute!(buffered_stderr, EnableFocusChange)?;
+        if self.mouse {
+            execute!(buffered_stderr, EnableMouseCapture)?;
+        }
         Ok(())
     }
 
@@ -62,8 +88,15 @@ where
 
             disable_raw_mode()?;
             let mut buffered_stderr = LineWriter::new(stderr());
-            execute!(buffered_stderr, cursor::Show)?;
+            if self.mouse {
+                execute!(buffered_stderr, DisableMouseCapture)?;
+            }
+            execute!(buffered_stderr, DisableFocusChange)?;
             execute!(buffered_stderr, LeaveAlternateScreen)?;
+            reset_terminal_state(&mut buffered_stderr)?;
+            // the soft reset already shows the cursor but not all terminals
+            // support it
+            execute!(buffered_stderr, cursor::Show)?;
         }
 
         Ok(())
@@ -113,3 +146,15 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_terminal_state() {
+        let mut buffer = Vec::new();
+        reset_terminal_state(&mut buffer).unwrap();
+        assert_eq!(buffer, b"\x1b[r\x1b[!p");
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use color_eyre::eyre::{eyre, Result};
+use copypasta::{ClipboardContext, ClipboardProvider};
+use tracing::debug;
+
+/// Copy `content` to the system clipboard.
+///
+/// With `persistent`, the content is handed over to a clipboard tool
+/// (`pbcopy`, `wl-copy`, `xclip` or `xsel`) when one is available: on X11 and
+/// Wayland, the clipboard belongs to the process that set it and would be
+/// emptied as soon as television exits.
+pub fn copy_to_clipboard(content: &str, persistent: bool) -> Result<()> {
+    if persistent {
+        for tool in clipboard_tools() {
+            if copy_with_tool(tool, content) {
+                return Ok(());
+            }
+        }
+    }
+    // the reason is logged since it is usually too long for a status line
+    let mut ctx = ClipboardContext::new().map_err(|e| {
+        debug!("Failed to access the clipboard: {e}");
+        eyre!("Failed to access the clipboard")
+    })?;
+    ctx.set_contents(content.to_string()).map_err(|e| {
+        debug!("Failed to copy to the clipboard: {e}");
+        eyre!("Failed to copy to the clipboard")
+    })
+}
+
+/// The commands holding the clipboard on their own that may be used on this
+/// system, in order of preference.
+fn clipboard_tools() -> Vec<&'static [&'static str]> {
+    let mut tools: Vec<&[&str]> = Vec::new();
+    if cfg!(target_os = "macos") {
+        tools.push(&["pbcopy"]);
+    }
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        tools.push(&["wl-copy"]);
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        tools.push(&["xclip", "-selection", "clipboard"]);
+        tools.push(&["xsel", "--clipboard", "--input"]);
+    }
+    tools
+}
+
+/// Feed `content` to the given clipboard command, returning whether it
+/// succeeded.
+fn copy_with_tool(tool: &[&str], content: &str) -> bool {
+    let Ok(mut child) = Command::new(tool[0])
+        .args(&tool[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(content.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
@@ -1,19 +1,98 @@
-use std::process::Command;
+use std::process::{Child, Command};
 
+use super::env_file::env_file_vars;
+
+/// Build a command running its argument through the platform's shell (`sh -c`
+/// on unix, `cmd /C` on Windows).
+///
+/// The variables of the env file, if one was loaded, are added to the
+/// command's environment.
+///
+/// Spawning may still fail if the shell can't be found, which callers should
+/// report rather than panic on.
 #[cfg(not(windows))]
 pub fn shell_command() -> Command {
     let mut cmd = Command::new("sh");
 
-    cmd.arg("-c");
+    cmd.arg("-c").envs(env_file_vars());
 
     cmd
 }
 
 #[cfg(windows)]
 pub fn shell_command() -> Command {
-    let mut cmd = Command::new("cmd");
+    // `COMSPEC` points to the command interpreter even when `cmd` isn't in
+    // the `PATH`
+    let mut cmd = Command::new(
+        std::env::var_os("COMSPEC").unwrap_or_else(|| "cmd".into()),
+    );
 
-    cmd.arg("/c");
+    cmd.arg("/C").envs(env_file_vars());
 
     cmd
 }
+
+/// Run the command in its own process group, so that the processes it
+/// spawns (e.g. every stage of a pipeline) can be signalled along with it
+/// with [`signal_process_group`].
+pub fn with_process_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+}
+
+/// Send `signal` to the process group led by `child` (see
+/// [`with_process_group`]).
+#[cfg(unix)]
+pub fn signal_process_group(child: &Child, signal: libc::c_int) {
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: killpg has no memory safety requirements
+        unsafe {
+            libc::killpg(pid, signal);
+        }
+    }
+}
+
+/// Kill `child` along with the processes of its process group (see
+/// [`with_process_group`]) and wait for it to exit.
+pub fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    signal_process_group(child, libc::SIGKILL);
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_command_echo() {
+        let output = shell_command().arg("echo hello").output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim_end(),
+            "hello"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_kill_process_group_kills_pipeline() {
+        let marker = std::env::temp_dir()
+            .join(format!("tv-test-process-group-{}", std::process::id()));
+        let mut child =
+            with_process_group(shell_command().arg(format!(
+                "sleep 1 | (sleep 1; touch {})",
+                marker.display()
+            )))
+            .spawn()
+            .unwrap();
+        kill_process_group(&mut child);
+        std::thread::sleep(std::time::Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+}
@@ -0,0 +1,140 @@
+use std::{collections::HashMap, path::Path, sync::OnceLock};
+
+use color_eyre::eyre::{eyre, Context, Result};
+
+/// Parse the contents of a dotenv-style file into a list of variables.
+///
+/// Each non-empty line that isn't a comment should be of the form
+/// `[export ]KEY=VALUE` where `VALUE` may be:
+/// - single-quoted (taken literally)
+/// - double-quoted (supporting `\n`, `\t`, `\"` and `\\` escapes)
+/// - unquoted (trimmed, with anything after ` #` treated as a comment)
+///
+/// # Example
+/// ```
+/// use television::utils::env_file::parse_env_file;
+///
+/// let vars = parse_env_file("# a comment\nexport API_URL=\"https://example.com\"\n").unwrap();
+///
+/// assert_eq!(vars, vec![("API_URL".to_string(), "https://example.com".to_string())]);
+/// ```
+pub fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            parse_line(line)
+                .ok_or_else(|| eyre!("Invalid line {line_number}: {line:?}"))
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<(String, String)> {
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty()
+        || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    Some((key.to_string(), parse_value(value.trim())?))
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        let (value, _) = rest.split_once('\'')?;
+        return Some(value.to_string());
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut parsed = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(parsed),
+                '\\' => parsed.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                }),
+                _ => parsed.push(c),
+            }
+        }
+        // unterminated quote
+        return None;
+    }
+    let value = match value.find(" #") {
+        Some(index) => &value[..index],
+        None => value,
+    };
+    Some(value.trim_end().to_string())
+}
+
+/// The variables loaded from the env file, if any.
+static ENV_FILE_VARS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Load the variables of the given dotenv-style file so that they are passed
+/// to all spawned commands (see [`env_file_vars`]).
+///
+/// The process environment itself is left untouched. This can only be done
+/// once, further calls are ignored.
+pub fn load_env_file(path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path).wrap_err_with(|| {
+        format!("Failed to read env file {}", path.display())
+    })?;
+    let _ = ENV_FILE_VARS.set(parse_env_file(&content)?.into_iter().collect());
+    Ok(())
+}
+
+/// The variables loaded with [`load_env_file`], to be added to the
+/// environment of spawned commands.
+pub fn env_file_vars(
+) -> impl Iterator<Item = (&'static String, &'static String)> {
+    ENV_FILE_VARS.get().into_iter().flatten()
+}
+
+/// Look up a variable in the env file, falling back to the process
+/// environment.
+pub fn env_var(name: &str) -> Option<String> {
+    ENV_FILE_VARS
+        .get()
+        .and_then(|vars| vars.get(name))
+        .cloned()
+        .or_else(|| std::env::var(name).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = r#"
+# comment
+FOO=bar
+export TOKEN = 'se#cr"et'
+GREETING="hello \"world\"\n"
+URL=https://example.com/#anchor # trailing comment
+EMPTY=
+"#;
+        assert_eq!(
+            parse_env_file(content).unwrap(),
+            vec![
+                ("FOO".to_string(), "bar".to_string()),
+                ("TOKEN".to_string(), "se#cr\"et".to_string()),
+                ("GREETING".to_string(), "hello \"world\"\n".to_string()),
+                ("URL".to_string(), "https://example.com/#anchor".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_env_file_invalid() {
+        assert!(parse_env_file("NOT A VARIABLE").is_err());
+        assert!(parse_env_file("KEY=\"unterminated").is_err());
+        assert!(parse_env_file("BAD-KEY=value").is_err());
+    }
+}
@@ -1,11 +1,12 @@
 use rustc_hash::FxHashSet;
 use std::fmt::Debug;
-use std::fs::File;
+use std::fs::{File, Metadata};
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
 
 use ignore::{overrides::Override, types::TypesBuilder, WalkBuilder};
 use lazy_static::lazy_static;
@@ -101,6 +102,86 @@ pub fn get_file_size(path: &Path) -> Option<u64> {
     std::fs::metadata(path).ok().map(|m| m.len())
 }
 
+/// A dimmed line describing the file at `path` (permissions, size and
+/// modification time) followed by an empty line, or `None` if `path` isn't a
+/// file or directory.
+pub fn file_metadata_header(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let mut fields = vec![format_permissions(&metadata)];
+    if !metadata.is_dir() {
+        fields.push(format_size(metadata.len()));
+    }
+    if let Some(secs) = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+    {
+        fields.push(format_timestamp(secs.as_secs()));
+    }
+    Some(format!("\x1b[2m{}\x1b[0m\n\n", fields.join("  ")))
+}
+
+#[cfg(unix)]
+fn format_permissions(metadata: &Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+    let mut permissions =
+        String::from(if metadata.is_dir() { "d" } else { "-" });
+    for (i, c) in "rwxrwxrwx".chars().enumerate() {
+        permissions.push(if mode & (1 << (8 - i)) == 0 { '-' } else { c });
+    }
+    permissions
+}
+
+#[cfg(not(unix))]
+fn format_permissions(metadata: &Metadata) -> String {
+    if metadata.permissions().readonly() {
+        String::from("read-only")
+    } else {
+        String::from("read-write")
+    }
+}
+
+/// Format a size in bytes with binary units (e.g. `1.5 KiB`).
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes;
+    let mut unit = 0;
+    while size >= 1024 * 1024 && unit < UNITS.len() - 1 {
+        size /= 1024;
+        unit += 1;
+    }
+    // one decimal, computed with integers
+    let tenths = (size * 10 / 1024) % 10;
+    format!("{}.{tenths} {}", size / 1024, UNITS[unit])
+}
+
+/// Format a UNIX timestamp as a UTC date and time (e.g. `2024-05-01 12:34
+/// UTC`).
+fn format_timestamp(secs: u64) -> String {
+    let days = i64::try_from(secs / 86_400).unwrap_or(i64::MAX);
+    let minutes = secs % 86_400 / 60;
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
 #[derive(Debug)]
 pub enum FileType {
     Text,
@@ -480,3 +561,24 @@ lazy_static! {
     .copied()
     .collect();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(10 * 1024 * 1024), "10.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp(1_714_566_840), "2024-05-01 12:34 UTC");
+    }
+}
@@ -341,6 +341,19 @@ impl Input {
         })
     }
 
+    /// Get the index of the line holding the cursor, and that line as an
+    /// input whose cursor is at the same position within the line.
+    pub fn cursor_line(&self) -> (usize, Input) {
+        let before: String = self.value.chars().take(self.cursor).collect();
+        let row = before.matches('\n').count();
+        let line = self.value.split('\n').nth(row).unwrap_or_default();
+        let column = before.rsplit('\n').next().unwrap_or_default();
+        (
+            row,
+            Input::new(line.to_string()).with_cursor(column.chars().count()),
+        )
+    }
+
     /// Get the scroll position with account for multi space characters.
     pub fn visual_scroll(&self, width: usize) -> usize {
         let scroll = self.visual_cursor().max(width) - width;
@@ -493,6 +506,116 @@ mod tests {
         assert_eq!(input.cursor(), 1);
     }
 
+    #[test]
+    fn go_to_prev_word() {
+        let mut input: Input = TEXT.into();
+
+        let req = InputRequest::GoToPrevWord;
+        let resp = input.handle(req);
+
+        assert_eq!(
+            resp,
+            Some(StateChanged {
+                value: false,
+                cursor: true,
+            })
+        );
+
+        assert_eq!(input.value(), "first second, third.");
+        assert_eq!(input.cursor(), 14);
+
+        input.handle(req);
+        assert_eq!(input.cursor(), 6);
+
+        input.handle(req);
+        assert_eq!(input.cursor(), 0);
+
+        let resp = input.handle(req);
+        assert_eq!(resp, None);
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn go_to_next_word() {
+        let mut input = Input::from(TEXT).with_cursor(0);
+
+        let req = InputRequest::GoToNextWord;
+        let resp = input.handle(req);
+
+        assert_eq!(
+            resp,
+            Some(StateChanged {
+                value: false,
+                cursor: true,
+            })
+        );
+
+        assert_eq!(input.value(), "first second, third.");
+        assert_eq!(input.cursor(), 6);
+
+        input.handle(req);
+        assert_eq!(input.cursor(), 14);
+
+        input.handle(req);
+        assert_eq!(input.cursor(), TEXT.chars().count());
+
+        let resp = input.handle(req);
+        assert_eq!(resp, None);
+        assert_eq!(input.cursor(), TEXT.chars().count());
+    }
+
+    #[test]
+    fn delete_next_word() {
+        let mut input = Input::from(TEXT).with_cursor(0);
+
+        let req = InputRequest::DeleteNextWord;
+        let resp = input.handle(req);
+
+        assert_eq!(
+            resp,
+            Some(StateChanged {
+                value: true,
+                cursor: false,
+            })
+        );
+
+        assert_eq!(input.value(), "second, third.");
+        assert_eq!(input.cursor(), 0);
+
+        let mut input = input.with_cursor(8);
+        input.handle(req);
+        assert_eq!(input.value(), "second, ");
+        assert_eq!(input.cursor(), 8);
+
+        let resp = input.handle(req);
+        assert_eq!(resp, None);
+        assert_eq!(input.value(), "second, ");
+    }
+
+    #[test]
+    fn word_moves_with_unicode_chars() {
+        let mut input: Input = "¡héllo wörld¡".into();
+
+        input.handle(InputRequest::GoToPrevWord);
+        assert_eq!(input.cursor(), 7);
+
+        input.handle(InputRequest::GoToPrevWord);
+        assert_eq!(input.cursor(), 1);
+
+        input.handle(InputRequest::GoToNextWord);
+        assert_eq!(input.cursor(), 7);
+
+        input.handle(InputRequest::GoToStart);
+        input.handle(InputRequest::GoToNextChar);
+        input.handle(InputRequest::DeleteNextWord);
+        assert_eq!(input.value(), "¡wörld¡");
+        assert_eq!(input.cursor(), 1);
+
+        input.handle(InputRequest::DeleteNextWord);
+        assert_eq!(input.value(), "¡");
+        assert_eq!(input.cursor(), 1);
+    }
+
     #[test]
     fn remove_unicode_chars() {
         let mut input: Input = "¡test¡".into();
@@ -564,6 +687,31 @@ mod tests {
         assert_eq!(input.cursor(), 2);
     }
 
+    #[test]
+    fn cursor_line() {
+        let input: Input = "first\nsecond line\nthird".into();
+
+        let (row, line) = input.clone().with_cursor(3).cursor_line();
+        assert_eq!(row, 0);
+        assert_eq!(line.value(), "first");
+        assert_eq!(line.cursor(), 3);
+
+        let (row, line) = input.clone().with_cursor(8).cursor_line();
+        assert_eq!(row, 1);
+        assert_eq!(line.value(), "second line");
+        assert_eq!(line.cursor(), 2);
+
+        let (row, line) = input.cursor_line();
+        assert_eq!(row, 2);
+        assert_eq!(line.value(), "third");
+        assert_eq!(line.cursor(), 5);
+
+        let (row, line) = Input::from("query\n").cursor_line();
+        assert_eq!(row, 1);
+        assert_eq!(line.value(), "");
+        assert_eq!(line.cursor(), 0);
+    }
+
     #[test]
     fn multispace_characters() {
         let input: Input = "Ｈｅｌｌｏ, ｗｏｒｌｄ!".into();
@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{Context, Result};
+
+/// The file (in the data directory) holding the last selected entry of each
+/// channel.
+const LAST_SELECTIONS_FILE: &str = "last_selections.toml";
+
+fn last_selections_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LAST_SELECTIONS_FILE)
+}
+
+fn read_last_selections(path: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The raw value of the entry last selected in the given channel, if any.
+pub fn load_last_selection(data_dir: &Path, channel: &str) -> Option<String> {
+    read_last_selections(&last_selections_path(data_dir)).remove(channel)
+}
+
+/// Remember `value` as the entry last selected in the given channel.
+pub fn save_last_selection(
+    data_dir: &Path,
+    channel: &str,
+    value: &str,
+) -> Result<()> {
+    let path = last_selections_path(data_dir);
+    let mut selections = read_last_selections(&path);
+    selections.insert(channel.to_string(), value.to_string());
+    std::fs::write(&path, toml::to_string(&selections)?).wrap_err_with(|| {
+        format!("Failed to write last selection to {}", path.display())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_selection_round_trip() {
+        let data_dir = std::env::temp_dir()
+            .join(format!("tv-last-selection-{}", std::process::id()));
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        assert_eq!(load_last_selection(&data_dir, "files"), None);
+        save_last_selection(&data_dir, "files", "src/main.rs").unwrap();
+        save_last_selection(&data_dir, "git-log", "1a2b3c \"fix\"").unwrap();
+        save_last_selection(&data_dir, "files", "Cargo.toml").unwrap();
+        assert_eq!(
+            load_last_selection(&data_dir, "files").as_deref(),
+            Some("Cargo.toml")
+        );
+        assert_eq!(
+            load_last_selection(&data_dir, "git-log").as_deref(),
+            Some("1a2b3c \"fix\"")
+        );
+
+        std::fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
@@ -1,11 +1,17 @@
 pub mod cache;
+pub mod clipboard;
 pub mod command;
+pub mod env_file;
 pub mod files;
 pub mod indices;
 pub mod input;
+pub mod last_selection;
 pub mod metadata;
+pub mod pipe;
+pub mod scroll;
 pub mod shell;
 pub mod stdin;
 pub mod strings;
 pub mod syntax;
+pub mod terminal;
 pub mod threads;
@@ -0,0 +1,192 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SendError, Sender};
+
+use color_eyre::eyre::{eyre, Result};
+use tracing::warn;
+
+/// Sends confirmed selections to another process through a named pipe, one
+/// entry per line, without exiting television.
+///
+/// The pipe is opened on the first selection sent. Writing is done on a
+/// dedicated thread so that a slow (or stopped) reader doesn't hold up the
+/// UI. If the reading end goes away, the pipe is reopened on the next
+/// selection. On non-Unix platforms, entries are appended to the given file
+/// instead.
+#[derive(Debug)]
+pub struct SelectionPipe {
+    path: PathBuf,
+    writer: Option<Sender<String>>,
+}
+
+impl SelectionPipe {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            writer: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Queue the given entries to be written to the pipe, each followed by a
+    /// newline.
+    pub fn send(
+        &mut self,
+        entries: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<()> {
+        let mut buffer = String::new();
+        for entry in entries {
+            buffer.push_str(entry.as_ref());
+            buffer.push('\n');
+        }
+        if let Some(writer) = &self.writer {
+            match writer.send(buffer) {
+                Ok(()) => return Ok(()),
+                // the writer stopped because the reader is gone, try again
+                // with a fresh pipe
+                Err(SendError(unsent)) => buffer = unsent,
+            }
+        }
+        let writer = self
+            .writer
+            .insert(spawn_writer(open(&self.path)?, self.path.clone()));
+        writer
+            .send(buffer)
+            .map_err(|_| eyre!("Failed to write to {}", self.path.display()))
+    }
+}
+
+/// Write the buffers received on the returned channel to `file` until the
+/// channel is closed or a write fails.
+fn spawn_writer(mut file: File, path: PathBuf) -> Sender<String> {
+    let (sender, receiver) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        for buffer in receiver {
+            if let Err(e) = file.write_all(buffer.as_bytes()) {
+                warn!("Failed to write to {}: {e}", path.display());
+                return;
+            }
+        }
+    });
+    sender
+}
+
+/// Open the pipe without blocking when no process is reading from it.
+///
+/// Writes do block once the pipe is open (on the writer thread), so that a
+/// slow reader or a selection larger than the pipe's buffer doesn't tear
+/// lines.
+#[cfg(unix)]
+fn open(path: &Path) -> Result<File> {
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let file = OpenOptions::new()
+        .append(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)
+        .map_err(|e| match e.raw_os_error() {
+            Some(libc::ENXIO) => {
+                eyre!("No process is reading from {}", path.display())
+            }
+            _ => open_error(path, &e),
+        })?;
+    let fd = file.as_raw_fd();
+    // SAFETY: `fd` is a valid descriptor owned by `file`
+    let cleared = unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        flags >= 0
+            && libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) >= 0
+    };
+    if !cleared {
+        return Err(open_error(path, &io::Error::last_os_error()));
+    }
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn open(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| open_error(path, &e))
+}
+
+fn open_error(path: &Path, e: &io::Error) -> color_eyre::Report {
+    eyre!("Failed to open {}: {e}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_selection_pipe_appends_entries() {
+        let path = std::env::temp_dir()
+            .join(format!("tv-selection-pipe-{}", std::process::id()));
+        File::create(&path).unwrap();
+
+        let mut pipe = SelectionPipe::new(&path);
+        pipe.send(["a", "b"]).unwrap();
+        pipe.send(["c"]).unwrap();
+        drop(pipe);
+
+        // the entries are written in the background
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while std::fs::read_to_string(&path).unwrap() != "a\nb\nc\n"
+            && Instant::now() < deadline
+        {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb\nc\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_selection_pipe_waits_for_slow_reader() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = std::env::temp_dir()
+            .join(format!("tv-selection-fifo-{}", std::process::id()));
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        // SAFETY: `c_path` is a valid NUL-terminated path
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+        let reader_path = path.clone();
+        let (sent, wait_for_sent) = mpsc::channel();
+        let reader = std::thread::spawn(move || {
+            let mut file = File::open(reader_path).unwrap();
+            // only read once everything was sent: sending mustn't wait for
+            // the reader
+            wait_for_sent.recv().unwrap();
+            let mut content = String::new();
+            io::Read::read_to_string(&mut file, &mut content).unwrap();
+            content
+        });
+
+        // more than a pipe's buffer holds
+        let entries = (0..100_000).map(|i| format!("entry {i}"));
+        let mut pipe = SelectionPipe::new(&path);
+        while pipe.send(["ready"]).is_err() {
+            std::thread::yield_now();
+        }
+        pipe.send(entries.clone()).unwrap();
+        drop(pipe);
+        sent.send(()).unwrap();
+
+        let expected = std::iter::once(String::from("ready"))
+            .chain(entries)
+            .map(|entry| entry + "\n")
+            .collect::<String>();
+        assert_eq!(reader.join().unwrap(), expected);
+        std::fs::remove_file(path).unwrap();
+    }
+}
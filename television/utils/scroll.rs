@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use crate::action::Action;
+
+/// Increases the step of repeated scrolling actions.
+///
+/// Each time the same action is repeated within `reset_after` of the previous
+/// one, a repeat is counted, and every `repeats_per_step` repeats the step
+/// moves on to the next value of `steps`. Pausing or switching to another
+/// action goes back to the first step.
+#[derive(Debug)]
+pub struct ScrollAccelerator {
+    steps: Vec<u16>,
+    repeats_per_step: u16,
+    reset_after: Duration,
+    last: Option<(Action, Instant)>,
+    repeats: usize,
+}
+
+impl ScrollAccelerator {
+    pub fn new(
+        steps: Vec<u16>,
+        repeats_per_step: u16,
+        reset_after: Duration,
+    ) -> Self {
+        Self {
+            steps,
+            repeats_per_step,
+            reset_after,
+            last: None,
+            repeats: 0,
+        }
+    }
+
+    /// The step to use for `action` happening at `now`.
+    pub fn step(&mut self, action: &Action, now: Instant) -> u16 {
+        match &self.last {
+            Some((last_action, last_time))
+                if last_action == action
+                    && now.duration_since(*last_time) <= self.reset_after =>
+            {
+                self.repeats += 1;
+            }
+            _ => self.repeats = 0,
+        }
+        self.last = Some((action.clone(), now));
+        let index = self.repeats / usize::from(self.repeats_per_step.max(1));
+        self.steps
+            .get(index)
+            .or(self.steps.last())
+            .copied()
+            .unwrap_or(1)
+            .max(1)
+    }
+}
+
+impl Default for ScrollAccelerator {
+    fn default() -> Self {
+        Self::new(vec![1], 1, Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_acceleration() {
+        let mut accelerator = ScrollAccelerator::new(
+            vec![1, 5, 20],
+            2,
+            Duration::from_millis(100),
+        );
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let down = Action::ScrollPreviewDown;
+
+        let steps = (0..7)
+            .map(|i| accelerator.step(&down, at(i * 50)))
+            .collect::<Vec<_>>();
+        assert_eq!(steps, [1, 1, 5, 5, 20, 20, 20]);
+
+        // a pause resets the acceleration
+        assert_eq!(accelerator.step(&down, at(1000)), 1);
+        assert_eq!(accelerator.step(&down, at(1050)), 1);
+        assert_eq!(accelerator.step(&down, at(1100)), 5);
+
+        // so does changing direction
+        assert_eq!(accelerator.step(&Action::ScrollPreviewUp, at(1150)), 1);
+    }
+}
@@ -7,17 +7,20 @@ pub enum Shell {
     Fish,
     PowerShell,
     Cmd,
+    Tmux,
 }
 
 const COMPLETION_ZSH: &str = include_str!("shell/completion.zsh");
 const COMPLETION_BASH: &str = include_str!("shell/completion.bash");
 const COMPLETION_FISH: &str = include_str!("shell/completion.fish");
+const POPUP_TMUX: &str = include_str!("shell/popup.tmux");
 
 pub fn completion_script(shell: Shell) -> Result<&'static str> {
     match shell {
         Shell::Bash => Ok(COMPLETION_BASH),
         Shell::Zsh => Ok(COMPLETION_ZSH),
         Shell::Fish => Ok(COMPLETION_FISH),
+        Shell::Tmux => Ok(POPUP_TMUX),
         _ => color_eyre::eyre::bail!(
             "This shell is not yet supported: {:?}",
             shell
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use lazy_static::lazy_static;
 
 /// Returns the index of the next character boundary in the given string.
@@ -543,6 +545,22 @@ pub fn shrink_with_ellipsis(s: &str, max_length: usize) -> String {
     format!("{first_half}…{second_half}")
 }
 
+/// Quote a string so that it can safely be used as a single shell word.
+///
+/// Strings that don't need quoting are returned unchanged. Strings that can't
+/// be quoted (i.e. containing a nul byte) are returned as is.
+///
+/// # Examples
+/// ```
+/// use television::utils::strings::shell_quote;
+///
+/// assert_eq!(shell_quote("foo/bar.rs"), "foo/bar.rs");
+/// assert_eq!(shell_quote("my file.rs"), "'my file.rs'");
+/// ```
+pub fn shell_quote(s: &str) -> String {
+    shlex::try_quote(s).map_or_else(|_| s.to_string(), Cow::into_owned)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -804,4 +822,12 @@ mod tests {
         test_preprocess_line("Hello, World!\u{FEFF}", "Hello, World!␀");
         test_preprocess_line(&"a".repeat(400), &"a".repeat(300));
     }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("file.txt"), "file.txt");
+        assert_eq!(shell_quote("my file.txt"), "'my file.txt'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote(""), "''");
+    }
 }
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+/// How long to keep discarding the terminal's answer once the query timed
+/// out, so that a late answer isn't read as user input.
+#[cfg(unix)]
+const LATE_ANSWER_GRACE: Duration = Duration::from_millis(100);
+
+/// Query the terminal for its background color (using OSC 11).
+///
+/// A primary device attributes request (DA1), which virtually all terminals
+/// answer, is sent right after so that we know when to stop reading if the
+/// terminal doesn't support OSC 11.
+///
+/// Returns `None` if the terminal doesn't report its background color within
+/// the given timeout.
+#[cfg(unix)]
+pub fn query_background_color(timeout: Duration) -> Option<(u16, u16, u16)> {
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::time::Instant;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    enable_raw_mode().ok()?;
+    let mut response = Vec::new();
+    let query = tty
+        .write_all(b"\x1b]11;?\x07\x1b[c")
+        .and_then(|()| tty.flush());
+    let answered = query.is_ok()
+        && read_answer(&tty, &mut response, Instant::now() + timeout);
+    if query.is_ok() && !answered {
+        // drain what arrives shortly after, or it would be read as keys
+        read_answer(&tty, &mut response, Instant::now() + LATE_ANSWER_GRACE);
+    }
+    let _ = disable_raw_mode();
+    if answered {
+        parse_background_color_response(&response)
+    } else {
+        None
+    }
+}
+
+/// Read the terminal's answer into `response` until the end of the DA1
+/// answer or the deadline, whichever comes first.
+///
+/// Returns whether the whole answer was read.
+#[cfg(unix)]
+fn read_answer(
+    mut tty: &std::fs::File,
+    response: &mut Vec<u8>,
+    deadline: std::time::Instant,
+) -> bool {
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    // read byte by byte not to consume keys typed after the answer
+    let mut byte = [0; 1];
+    while !is_da1_response_end(response) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        let mut fds = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = libc::c_int::try_from(remaining.as_millis().max(1))
+            .unwrap_or(libc::c_int::MAX);
+        // SAFETY: `fds` is a valid pollfd for the duration of the call
+        match unsafe { libc::poll(&mut fds, 1, timeout_ms) } {
+            0 => return false,
+            n if n < 0 => {
+                if std::io::Error::last_os_error().kind()
+                    != std::io::ErrorKind::Interrupted
+                {
+                    return false;
+                }
+            }
+            _ => match tty.read(&mut byte) {
+                Ok(0) | Err(_) => return false,
+                Ok(_) => response.push(byte[0]),
+            },
+        }
+    }
+    true
+}
+
+#[cfg(not(unix))]
+pub fn query_background_color(_timeout: Duration) -> Option<(u16, u16, u16)> {
+    None
+}
+
+/// Whether the response ends with the answer to the DA1 request, e.g.
+/// `\x1b[?62;22c`.
+fn is_da1_response_end(response: &[u8]) -> bool {
+    response.ends_with(b"c")
+        && response
+            .windows(3)
+            .rposition(|w| w == b"\x1b[?")
+            .is_some_and(|start| {
+                response[start + 3..response.len() - 1]
+                    .iter()
+                    .all(|b| b.is_ascii_digit() || *b == b';')
+            })
+}
+
+/// Parse the terminal's answer to an OSC 11 query, e.g.
+/// `\x1b]11;rgb:1e1e/1e1e/2e2e\x07`, into 16-bit color components.
+pub fn parse_background_color_response(
+    response: &[u8],
+) -> Option<(u16, u16, u16)> {
+    let response = String::from_utf8_lossy(response);
+    let start = response.find("]11;rgb:")? + "]11;rgb:".len();
+    let mut components = response[start..]
+        .split(['/', '\x07', '\x1b'])
+        .take(3)
+        .map(parse_color_component);
+    Some((
+        components.next()??,
+        components.next()??,
+        components.next()??,
+    ))
+}
+
+/// Parse a color component of 1 to 4 hex digits, scaled to 16 bits.
+fn parse_color_component(component: &str) -> Option<u16> {
+    if component.is_empty() || component.len() > 4 {
+        return None;
+    }
+    let value = u32::from_str_radix(component, 16).ok()?;
+    let max = (1u32 << (4 * component.len())) - 1;
+    u16::try_from(value * 0xffff / max).ok()
+}
+
+/// Whether the given background color is light, based on its perceived
+/// luminance.
+pub fn is_light_color((r, g, b): (u16, u16, u16)) -> bool {
+    let luminance =
+        0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+    luminance > f64::from(u16::MAX) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_background_color_response() {
+        assert_eq!(
+            parse_background_color_response(
+                b"\x1b]11;rgb:ffff/8080/0000\x07\x1b[?62;22c"
+            ),
+            Some((0xffff, 0x8080, 0))
+        );
+        // two-digit components and ST terminator
+        assert_eq!(
+            parse_background_color_response(b"\x1b]11;rgb:ff/80/00\x1b\\"),
+            Some((0xffff, 0x8080, 0))
+        );
+        // the terminal only answered the DA1 request
+        assert_eq!(parse_background_color_response(b"\x1b[?62;22c"), None);
+    }
+
+    #[test]
+    fn test_is_da1_response_end() {
+        assert!(is_da1_response_end(b"\x1b]11;rgb:0/0/0\x07\x1b[?62;22c"));
+        assert!(!is_da1_response_end(b"\x1b]11;rgb:0/0/0\x07"));
+        assert!(!is_da1_response_end(b"\x1b]11;rgb:c"));
+    }
+
+    #[test]
+    fn test_is_light_color() {
+        assert!(is_light_color((0xffff, 0xffff, 0xffff)));
+        assert!(is_light_color((0xfdfd, 0xf6f6, 0xe3e3)));
+        assert!(!is_light_color((0x1e1e, 0x1e1e, 0x2e2e)));
+    }
+}